@@ -0,0 +1,270 @@
+//! Endpoints served, each mapped onto a [`MetroRail`] or [`MetroBus`] method.
+//!
+//! | Path | Query | Method |
+//! | --- | --- | --- |
+//! | `/rail/lines` | | [`MetroRail::lines`] |
+//! | `/rail/stations` | `line` | [`MetroRail::stations_on`] |
+//! | `/rail/stations/{station}` | | [`MetroRail::station_information`] |
+//! | `/rail/stations/{station}/predictions` | | [`MetroRail::next_trains`] |
+//! | `/rail/stations/{station}/timings` | | [`MetroRail::timings`] |
+//! | `/rail/stations/{station}/parking` | | [`MetroRail::parking_information`] |
+//! | `/rail/entrances` | `lat`, `lon`, `radius` | [`MetroRail::entrances`] |
+//! | `/rail/positions` | | [`MetroRail::positions`] |
+//! | `/rail/routes` | | [`MetroRail::routes`] |
+//! | `/rail/circuits` | | [`MetroRail::circuits`] |
+//! | `/rail/incidents` | `station` | [`MetroRail::incidents_at`] |
+//! | `/rail/elevator-incidents` | `station` | [`MetroRail::elevator_and_escalator_incidents_at`] |
+//! | `/rail/path` | `from`, `to` | [`MetroRail::path_from`] |
+//! | `/rail/fares` | `from`, `to` | [`MetroRail::station_to_station`] |
+//! | `/bus/routes` | | [`MetroBus::routes`] |
+//! | `/bus/routes/{route}/path` | `date` | [`MetroBus::path`] |
+//! | `/bus/routes/{route}/schedule` | `date`, `variations` | [`MetroBus::route_schedule`] |
+//! | `/bus/stops` | `lat`, `lon`, `radius` | [`MetroBus::stops`] |
+//! | `/bus/stops/{stop}/predictions` | | [`MetroBus::next_buses`] |
+//! | `/bus/stops/{stop}/schedule` | `date` | [`MetroBus::stop_schedule`] |
+//! | `/bus/positions` | `route`, `lat`, `lon`, `radius` | [`MetroBus::positions_along`] |
+//! | `/bus/incidents` | `route` | [`MetroBus::incidents_along`] |
+//!
+//! Dates are `YYYY-MM-DD`. A point is given by all of `lat`, `lon`, and `radius` (in meters), or none of them.
+mod responses;
+mod tests;
+
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::{collections::HashMap, convert::TryFrom, str::FromStr};
+use wmata::{
+    error::Error, Date, Line, MetroBus, MetroRail, RadiusAtLatLong, Route, Station, Stop, Wmata,
+};
+
+/// A request for a single WMATA API call.
+#[derive(Debug, PartialEq)]
+pub enum Endpoint {
+    Lines,
+    Stations(Option<Line>),
+    Station(Station),
+    NextTrains(Station),
+    Timings(Station),
+    Parking(Station),
+    Entrances(RadiusAtLatLong),
+    TrainPositions,
+    StandardRoutes,
+    Circuits,
+    RailIncidents(Option<Station>),
+    ElevatorIncidents(Option<Station>),
+    Path(Station, Station),
+    Fares(Option<Station>, Option<Station>),
+    Routes,
+    RoutePath(Route, Option<Date>),
+    RouteSchedule(Route, Option<Date>, bool),
+    Stops(Option<RadiusAtLatLong>),
+    NextBuses(Stop),
+    StopSchedule(Stop, Option<Date>),
+    BusPositions(Option<Route>, Option<RadiusAtLatLong>),
+    BusIncidents(Option<Route>),
+}
+
+/// Why a request doesn't map onto an endpoint.
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    NotFound,
+    BadRequest(String),
+}
+
+impl Endpoint {
+    /// The endpoint for a request `path` and `query` string.
+    pub fn parse(path: &str, query: Option<&str>) -> Result<Endpoint, Rejection> {
+        let segments: Vec<&str> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let query = Query::parse(query.unwrap_or(""));
+
+        let endpoint = match segments.as_slice() {
+            ["rail", "lines"] => Endpoint::Lines,
+            ["rail", "stations"] => Endpoint::Stations(query.get("line")?),
+            ["rail", "stations", station] => Endpoint::Station(parse("station", station)?),
+            ["rail", "stations", station, "predictions"] => {
+                Endpoint::NextTrains(parse("station", station)?)
+            }
+            ["rail", "stations", station, "timings"] => {
+                Endpoint::Timings(parse("station", station)?)
+            }
+            ["rail", "stations", station, "parking"] => {
+                Endpoint::Parking(parse("station", station)?)
+            }
+            ["rail", "entrances"] => {
+                Endpoint::Entrances(query.radius_at_lat_long()?.ok_or_else(|| {
+                    Rejection::BadRequest("lat, lon, and radius are required".to_string())
+                })?)
+            }
+            ["rail", "positions"] => Endpoint::TrainPositions,
+            ["rail", "routes"] => Endpoint::StandardRoutes,
+            ["rail", "circuits"] => Endpoint::Circuits,
+            ["rail", "incidents"] => Endpoint::RailIncidents(query.get("station")?),
+            ["rail", "elevator-incidents"] => Endpoint::ElevatorIncidents(query.get("station")?),
+            ["rail", "path"] => Endpoint::Path(query.require("from")?, query.require("to")?),
+            ["rail", "fares"] => Endpoint::Fares(query.get("from")?, query.get("to")?),
+            ["bus", "routes"] => Endpoint::Routes,
+            ["bus", "routes", route, "path"] => {
+                Endpoint::RoutePath(parse("route", route)?, query.date()?)
+            }
+            ["bus", "routes", route, "schedule"] => Endpoint::RouteSchedule(
+                parse("route", route)?,
+                query.date()?,
+                query.get("variations")?.unwrap_or(false),
+            ),
+            ["bus", "stops"] => Endpoint::Stops(query.radius_at_lat_long()?),
+            ["bus", "stops", stop, "predictions"] => Endpoint::NextBuses(Stop::new(stop)),
+            ["bus", "stops", stop, "schedule"] => {
+                Endpoint::StopSchedule(Stop::new(stop), query.date()?)
+            }
+            ["bus", "positions"] => {
+                Endpoint::BusPositions(query.get("route")?, query.radius_at_lat_long()?)
+            }
+            ["bus", "incidents"] => Endpoint::BusIncidents(query.get("route")?),
+            _ => return Err(Rejection::NotFound),
+        };
+
+        Ok(endpoint)
+    }
+
+    /// Calls the WMATA API, returning the response re-serialized as JSON with snake_case fields.
+    /// See [`responses`].
+    pub async fn fetch(&self, client: &Wmata) -> Result<Vec<u8>, Error> {
+        let (rail, bus): (&MetroRail, &MetroBus) = (client.rail(), client.bus());
+
+        match self {
+            Endpoint::Lines => json(responses::Lines::from(rail.lines().await?)),
+            Endpoint::Stations(line) => {
+                json(responses::Stations::from(rail.stations_on(*line).await?))
+            }
+            Endpoint::Station(station) => json(responses::StationInformation::from(
+                rail.station_information(*station).await?,
+            )),
+            Endpoint::NextTrains(station) => json(responses::RailPredictions::from(
+                rail.next_trains(*station).await?,
+            )),
+            Endpoint::Timings(station) => json(responses::StationTimings::from(
+                rail.timings(*station).await?,
+            )),
+            Endpoint::Parking(station) => json(responses::StationsParking::from(
+                rail.parking_information(*station).await?,
+            )),
+            Endpoint::Entrances(radius_at_lat_long) => json(responses::StationEntrances::from(
+                rail.entrances(*radius_at_lat_long).await?,
+            )),
+            Endpoint::TrainPositions => {
+                json(responses::TrainPositions::from(rail.positions().await?))
+            }
+            Endpoint::StandardRoutes => json(responses::StandardRoutes::from(rail.routes().await?)),
+            Endpoint::Circuits => json(responses::TrackCircuits::from(rail.circuits().await?)),
+            Endpoint::RailIncidents(station) => json(responses::RailIncidents::from(
+                rail.incidents_at(*station).await?,
+            )),
+            Endpoint::ElevatorIncidents(station) => {
+                json(responses::ElevatorAndEscalatorIncidents::from(
+                    rail.elevator_and_escalator_incidents_at(*station).await?,
+                ))
+            }
+            Endpoint::Path(from, to) => json(responses::PathBetweenStations::from(
+                rail.path_from(*from, *to).await?,
+            )),
+            Endpoint::Fares(from, to) => json(responses::StationToStationInfos::from(
+                rail.station_to_station(*from, *to).await?,
+            )),
+            Endpoint::Routes => json(responses::Routes::from(bus.routes().await?)),
+            Endpoint::RoutePath(route, date) => {
+                json(responses::PathDetails::from(bus.path(*route, *date).await?))
+            }
+            Endpoint::RouteSchedule(route, date, including_variations) => {
+                json(responses::RouteSchedule::from(
+                    bus.route_schedule(*route, *date, *including_variations)
+                        .await?,
+                ))
+            }
+            Endpoint::Stops(radius_at_lat_long) => json(responses::Stops::from(
+                bus.stops(*radius_at_lat_long).await?,
+            )),
+            Endpoint::NextBuses(stop) => json(responses::Predictions::from(
+                bus.next_buses(Stop::new(&stop.0)).await?,
+            )),
+            Endpoint::StopSchedule(stop, date) => json(responses::StopSchedule::from(
+                bus.stop_schedule(Stop::new(&stop.0), *date).await?,
+            )),
+            Endpoint::BusPositions(route, radius_at_lat_long) => {
+                json(responses::BusPositions::from(
+                    bus.positions_along(*route, *radius_at_lat_long).await?,
+                ))
+            }
+            Endpoint::BusIncidents(route) => json(responses::Incidents::from(
+                bus.incidents_along(*route).await?,
+            )),
+        }
+    }
+}
+
+fn json<T: Serialize>(response: T) -> Result<Vec<u8>, Error> {
+    serde_json::to_vec(&response).map_err(|err| Error::new(err.to_string()))
+}
+
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, Rejection> {
+    value
+        .parse()
+        .map_err(|_| Rejection::BadRequest(format!("invalid {}: {}", name, value)))
+}
+
+/// Query string parameters. Values are codes, numbers, and dates, so aren't percent-decoded.
+struct Query<'a>(HashMap<&'a str, &'a str>);
+
+impl<'a> Query<'a> {
+    fn parse(query: &'a str) -> Self {
+        Query(
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let mut pair = pair.splitn(2, '=');
+                    (pair.next().unwrap_or(""), pair.next().unwrap_or(""))
+                })
+                .collect(),
+        )
+    }
+
+    fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, Rejection> {
+        self.0.get(name).map(|value| parse(name, value)).transpose()
+    }
+
+    fn require<T: FromStr>(&self, name: &str) -> Result<T, Rejection> {
+        self.get(name)?
+            .ok_or_else(|| Rejection::BadRequest(format!("{} is required", name)))
+    }
+
+    fn date(&self) -> Result<Option<Date>, Rejection> {
+        self.0
+            .get("date")
+            .map(|value| {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| Rejection::BadRequest(format!("invalid date: {}", value)))?;
+
+                Ok(Date::new(
+                    u16::try_from(date.year())
+                        .map_err(|_| Rejection::BadRequest(format!("invalid date: {}", value)))?,
+                    date.month() as u8,
+                    date.day() as u8,
+                ))
+            })
+            .transpose()
+    }
+
+    fn radius_at_lat_long(&self) -> Result<Option<RadiusAtLatLong>, Rejection> {
+        match (self.get("lat")?, self.get("lon")?, self.get("radius")?) {
+            (Some(latitude), Some(longitude), Some(radius)) => {
+                Ok(Some(RadiusAtLatLong::new(radius, latitude, longitude)))
+            }
+            (None, None, None) => Ok(None),
+            _ => Err(Rejection::BadRequest(
+                "lat, lon, and radius must be given together".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,1074 @@
+//! Responses served, with snake_case fields.
+//!
+//! Each mirrors the library response of the same name field for field, so the server's schema
+//! stays the same however the library's types are (de)serialized. Dates are `YYYY-MM-DDTHH:mm:ss`, Eastern, and times of day `HHmm`.
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use serde::{Serialize, Serializer};
+use wmata::{
+    bus::client::responses as bus,
+    rail::{
+        client::responses as rail,
+        unit::{UnitStatus, UnitType},
+    },
+    Line, Route, Station, Stop,
+};
+
+/// Converts each of `items`.
+fn all<T, U: From<T>>(items: Box<[T]>) -> Vec<U> {
+    items.into_vec().into_iter().map(U::from).collect()
+}
+
+fn date_time<S: Serializer>(
+    date_time: &DateTime<FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date_time.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+fn optional_date_time<S: Serializer>(
+    date_time: &Option<DateTime<FixedOffset>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match date_time {
+        Some(date_time) => self::date_time(date_time, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn hour_minute<S: Serializer>(time: &Option<NaiveTime>, serializer: S) -> Result<S::Ok, S::Error> {
+    match time {
+        Some(time) => serializer.serialize_str(&time.format("%H%M").to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Serialize)]
+pub struct Lines {
+    lines: Vec<LineResponse>,
+}
+
+impl From<rail::Lines> for Lines {
+    fn from(lines: rail::Lines) -> Self {
+        Lines {
+            lines: all(lines.lines),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LineResponse {
+    line: Line,
+    display_name: String,
+    start_station: Station,
+    end_station: Station,
+    first_internal_destination: Option<Station>,
+    second_internal_destination: Option<Station>,
+}
+
+impl From<rail::LineResponse> for LineResponse {
+    fn from(line: rail::LineResponse) -> Self {
+        LineResponse {
+            line: line.line,
+            display_name: line.display_name,
+            start_station: line.start_station,
+            end_station: line.end_station,
+            first_internal_destination: line.first_internal_destination,
+            second_internal_destination: line.second_internal_destination,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationEntrances {
+    entrances: Vec<StationEntrance>,
+}
+
+impl From<rail::StationEntrances> for StationEntrances {
+    fn from(entrances: rail::StationEntrances) -> Self {
+        StationEntrances {
+            entrances: all(entrances.entrances),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationEntrance {
+    description: String,
+    id: String,
+    latitude: f64,
+    longitude: f64,
+    name: String,
+    first_station: Station,
+    second_station: Option<Station>,
+}
+
+impl From<rail::StationEntrance> for StationEntrance {
+    fn from(entrance: rail::StationEntrance) -> Self {
+        StationEntrance {
+            description: entrance.description,
+            id: entrance.id,
+            latitude: entrance.latitude,
+            longitude: entrance.longitude,
+            name: entrance.name,
+            first_station: entrance.first_station,
+            second_station: entrance.second_station,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrainPositions {
+    train_positions: Vec<TrainPosition>,
+}
+
+impl From<rail::TrainPositions> for TrainPositions {
+    fn from(positions: rail::TrainPositions) -> Self {
+        TrainPositions {
+            train_positions: all(positions.train_positions),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrainPosition {
+    train_id: String,
+    train_number: String,
+    car_count: i32,
+    direction_number: i32,
+    circuit_id: i32,
+    destination_station: Option<Station>,
+    line: Option<Line>,
+    seconds_at_location: i32,
+    service_type: String,
+}
+
+impl From<rail::TrainPosition> for TrainPosition {
+    fn from(position: rail::TrainPosition) -> Self {
+        TrainPosition {
+            train_id: position.train_id,
+            train_number: position.train_number,
+            car_count: position.car_count,
+            direction_number: position.direction_number,
+            circuit_id: position.circuit_id,
+            destination_station: position.destination_station,
+            line: position.line,
+            seconds_at_location: position.seconds_at_location,
+            service_type: position.service_type,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StandardRoutes {
+    standard_routes: Vec<StandardRoute>,
+}
+
+impl From<rail::StandardRoutes> for StandardRoutes {
+    fn from(routes: rail::StandardRoutes) -> Self {
+        StandardRoutes {
+            standard_routes: all(routes.standard_routes),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StandardRoute {
+    line: Line,
+    track_number: i32,
+    track_circuits: Vec<TrackCircuitWithStation>,
+}
+
+impl From<rail::StandardRoute> for StandardRoute {
+    fn from(route: rail::StandardRoute) -> Self {
+        StandardRoute {
+            line: route.line,
+            track_number: route.track_number,
+            track_circuits: all(route.track_circuits),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrackCircuitWithStation {
+    sequence_number: i32,
+    circuit_id: i32,
+    station: Option<Station>,
+}
+
+impl From<rail::TrackCircuitWithStation> for TrackCircuitWithStation {
+    fn from(circuit: rail::TrackCircuitWithStation) -> Self {
+        TrackCircuitWithStation {
+            sequence_number: circuit.sequence_number,
+            circuit_id: circuit.circuit_id,
+            station: circuit.station,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrackCircuits {
+    track_circuits: Vec<TrackCircuit>,
+}
+
+impl From<rail::TrackCircuits> for TrackCircuits {
+    fn from(circuits: rail::TrackCircuits) -> Self {
+        TrackCircuits {
+            track_circuits: all(circuits.track_circuits),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrackCircuit {
+    track: i32,
+    circuit_id: i32,
+    neighbors: Vec<TrackNeighbor>,
+}
+
+impl From<rail::TrackCircuit> for TrackCircuit {
+    fn from(circuit: rail::TrackCircuit) -> Self {
+        TrackCircuit {
+            track: circuit.track,
+            circuit_id: circuit.circuit_id,
+            neighbors: all(circuit.neighbors),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrackNeighbor {
+    neighbor_type: String,
+    circuit_ids: Box<[i32]>,
+}
+
+impl From<rail::TrackNeighbor> for TrackNeighbor {
+    fn from(neighbor: rail::TrackNeighbor) -> Self {
+        TrackNeighbor {
+            neighbor_type: neighbor.neighbor_type,
+            circuit_ids: neighbor.circuit_ids,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ElevatorAndEscalatorIncidents {
+    incidents: Vec<ElevatorAndEscalatorIncident>,
+}
+
+impl From<rail::ElevatorAndEscalatorIncidents> for ElevatorAndEscalatorIncidents {
+    fn from(incidents: rail::ElevatorAndEscalatorIncidents) -> Self {
+        ElevatorAndEscalatorIncidents {
+            incidents: all(incidents.incidents),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ElevatorAndEscalatorIncident {
+    unit_name: String,
+    unit_type: UnitType,
+    unit_status: Option<UnitStatus>,
+    station: Station,
+    station_name: String,
+    location_description: String,
+    symptom_code: Option<String>,
+    #[serde(serialize_with = "hour_minute")]
+    time_out_of_service: Option<NaiveTime>,
+    symptom_description: String,
+    display_order: f64,
+    #[serde(serialize_with = "date_time")]
+    date_out_of_service: DateTime<FixedOffset>,
+    #[serde(serialize_with = "date_time")]
+    date_updated: DateTime<FixedOffset>,
+    #[serde(serialize_with = "optional_date_time")]
+    estimated_return_to_service: Option<DateTime<FixedOffset>>,
+}
+
+impl From<rail::ElevatorAndEscalatorIncident> for ElevatorAndEscalatorIncident {
+    fn from(incident: rail::ElevatorAndEscalatorIncident) -> Self {
+        ElevatorAndEscalatorIncident {
+            unit_name: incident.unit_name,
+            unit_type: incident.unit_type,
+            unit_status: incident.unit_status,
+            station: incident.station,
+            station_name: incident.station_name,
+            location_description: incident.location_description,
+            symptom_code: incident.symptom_code,
+            time_out_of_service: incident.time_out_of_service,
+            symptom_description: incident.symptom_description,
+            display_order: incident.display_order,
+            date_out_of_service: incident.date_out_of_service,
+            date_updated: incident.date_updated,
+            estimated_return_to_service: incident.estimated_return_to_service,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RailIncidents {
+    incidents: Vec<RailIncident>,
+}
+
+impl From<rail::RailIncidents> for RailIncidents {
+    fn from(incidents: rail::RailIncidents) -> Self {
+        RailIncidents {
+            incidents: all(incidents.incidents),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RailIncident {
+    incident_id: String,
+    description: String,
+    start_location_full_name: Option<String>,
+    end_location_full_name: Option<String>,
+    passenger_delay: f64,
+    delay_severity: Option<String>,
+    incident_type: String,
+    emergency_text: Option<String>,
+    lines_affected: String,
+    #[serde(serialize_with = "date_time")]
+    date_updated: DateTime<FixedOffset>,
+}
+
+impl From<rail::RailIncident> for RailIncident {
+    fn from(incident: rail::RailIncident) -> Self {
+        RailIncident {
+            incident_id: incident.incident_id,
+            description: incident.description,
+            start_location_full_name: incident.start_location_full_name,
+            end_location_full_name: incident.end_location_full_name,
+            passenger_delay: incident.passenger_delay,
+            delay_severity: incident.delay_severity,
+            incident_type: incident.incident_type,
+            emergency_text: incident.emergency_text,
+            lines_affected: incident.lines_affected,
+            date_updated: incident.date_updated,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationToStationInfos {
+    station_to_station_infos: Vec<StationToStationInfo>,
+}
+
+impl From<rail::StationToStationInfos> for StationToStationInfos {
+    fn from(infos: rail::StationToStationInfos) -> Self {
+        StationToStationInfos {
+            station_to_station_infos: all(infos.station_to_station_infos),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationToStationInfo {
+    composite_miles: f64,
+    destination_station: Station,
+    rail_fare: RailFare,
+    rail_time: i32,
+    source_station: Station,
+}
+
+impl From<rail::StationToStationInfo> for StationToStationInfo {
+    fn from(info: rail::StationToStationInfo) -> Self {
+        StationToStationInfo {
+            composite_miles: info.composite_miles,
+            destination_station: info.destination_station,
+            rail_fare: info.rail_fare.into(),
+            rail_time: info.rail_time,
+            source_station: info.source_station,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RailFare {
+    off_peak_time: f64,
+    peak_time: f64,
+    senior_disabled: f64,
+}
+
+impl From<rail::RailFare> for RailFare {
+    fn from(fare: rail::RailFare) -> Self {
+        RailFare {
+            off_peak_time: fare.off_peak_time,
+            peak_time: fare.peak_time,
+            senior_disabled: fare.senior_disabled,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RailPredictions {
+    trains: Vec<RailPrediction>,
+}
+
+impl From<rail::RailPredictions> for RailPredictions {
+    fn from(predictions: rail::RailPredictions) -> Self {
+        RailPredictions {
+            trains: all(predictions.trains),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RailPrediction {
+    car: Option<String>,
+    destination: String,
+    destination_station: Option<Station>,
+    destination_name: String,
+    group: String,
+    line: String,
+    location: Station,
+    location_name: String,
+    minutes: String,
+}
+
+impl From<rail::RailPrediction> for RailPrediction {
+    fn from(prediction: rail::RailPrediction) -> Self {
+        RailPrediction {
+            car: prediction.car,
+            destination: prediction.destination,
+            destination_station: prediction.destination_station,
+            destination_name: prediction.destination_name,
+            group: prediction.group,
+            line: prediction.line,
+            location: prediction.location,
+            location_name: prediction.location_name,
+            minutes: prediction.minutes,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationInformation {
+    address: Address,
+    station: Station,
+    latitude: f64,
+    longitude: f64,
+    first_line: Line,
+    second_line: Option<Line>,
+    third_line: Option<Line>,
+    fourth_line: Option<Line>,
+    name: String,
+    first_station_together: Option<Station>,
+    second_station_together: Option<Station>,
+}
+
+impl From<rail::StationInformation> for StationInformation {
+    fn from(station: rail::StationInformation) -> Self {
+        StationInformation {
+            address: Address {
+                city: station.address.city,
+                state: station.address.state,
+                street: station.address.street,
+                zip: station.address.zip,
+            },
+            station: station.station,
+            latitude: station.latitude,
+            longitude: station.longitude,
+            first_line: station.first_line,
+            second_line: station.second_line,
+            third_line: station.third_line,
+            fourth_line: station.fourth_line,
+            name: station.name,
+            first_station_together: station.first_station_together,
+            second_station_together: station.second_station_together,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationsParking {
+    stations_parking: Vec<StationParking>,
+}
+
+impl From<rail::StationsParking> for StationsParking {
+    fn from(parking: rail::StationsParking) -> Self {
+        StationsParking {
+            stations_parking: all(parking.stations_parking),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationParking {
+    station: Station,
+    notes: Option<String>,
+    all_day_parking: AllDayParking,
+    short_term_parking: ShortTermParking,
+}
+
+impl From<rail::StationParking> for StationParking {
+    fn from(parking: rail::StationParking) -> Self {
+        StationParking {
+            station: parking.station,
+            notes: parking.notes,
+            all_day_parking: AllDayParking {
+                total_count: parking.all_day_parking.total_count,
+                rider_cost: parking.all_day_parking.rider_cost,
+                non_rider_cost: parking.all_day_parking.non_rider_cost,
+                saturday_rider_cost: parking.all_day_parking.saturday_rider_cost,
+                saturday_non_rider_cost: parking.all_day_parking.saturday_non_rider_cost,
+            },
+            short_term_parking: ShortTermParking {
+                total_count: parking.short_term_parking.total_count,
+                notes: parking.short_term_parking.notes,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AllDayParking {
+    total_count: i32,
+    rider_cost: Option<f64>,
+    non_rider_cost: Option<f64>,
+    saturday_rider_cost: Option<f64>,
+    saturday_non_rider_cost: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct ShortTermParking {
+    total_count: i32,
+    notes: String,
+}
+
+#[derive(Serialize)]
+pub struct PathBetweenStations {
+    path: Vec<Path>,
+}
+
+impl From<rail::PathBetweenStations> for PathBetweenStations {
+    fn from(path: rail::PathBetweenStations) -> Self {
+        PathBetweenStations {
+            path: all(path.path),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Path {
+    distance_to_previous_station: i32,
+    line: Line,
+    sequence_number: i32,
+    station: Station,
+    station_name: String,
+}
+
+impl From<rail::Path> for Path {
+    fn from(path: rail::Path) -> Self {
+        Path {
+            distance_to_previous_station: path.distance_to_previous_station,
+            line: path.line,
+            sequence_number: path.sequence_number,
+            station: path.station,
+            station_name: path.station_name,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationTimings {
+    station_times: Vec<StationTime>,
+}
+
+impl From<rail::StationTimings> for StationTimings {
+    fn from(timings: rail::StationTimings) -> Self {
+        StationTimings {
+            station_times: all(timings.station_times),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationTime {
+    station: Station,
+    station_name: String,
+    monday: StationFirstLastTrains,
+    tuesday: StationFirstLastTrains,
+    wednesday: StationFirstLastTrains,
+    thursday: StationFirstLastTrains,
+    friday: StationFirstLastTrains,
+    saturday: StationFirstLastTrains,
+    sunday: StationFirstLastTrains,
+}
+
+impl From<rail::StationTime> for StationTime {
+    fn from(time: rail::StationTime) -> Self {
+        StationTime {
+            station: time.station,
+            station_name: time.station_name,
+            monday: time.monday.into(),
+            tuesday: time.tuesday.into(),
+            wednesday: time.wednesday.into(),
+            thursday: time.thursday.into(),
+            friday: time.friday.into(),
+            saturday: time.saturday.into(),
+            sunday: time.sunday.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationFirstLastTrains {
+    opening_time: String,
+    first_trains: Vec<TrainTime>,
+    last_trains: Vec<TrainTime>,
+}
+
+impl From<rail::StationFirstLastTrains> for StationFirstLastTrains {
+    fn from(trains: rail::StationFirstLastTrains) -> Self {
+        StationFirstLastTrains {
+            opening_time: trains.opening_time,
+            first_trains: all(trains.first_trains),
+            last_trains: all(trains.last_trains),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrainTime {
+    time: String,
+    destination: Station,
+}
+
+impl From<rail::TrainTime> for TrainTime {
+    fn from(time: rail::TrainTime) -> Self {
+        TrainTime {
+            time: time.time,
+            destination: time.destination,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Stations {
+    stations: Vec<StationResponse>,
+}
+
+impl From<rail::Stations> for Stations {
+    fn from(stations: rail::Stations) -> Self {
+        Stations {
+            stations: all(stations.stations),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StationResponse {
+    address: Address,
+    station: Station,
+    latitude: f64,
+    longitude: f64,
+    first_line: Line,
+    second_line: Option<Line>,
+    third_line: Option<Line>,
+    fourth_line: Option<Line>,
+    name: String,
+    first_station_together: Option<Station>,
+    second_station_together: Option<Station>,
+}
+
+impl From<rail::StationResponse> for StationResponse {
+    fn from(station: rail::StationResponse) -> Self {
+        StationResponse {
+            address: Address {
+                city: station.address.city,
+                state: station.address.state,
+                street: station.address.street,
+                zip: station.address.zip,
+            },
+            station: station.station,
+            latitude: station.latitude,
+            longitude: station.longitude,
+            first_line: station.first_line,
+            second_line: station.second_line,
+            third_line: station.third_line,
+            fourth_line: station.fourth_line,
+            name: station.name,
+            first_station_together: station.first_station_together,
+            second_station_together: station.second_station_together,
+        }
+    }
+}
+
+/// Address of a station, for both [`StationInformation`] and [`StationResponse`].
+#[derive(Serialize)]
+pub struct Address {
+    city: String,
+    state: String,
+    street: String,
+    zip: String,
+}
+
+#[derive(Serialize)]
+pub struct BusPositions {
+    bus_positions: Vec<BusPosition>,
+}
+
+impl From<bus::BusPositions> for BusPositions {
+    fn from(positions: bus::BusPositions) -> Self {
+        BusPositions {
+            bus_positions: all(positions.bus_positions),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BusPosition {
+    #[serde(serialize_with = "date_time")]
+    date_time: DateTime<FixedOffset>,
+    deviation: f64,
+    direction_number: i32,
+    direction_text: String,
+    latitude: f64,
+    longitude: f64,
+    route: Route,
+    #[serde(serialize_with = "date_time")]
+    trip_end_time: DateTime<FixedOffset>,
+    trip_headsign: String,
+    trip_id: String,
+    #[serde(serialize_with = "date_time")]
+    trip_start_time: DateTime<FixedOffset>,
+    vehicle_id: String,
+}
+
+impl From<bus::BusPosition> for BusPosition {
+    fn from(position: bus::BusPosition) -> Self {
+        BusPosition {
+            date_time: position.date_time,
+            deviation: position.deviation,
+            direction_number: position.direction_number,
+            direction_text: position.direction_text,
+            latitude: position.latitude,
+            longitude: position.longitude,
+            route: position.route,
+            trip_end_time: position.trip_end_time,
+            trip_headsign: position.trip_headsign,
+            trip_id: position.trip_id,
+            trip_start_time: position.trip_start_time,
+            vehicle_id: position.vehicle_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Routes {
+    routes: Vec<RouteResponse>,
+}
+
+impl From<bus::Routes> for Routes {
+    fn from(routes: bus::Routes) -> Self {
+        Routes {
+            routes: all(routes.routes),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RouteResponse {
+    route: Route,
+    name: String,
+    line_description: String,
+}
+
+impl From<bus::RouteResponse> for RouteResponse {
+    fn from(route: bus::RouteResponse) -> Self {
+        RouteResponse {
+            route: route.route,
+            name: route.name,
+            line_description: route.line_description,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Stops {
+    stops: Vec<StopRoutes>,
+}
+
+impl From<bus::Stops> for Stops {
+    fn from(stops: bus::Stops) -> Self {
+        Stops {
+            stops: all(stops.stops),
+        }
+    }
+}
+
+/// A stop and the routes serving it, for both [`Stops`] and [`StopSchedule`].
+#[derive(Serialize)]
+pub struct StopRoutes {
+    stop: Option<Stop>,
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    routes: Box<[Route]>,
+}
+
+impl From<bus::StopResponse> for StopRoutes {
+    fn from(stop: bus::StopResponse) -> Self {
+        StopRoutes {
+            stop: stop.stop,
+            name: stop.name,
+            latitude: stop.latitude,
+            longitude: stop.longitude,
+            routes: stop.routes,
+        }
+    }
+}
+
+impl From<bus::StopRoutes> for StopRoutes {
+    fn from(stop: bus::StopRoutes) -> Self {
+        StopRoutes {
+            stop: stop.stop,
+            name: stop.name,
+            latitude: stop.latitude,
+            longitude: stop.longitude,
+            routes: stop.routes,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Incidents {
+    incidents: Vec<Incident>,
+}
+
+impl From<bus::Incidents> for Incidents {
+    fn from(incidents: bus::Incidents) -> Self {
+        Incidents {
+            incidents: all(incidents.incidents),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Incident {
+    #[serde(serialize_with = "date_time")]
+    date_updated: DateTime<FixedOffset>,
+    description: String,
+    incident_id: String,
+    incident_type: String,
+    routes_affected: Box<[Route]>,
+}
+
+impl From<bus::Incident> for Incident {
+    fn from(incident: bus::Incident) -> Self {
+        Incident {
+            date_updated: incident.date_updated,
+            description: incident.description,
+            incident_id: incident.incident_id,
+            incident_type: incident.incident_type,
+            routes_affected: incident.routes_affected,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PathDetails {
+    route: Route,
+    name: String,
+    direction_zero: PathDirection,
+    direction_one: PathDirection,
+}
+
+impl From<bus::PathDetails> for PathDetails {
+    fn from(path: bus::PathDetails) -> Self {
+        PathDetails {
+            route: path.route,
+            name: path.name,
+            direction_zero: path.direction_zero.into(),
+            direction_one: path.direction_one.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PathDirection {
+    trip_headsign: String,
+    direction_text: String,
+    direction_number: String,
+    shape: Vec<PathShape>,
+    stops: Vec<StopRoutes>,
+}
+
+impl From<bus::PathDirection> for PathDirection {
+    fn from(direction: bus::PathDirection) -> Self {
+        PathDirection {
+            trip_headsign: direction.trip_headsign,
+            direction_text: direction.direction_text,
+            direction_number: direction.direction_number,
+            shape: all(direction.shape),
+            stops: all(direction.stops),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PathShape {
+    latitude: f64,
+    longitude: f64,
+    sequence_number: i32,
+}
+
+impl From<bus::PathShape> for PathShape {
+    fn from(shape: bus::PathShape) -> Self {
+        PathShape {
+            latitude: shape.latitude,
+            longitude: shape.longitude,
+            sequence_number: shape.sequence_number,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Predictions {
+    predictions: Vec<Prediction>,
+    stop_name: String,
+}
+
+impl From<bus::Predictions> for Predictions {
+    fn from(predictions: bus::Predictions) -> Self {
+        Predictions {
+            predictions: all(predictions.predictions),
+            stop_name: predictions.stop_name,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Prediction {
+    direction_number: String,
+    direction_text: String,
+    minutes: i32,
+    route: Route,
+    trip_id: String,
+    vehicle_id: String,
+}
+
+impl From<bus::Prediction> for Prediction {
+    fn from(prediction: bus::Prediction) -> Self {
+        Prediction {
+            direction_number: prediction.direction_number,
+            direction_text: prediction.direction_text,
+            minutes: prediction.minutes,
+            route: prediction.route,
+            trip_id: prediction.trip_id,
+            vehicle_id: prediction.vehicle_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StopSchedule {
+    arrivals: Vec<Arrival>,
+    stop: StopRoutes,
+}
+
+impl From<bus::StopSchedule> for StopSchedule {
+    fn from(schedule: bus::StopSchedule) -> Self {
+        StopSchedule {
+            arrivals: all(schedule.arrivals),
+            stop: schedule.stop.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Arrival {
+    #[serde(serialize_with = "date_time")]
+    schedule_time: DateTime<FixedOffset>,
+    direction_number: String,
+    #[serde(serialize_with = "date_time")]
+    start_time: DateTime<FixedOffset>,
+    #[serde(serialize_with = "date_time")]
+    end_time: DateTime<FixedOffset>,
+    route: Route,
+    trip_direction_text: String,
+    trip_headsign: String,
+    trip_id: String,
+}
+
+impl From<bus::Arrival> for Arrival {
+    fn from(arrival: bus::Arrival) -> Self {
+        Arrival {
+            schedule_time: arrival.schedule_time,
+            direction_number: arrival.direction_number,
+            start_time: arrival.start_time,
+            end_time: arrival.end_time,
+            route: arrival.route,
+            trip_direction_text: arrival.trip_direction_text,
+            trip_headsign: arrival.trip_headsign,
+            trip_id: arrival.trip_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RouteSchedule {
+    name: String,
+    direction_zero: Vec<RouteInfo>,
+    direction_one: Vec<RouteInfo>,
+}
+
+impl From<bus::RouteSchedule> for RouteSchedule {
+    fn from(schedule: bus::RouteSchedule) -> Self {
+        RouteSchedule {
+            name: schedule.name,
+            direction_zero: all(schedule.direction_zero),
+            direction_one: all(schedule.direction_one),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RouteInfo {
+    route: Route,
+    direction_number: String,
+    trip_direction_text: String,
+    trip_headsign: String,
+    #[serde(serialize_with = "date_time")]
+    start_time: DateTime<FixedOffset>,
+    #[serde(serialize_with = "date_time")]
+    end_time: DateTime<FixedOffset>,
+    stop_times: Vec<StopInfo>,
+    trip_id: String,
+}
+
+impl From<bus::RouteInfo> for RouteInfo {
+    fn from(info: bus::RouteInfo) -> Self {
+        RouteInfo {
+            route: info.route,
+            direction_number: info.direction_number,
+            trip_direction_text: info.trip_direction_text,
+            trip_headsign: info.trip_headsign,
+            start_time: info.start_time,
+            end_time: info.end_time,
+            stop_times: all(info.stop_times),
+            trip_id: info.trip_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StopInfo {
+    stop: Stop,
+    stop_name: String,
+    stop_sequence: i32,
+    #[serde(serialize_with = "date_time")]
+    time: DateTime<FixedOffset>,
+}
+
+impl From<bus::StopInfo> for StopInfo {
+    fn from(info: bus::StopInfo) -> Self {
+        StopInfo {
+            stop: info.stop,
+            stop_name: info.stop_name,
+            stop_sequence: info.stop_sequence,
+            time: info.time,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Tests for endpoints
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_rail() {
+    assert_eq!(Endpoint::parse("/rail/lines", None), Ok(Endpoint::Lines));
+    assert_eq!(
+        Endpoint::parse("/rail/stations", Some("line=RD")),
+        Ok(Endpoint::Stations(Some(Line::Red)))
+    );
+    assert_eq!(
+        Endpoint::parse("/rail/stations/A01/predictions/", None),
+        Ok(Endpoint::NextTrains(Station::A01))
+    );
+    assert_eq!(
+        Endpoint::parse("/rail/path", Some("to=A02&from=A01")),
+        Ok(Endpoint::Path(Station::A01, Station::A02))
+    );
+    assert_eq!(
+        Endpoint::parse("/rail/fares", Some("from=A01")),
+        Ok(Endpoint::Fares(Some(Station::A01), None))
+    );
+    assert_eq!(
+        Endpoint::parse(
+            "/rail/entrances",
+            Some("lat=38.8817596&lon=-77.0166426&radius=500")
+        ),
+        Ok(Endpoint::Entrances(RadiusAtLatLong::new(
+            500,
+            38.8817596,
+            -77.0166426
+        )))
+    );
+}
+
+#[test]
+fn test_bus() {
+    assert_eq!(
+        Endpoint::parse(
+            "/bus/routes/A2/schedule",
+            Some("date=2019-10-02&variations=true")
+        ),
+        Ok(Endpoint::RouteSchedule(
+            Route::A2,
+            Some(Date::new(2019, 10, 2)),
+            true
+        ))
+    );
+    assert_eq!(
+        Endpoint::parse("/bus/stops/1001195/predictions", None),
+        Ok(Endpoint::NextBuses(Stop::new("1001195")))
+    );
+    assert_eq!(
+        Endpoint::parse("/bus/positions", Some("route=10A")),
+        Ok(Endpoint::BusPositions(Some(Route::One0A), None))
+    );
+    assert_eq!(
+        Endpoint::parse("/bus/stops", None),
+        Ok(Endpoint::Stops(None))
+    );
+}
+
+#[test]
+fn test_rejected() {
+    assert_eq!(Endpoint::parse("/", None), Err(Rejection::NotFound));
+    assert_eq!(
+        Endpoint::parse("/rail/stations/A01/exits", None),
+        Err(Rejection::NotFound)
+    );
+    assert_eq!(
+        Endpoint::parse("/rail/stations/Z99", None),
+        Err(Rejection::BadRequest("invalid station: Z99".to_string()))
+    );
+    assert_eq!(
+        Endpoint::parse("/rail/path", Some("from=A01")),
+        Err(Rejection::BadRequest("to is required".to_string()))
+    );
+    assert_eq!(
+        Endpoint::parse("/bus/routes/A2/path", Some("date=2019-02-30")),
+        Err(Rejection::BadRequest(
+            "invalid date: 2019-02-30".to_string()
+        ))
+    );
+    assert!(Endpoint::parse("/bus/stops", Some("lat=38.88&lon=-77.01")).is_err());
+    assert!(Endpoint::parse("/rail/entrances", None).is_err());
+}
+
+#[test]
+fn test_fetch_snake_case() {
+    let server = wmata::testing::FakeServer::start();
+    let fetch = |endpoint: Endpoint| {
+        let body = tokio_test::block_on(endpoint.fetch(&server.wmata())).unwrap();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    };
+
+    let incidents = fetch(Endpoint::ElevatorIncidents(None));
+    let incident = &incidents["incidents"][0];
+    assert!(incident["unit_type"].is_string());
+    assert!(incident["station"].is_string());
+    assert!(incident["date_out_of_service"].is_string());
+    assert!(incident.get("StationCode").is_none());
+
+    let positions = fetch(Endpoint::BusPositions(None, None));
+    let position = &positions["bus_positions"][0];
+    assert!(position["route"].is_string());
+    assert!(position["vehicle_id"].is_string());
+    assert!(position.get("RouteID").is_none());
+}
@@ -0,0 +1,138 @@
+//! `wmata-server`, an HTTP/JSON proxy in front of the WMATA API.
+//!
+//! Internal apps call this server instead of WMATA, so they share one upstream API key.
+//! Upstream responses are cached in memory, identical concurrent requests make a single upstream call,
+//! and upstream calls are rate limited to stay within the key's quota, all by one [`Wmata`] client
+//! with a [`CachePolicy`] and [`RateLimit`]. Requests waiting on the rate limit longer than `--maximum-wait`
+//! fail with 502 Bad Gateway, like other upstream failures.
+//!
+//! Responses mirror the crate's own response types, serialized as JSON with snake_case fields
+//! rather than WMATA's PascalCase. See [`endpoint`] for the paths served.
+mod endpoint;
+
+use clap::Parser;
+use endpoint::{Endpoint, Rejection};
+use hyper::{
+    header::{HeaderValue, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use std::{convert::Infallible, net::SocketAddr, process, time::Duration};
+use wmata::{cache::CachePolicy, limit::RateLimit, Wmata};
+
+/// HTTP/JSON proxy for the WMATA API, sharing one API key between clients.
+#[derive(Parser)]
+#[command(name = "wmata-server", version)]
+struct Args {
+    /// WMATA API key used for all upstream calls.
+    #[arg(long, env = "WMATA_API_KEY", hide_env_values = true)]
+    api_key: String,
+
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    address: SocketAddr,
+
+    /// Seconds to cache live data, like predictions, positions, and incidents.
+    #[arg(long, default_value_t = 10)]
+    live_ttl: u64,
+
+    /// Seconds to cache everything else, like stations, routes, and schedules.
+    #[arg(long, default_value_t = 3600)]
+    ttl: u64,
+
+    /// Upstream calls allowed per second. WMATA's default tier allows 10.
+    #[arg(long, default_value_t = 10.0)]
+    rate_limit: f64,
+
+    /// Seconds a request will wait for an upstream call before being refused.
+    #[arg(long, default_value_t = 5)]
+    maximum_wait: u64,
+}
+
+#[tokio::main(basic_scheduler)]
+async fn main() {
+    let args = Args::parse();
+
+    let limit = match RateLimit::new(
+        args.rate_limit,
+        args.rate_limit.max(1.0),
+        Duration::from_secs(args.maximum_wait),
+    ) {
+        Ok(limit) => limit,
+        Err(err) => {
+            eprintln!("wmata-server: --rate-limit: {}", err.message);
+            process::exit(2);
+        }
+    };
+
+    let client = Wmata::builder(&args.api_key)
+        .cache(CachePolicy::new(
+            Duration::from_secs(args.ttl),
+            Duration::from_secs(args.live_ttl),
+        ))
+        .rate_limit(Some(limit))
+        .build();
+
+    let client = match client {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("wmata-server: {}", err.message);
+            process::exit(2);
+        }
+    };
+
+    let service = make_service_fn(move |_| {
+        let client = client.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let client = client.clone();
+
+                async move { Ok::<_, Infallible>(handle(&client, request).await) }
+            }))
+        }
+    });
+
+    eprintln!("wmata-server: listening on http://{}", args.address);
+
+    if let Err(err) = Server::bind(&args.address).serve(service).await {
+        eprintln!("wmata-server: {}", err);
+        process::exit(1);
+    }
+}
+
+async fn handle(client: &Wmata, request: Request<Body>) -> Response<Body> {
+    if request.method() != Method::GET {
+        return error(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported");
+    }
+
+    let endpoint = match Endpoint::parse(request.uri().path(), request.uri().query()) {
+        Ok(endpoint) => endpoint,
+        Err(Rejection::NotFound) => return error(StatusCode::NOT_FOUND, "not found"),
+        Err(Rejection::BadRequest(message)) => return error(StatusCode::BAD_REQUEST, &message),
+    };
+
+    match endpoint.fetch(client).await {
+        Ok(body) => json(StatusCode::OK, body),
+        Err(err) => error(StatusCode::BAD_GATEWAY, &err.message),
+    }
+}
+
+fn json(status: StatusCode, body: Vec<u8>) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// An error response, like `{"error": "not found"}`.
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+    json(
+        status,
+        serde_json::json!({ "error": message })
+            .to_string()
+            .into_bytes(),
+    )
+}
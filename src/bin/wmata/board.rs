@@ -0,0 +1,410 @@
+//! A live departure board for a spare screen, showing the next trains at a set of stations
+//! and the next buses at a set of stops, with incidents and elevator outages in a ticker.
+//!
+//! Boards are listed in a JSON file, by default `~/.config/wmata/boards.json`:
+//! ```json
+//! {
+//!     "refresh": 30,
+//!     "boards": [
+//!         { "title": "Metro Center", "stations": ["A01", "C01"] },
+//!         { "title": "Outside", "stops": ["1001195", "1001196"] }
+//!     ]
+//! }
+//! ```
+//! `refresh` is in seconds, and defaults to 30.
+mod tests;
+
+use chrono::Local;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Paragraph, Row, Table},
+    DefaultTerminal, Frame,
+};
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use wmata::{error::Error, Line, MetroBus, MetroRail, Station, Stop};
+
+/// Seconds between refreshes when not set in the config file.
+const DEFAULT_REFRESH: u64 = 30;
+
+/// How often the ticker moves and input is checked.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Placed between ticker messages.
+const TICKER_SEPARATOR: &str = "   ///   ";
+
+/// Boards shown together, read from a config file.
+#[derive(Deserialize)]
+pub struct Boards {
+    /// Seconds between refreshes.
+    #[serde(default = "default_refresh")]
+    pub refresh: u64,
+    pub boards: Vec<Board>,
+}
+
+/// Departures from a set of stations and stops.
+#[derive(Deserialize)]
+pub struct Board {
+    pub title: Option<String>,
+    #[serde(default)]
+    pub stations: Vec<Station>,
+    #[serde(default)]
+    pub stops: Vec<Stop>,
+}
+
+fn default_refresh() -> u64 {
+    DEFAULT_REFRESH
+}
+
+impl Boards {
+    /// Reads boards from the config file at `path`.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err)))?;
+
+        Boards::parse(&contents)
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err.message)))
+    }
+
+    fn parse(contents: &str) -> Result<Self, Error> {
+        let boards: Boards =
+            serde_json::from_str(contents).map_err(|err| Error::new(err.to_string()))?;
+
+        if boards.refresh == 0 {
+            return Err(Error::new("refresh must be at least 1 second".to_string()));
+        }
+
+        if boards.boards.is_empty() {
+            return Err(Error::new("no boards listed".to_string()));
+        }
+
+        if let Some(board) = boards
+            .boards
+            .iter()
+            .find(|board| board.stations.is_empty() && board.stops.is_empty())
+        {
+            return Err(Error::new(format!(
+                "board {} has no stations or stops",
+                board.title()
+            )));
+        }
+
+        Ok(boards)
+    }
+
+    /// Every station on any board, which the ticker's elevator outages are limited to.
+    fn stations(&self) -> Vec<Station> {
+        let mut stations: Vec<Station> = vec![];
+
+        for station in self.boards.iter().flat_map(|board| board.stations.iter()) {
+            if !stations.contains(station) {
+                stations.push(*station);
+            }
+        }
+
+        stations
+    }
+}
+
+/// A train or bus on its way.
+struct Departure {
+    /// Line of a train, used to color its row.
+    line: Option<Line>,
+    /// Line code of a train, or route of a bus.
+    route: String,
+    /// Station or stop departed from.
+    from: String,
+    destination: String,
+    minutes: String,
+}
+
+/// What's currently on screen.
+struct State {
+    departures: Vec<Vec<Departure>>,
+    ticker: Vec<String>,
+    /// When departures were last fetched, as shown to the user.
+    updated: Option<String>,
+    /// Most recent error while fetching, cleared once a refresh succeeds.
+    error: Option<String>,
+    /// How far the ticker has scrolled.
+    offset: usize,
+}
+
+impl State {
+    fn new(boards: &Boards) -> Self {
+        State {
+            departures: boards.boards.iter().map(|_| vec![]).collect(),
+            ticker: vec![],
+            updated: None,
+            error: None,
+            offset: 0,
+        }
+    }
+
+    /// Fetches all departures and the ticker. Failures keep the previous values.
+    async fn refresh(&mut self, rail: &MetroRail, bus: &MetroBus, boards: &Boards) {
+        let mut error = None;
+
+        for (departures, board) in self.departures.iter_mut().zip(boards.boards.iter()) {
+            match board.departures(rail, bus).await {
+                Ok(fetched) => *departures = fetched,
+                Err(err) => error = Some(err.message),
+            }
+        }
+
+        match ticker(rail, &boards.stations()).await {
+            Ok(ticker) => self.ticker = ticker,
+            Err(err) => error = Some(err.message),
+        }
+
+        self.updated = Some(Local::now().format("%H:%M:%S").to_string());
+        self.error = error;
+    }
+}
+
+impl Board {
+    fn title(&self) -> String {
+        match &self.title {
+            Some(title) => title.clone(),
+            None => self
+                .stations
+                .iter()
+                .map(|station| station.name())
+                .chain(self.stops.iter().map(|stop| stop.0.clone()))
+                .collect::<Vec<String>>()
+                .join(", "),
+        }
+    }
+
+    async fn departures(&self, rail: &MetroRail, bus: &MetroBus) -> Result<Vec<Departure>, Error> {
+        let mut departures = vec![];
+
+        for station in self.stations.iter() {
+            for train in rail.next_trains(*station).await?.trains.iter() {
+                departures.push(Departure {
+                    line: Line::from_str(&train.line).ok(),
+                    route: train.line.clone(),
+                    from: train.location_name.clone(),
+                    destination: train.destination_name.clone(),
+                    minutes: train.minutes.clone(),
+                });
+            }
+        }
+
+        for stop in self.stops.iter() {
+            let predictions = bus.next_buses(Stop::new(&stop.0)).await?;
+
+            for prediction in predictions.predictions.iter() {
+                departures.push(Departure {
+                    line: None,
+                    route: prediction.route.to_string(),
+                    from: predictions.stop_name.clone(),
+                    destination: prediction.direction_text.clone(),
+                    minutes: prediction.minutes.to_string(),
+                });
+            }
+        }
+
+        Ok(departures)
+    }
+}
+
+/// Rail incidents, and elevator and escalator outages at `stations`.
+async fn ticker(rail: &MetroRail, stations: &[Station]) -> Result<Vec<String>, Error> {
+    let mut ticker: Vec<String> = rail
+        .incidents_at(None)
+        .await?
+        .incidents
+        .iter()
+        .map(|incident| incident.description.clone())
+        .collect();
+
+    if !stations.is_empty() {
+        for outage in rail
+            .elevator_and_escalator_incidents_at(None)
+            .await?
+            .incidents
+            .iter()
+            .filter(|outage| stations.contains(&outage.station))
+        {
+            ticker.push(format!(
+                "{} {} out of service: {}",
+                outage.station_name,
+                outage.unit_type.to_string().to_lowercase(),
+                outage.location_description
+            ));
+        }
+    }
+
+    Ok(ticker)
+}
+
+/// Shows `boards` until the user quits with `q` or `Esc`. `r` refreshes immediately.
+pub async fn show(rail: &MetroRail, bus: &MetroBus, boards: &Boards) -> Result<(), Error> {
+    let mut terminal = ratatui::try_init().map_err(terminal_error)?;
+    let result = run(&mut terminal, rail, bus, boards).await;
+    ratatui::restore();
+
+    result
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    rail: &MetroRail,
+    bus: &MetroBus,
+    boards: &Boards,
+) -> Result<(), Error> {
+    let refresh = Duration::from_secs(boards.refresh);
+    let mut state = State::new(boards);
+    let mut refreshed: Option<Instant> = None;
+
+    loop {
+        let due = match refreshed {
+            Some(refreshed) => refreshed.elapsed() >= refresh,
+            None => true,
+        };
+
+        if due {
+            state.refresh(rail, bus, boards).await;
+            refreshed = Some(Instant::now());
+        }
+
+        terminal
+            .draw(|frame| draw(frame, boards, &state))
+            .map_err(terminal_error)?;
+
+        if let Some(Event::Key(key)) = next_event().await? {
+            if key.kind == KeyEventKind::Press {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('r') => refreshed = None,
+                    _ => (),
+                }
+            }
+        }
+
+        state.offset = state.offset.wrapping_add(1);
+    }
+}
+
+fn draw(frame: &mut Frame, boards: &Boards, state: &State) {
+    let [main, status, ticker] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let areas = Layout::vertical(
+        boards
+            .boards
+            .iter()
+            .map(|_| Constraint::Ratio(1, boards.boards.len() as u32)),
+    )
+    .split(main);
+
+    for ((board, departures), area) in boards
+        .boards
+        .iter()
+        .zip(state.departures.iter())
+        .zip(areas.iter())
+    {
+        let rows = departures.iter().map(|departure| {
+            Row::new(vec![
+                departure.route.clone(),
+                departure.from.clone(),
+                departure.destination.clone(),
+                departure.minutes.clone(),
+            ])
+            .style(
+                departure
+                    .line
+                    .map(|line| Style::default().fg(color(line)))
+                    .unwrap_or_default(),
+            )
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(5),
+                Constraint::Percentage(35),
+                Constraint::Fill(1),
+                Constraint::Length(4),
+            ],
+        )
+        .header(Row::new(vec!["", "FROM", "TO", "MIN"]).bold())
+        .block(Block::bordered().title(board.title()));
+
+        frame.render_widget(table, *area);
+    }
+
+    let status_line = match (&state.error, &state.updated) {
+        (Some(error), _) => Paragraph::new(format!("Error: {}", error)).red(),
+        (None, Some(updated)) => Paragraph::new(format!("Updated {}", updated)).dim(),
+        (None, None) => Paragraph::new("Loading...").dim(),
+    };
+    frame.render_widget(status_line, status);
+
+    frame.render_widget(
+        Paragraph::new(scroll(
+            &state.ticker.join(TICKER_SEPARATOR),
+            state.offset,
+            ticker.width as usize,
+        ))
+        .yellow(),
+        ticker,
+    );
+}
+
+/// Terminal color of a line, from its hex code.
+fn color(line: Line) -> Color {
+    let hex = line.color();
+    let channel = |range| u8::from_str_radix(hex.get(range).unwrap_or("FF"), 16).unwrap_or(0xFF);
+
+    Color::Rgb(channel(1..3), channel(3..5), channel(5..7))
+}
+
+/// `width` characters of `text`, starting `offset` characters in, wrapping around to the start.
+/// Text narrower than `width` doesn't scroll.
+fn scroll(text: &str, offset: usize, width: usize) -> String {
+    let length = text.chars().count();
+
+    if length <= width {
+        return text.to_string();
+    }
+
+    let looped: Vec<char> = text.chars().chain(TICKER_SEPARATOR.chars()).collect();
+
+    looped
+        .iter()
+        .cycle()
+        .skip(offset % looped.len())
+        .take(width)
+        .collect()
+}
+
+/// The next terminal event within a [`TICK`], if any. Waits on a blocking thread, to keep the runtime free.
+async fn next_event() -> Result<Option<Event>, Error> {
+    tokio::task::spawn_blocking(|| {
+        if event::poll(TICK)? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    })
+    .await
+    .map_err(|err| Error::new(err.to_string()))?
+    .map_err(terminal_error)
+}
+
+fn terminal_error(err: io::Error) -> Error {
+    Error::new(format!("Terminal error: {}", err))
+}
@@ -0,0 +1,46 @@
+//! Tests for the departure board
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_parse() {
+    let boards = Boards::parse(
+        r#"{
+            "boards": [
+                { "title": "Metro Center", "stations": ["A01", "C01"] },
+                { "stations": ["A01"], "stops": ["1001195"] }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(boards.refresh, DEFAULT_REFRESH);
+    assert_eq!(boards.boards[0].title(), "Metro Center");
+    assert_eq!(boards.boards[1].title(), "Metro Center, 1001195");
+    assert_eq!(boards.boards[1].stops, vec![Stop::new("1001195")]);
+    assert_eq!(boards.stations(), vec![Station::A01, Station::C01]);
+}
+
+#[test]
+fn test_parse_invalid() {
+    assert!(Boards::parse(r#"{ "boards": [] }"#).is_err());
+    assert!(Boards::parse(r#"{ "refresh": 0, "boards": [{ "stations": ["A01"] }] }"#).is_err());
+    assert!(Boards::parse(r#"{ "boards": [{ "title": "Empty" }] }"#).is_err());
+    assert!(Boards::parse(r#"{ "boards": [{ "stations": ["Z99"] }] }"#).is_err());
+}
+
+#[test]
+fn test_color() {
+    assert_eq!(color(Line::Blue), Color::Rgb(0x35, 0x7A, 0xBB));
+    assert_eq!(color(Line::Silver), Color::Rgb(0xA4, 0xA6, 0xA3));
+}
+
+#[test]
+fn test_scroll() {
+    assert_eq!(scroll("Delays", 3, 10), "Delays");
+    assert_eq!(scroll("Red Line delays", 0, 8), "Red Line");
+    assert_eq!(scroll("Red Line delays", 4, 8), "Line del");
+    // Wraps around through the separator
+    assert_eq!(scroll("Red Line delays", 12, 8), "ays   //");
+    assert_eq!(scroll("Red Line delays", 24, 8), "Red Line");
+}
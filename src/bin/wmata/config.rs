@@ -0,0 +1,68 @@
+//! The config file, holding the WMATA API key when it isn't set in the environment.
+//!
+//! The config file is JSON, read from `$WMATA_CONFIG`, `$XDG_CONFIG_HOME/wmata/config.json`,
+//! or `~/.config/wmata/config.json`, in that order:
+//! ```json
+//! { "api_key": "9e38c3eab34c4e6c990828002828f5ed" }
+//! ```
+mod tests;
+
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use wmata::error::Error;
+
+/// Environment variable holding the WMATA API key.
+pub const API_KEY_VARIABLE: &str = "WMATA_API_KEY";
+
+/// Environment variable overriding the config file location.
+const CONFIG_VARIABLE: &str = "WMATA_CONFIG";
+
+#[derive(Deserialize)]
+struct Config {
+    api_key: String,
+}
+
+/// Location of the config file, if one can be determined.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_VARIABLE) {
+        return Some(PathBuf::from(path));
+    }
+
+    directory().map(|directory| directory.join("config.json"))
+}
+
+/// Directory holding `wmata`'s config files, if one can be determined.
+pub fn directory() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|directory| directory.join("wmata"))
+}
+
+/// Reads the API key from the config file at `path`.
+pub fn api_key(path: &Path) -> Result<String, Error> {
+    let contents = fs::read_to_string(path).map_err(|err| {
+        Error::new(format!(
+            "No API key: set {} or create {} ({})",
+            API_KEY_VARIABLE,
+            path.display(),
+            err
+        ))
+    })?;
+
+    parse(&contents).map_err(|err| Error::new(format!("{}: {}", path.display(), err.message)))
+}
+
+fn parse(contents: &str) -> Result<String, Error> {
+    let config: Config =
+        serde_json::from_str(contents).map_err(|err| Error::new(err.to_string()))?;
+
+    if config.api_key.trim().is_empty() {
+        return Err(Error::new("api_key is empty".to_string()));
+    }
+
+    Ok(config.api_key.trim().to_string())
+}
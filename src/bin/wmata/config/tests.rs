@@ -0,0 +1,26 @@
+//! Tests for the config file
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        parse(r#"{ "api_key": " 9e38c3eab34c4e6c990828002828f5ed\n" }"#).unwrap(),
+        "9e38c3eab34c4e6c990828002828f5ed"
+    );
+}
+
+#[test]
+fn test_parse_invalid() {
+    assert!(parse(r#"{ "api_key": "" }"#).is_err());
+    assert!(parse(r#"{ "key": "9e38c3eab34c4e6c990828002828f5ed" }"#).is_err());
+    assert!(parse("api_key = 9e38c3eab34c4e6c990828002828f5ed").is_err());
+}
+
+#[test]
+fn test_missing_file() {
+    let err = api_key(Path::new("/nonexistent/wmata/config.json")).unwrap_err();
+
+    assert!(err.message.contains(API_KEY_VARIABLE));
+    assert!(err.message.contains("/nonexistent/wmata/config.json"));
+}
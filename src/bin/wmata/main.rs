@@ -0,0 +1,365 @@
+//! `wmata`, a command-line interface to WMATA's MetroRail and MetroBus APIs.
+//!
+//! Every command prints a table, or the API response as JSON with `--json`.
+//! The API key is read from `$WMATA_API_KEY`, or from the config file described in [`config`].
+#[cfg(feature = "tui")]
+mod board;
+mod config;
+mod table;
+mod tests;
+
+use chrono::{Datelike, NaiveDate};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+#[cfg(feature = "tui")]
+use std::path::PathBuf;
+use std::{convert::TryFrom, process};
+use table::Table;
+use wmata::{
+    bus::client::responses::RouteInfo, error::Error, Date, Line, MetroBus, MetroRail,
+    RadiusAtLatLong, Route, Station, Stop,
+};
+
+/// Command-line access to WMATA's MetroRail and MetroBus APIs.
+#[derive(Parser)]
+#[command(name = "wmata", version)]
+struct Cli {
+    /// WMATA API key. Falls back to the config file when not set.
+    #[arg(long, env = config::API_KEY_VARIABLE, hide_env_values = true, global = true)]
+    api_key: Option<String>,
+
+    /// Print the API response as JSON instead of a table.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Next trains arriving at a station.
+    Trains {
+        /// Station code, like A01.
+        station: Station,
+    },
+    /// Next buses arriving at a stop.
+    Buses {
+        /// Stop ID, like 1001195.
+        stop: String,
+    },
+    /// MetroRail incidents.
+    Incidents {
+        /// Only show incidents affecting this line code, like RD.
+        #[arg(long)]
+        line: Option<Line>,
+    },
+    /// Elevator and escalator outages.
+    Elevators {
+        /// Only show outages at this station code.
+        #[arg(long)]
+        station: Option<Station>,
+    },
+    /// Stations between two stations on the same line.
+    Path { from: Station, to: Station },
+    /// Fare and travel time between two stations.
+    Fare { from: Station, to: Station },
+    /// All MetroBus routes.
+    Routes,
+    /// MetroBus stops, optionally near a point.
+    Stops {
+        /// Latitude, longitude, and radius in meters, like 38.8817596,-77.0166426,500.
+        #[arg(long, value_parser = parse_radius_at_lat_long, allow_hyphen_values = true)]
+        near: Option<RadiusAtLatLong>,
+    },
+    /// Trips scheduled on a route.
+    Schedule {
+        /// Route ID, like A2.
+        route: Route,
+        /// Date, like 2019-10-02. Defaults to today.
+        #[arg(long, value_parser = parse_date)]
+        date: Option<Date>,
+    },
+    /// Live departure board for the stations and stops listed in a boards file.
+    #[cfg(feature = "tui")]
+    Board {
+        /// Boards file. Defaults to boards.json in the config directory.
+        #[arg(long)]
+        boards: Option<PathBuf>,
+    },
+}
+
+#[tokio::main(basic_scheduler)]
+async fn main() {
+    let cli = Cli::parse();
+
+    if let Err(err) = run(cli).await {
+        eprintln!("wmata: {}", err);
+        process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Error> {
+    let api_key = match cli.api_key {
+        Some(api_key) => api_key,
+        None => config::api_key(&config::path().ok_or_else(|| {
+            Error::new(format!(
+                "No API key: set {} or create a config file",
+                config::API_KEY_VARIABLE
+            ))
+        })?)?,
+    };
+    let rail = MetroRail::new(&api_key);
+    let bus = MetroBus::new(&api_key);
+    let json = cli.json;
+
+    match cli.command {
+        Command::Trains { station } => {
+            let predictions = rail.next_trains(station).await?;
+
+            print(json, &predictions, |predictions| {
+                let mut table = Table::new(&["LINE", "CARS", "DESTINATION", "MIN"]);
+                for train in predictions.trains.iter() {
+                    table.row(vec![
+                        train.line.clone(),
+                        train.car.clone().unwrap_or_default(),
+                        train.destination_name.clone(),
+                        train.minutes.clone(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Buses { stop } => {
+            let predictions = bus.next_buses(Stop::new(&stop)).await?;
+
+            print(json, &predictions, |predictions| {
+                let mut table = Table::new(&["ROUTE", "DIRECTION", "MIN", "VEHICLE"]);
+                for prediction in predictions.predictions.iter() {
+                    table.row(vec![
+                        prediction.route.to_string(),
+                        prediction.direction_text.clone(),
+                        prediction.minutes.to_string(),
+                        prediction.vehicle_id.clone(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Incidents { line } => {
+            let mut incidents = rail.incidents_at(None).await?;
+
+            if let Some(line) = line {
+                // Lines affected look like "BL; OR; RD;"
+                incidents.incidents = incidents
+                    .incidents
+                    .into_vec()
+                    .into_iter()
+                    .filter(|incident| {
+                        incident
+                            .lines_affected
+                            .split(';')
+                            .any(|code| code.trim() == line.to_string())
+                    })
+                    .collect();
+            }
+
+            print(json, &incidents, |incidents| {
+                let mut table = Table::new(&["TYPE", "LINES", "UPDATED", "DESCRIPTION"]);
+                for incident in incidents.incidents.iter() {
+                    table.row(vec![
+                        incident.incident_type.clone(),
+                        incident.lines_affected.trim_end_matches(';').to_string(),
+                        incident.date_updated.format("%Y-%m-%d %H:%M").to_string(),
+                        incident.description.clone(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Elevators { station } => {
+            let incidents = rail.elevator_and_escalator_incidents_at(station).await?;
+
+            print(json, &incidents, |incidents| {
+                let mut table = Table::new(&["STATION", "UNIT", "TYPE", "SYMPTOM", "RETURN"]);
+                for incident in incidents.incidents.iter() {
+                    table.row(vec![
+                        incident.station_name.clone(),
+                        incident.unit_name.clone(),
+                        incident.unit_type.to_string(),
+                        incident.symptom_description.clone(),
+                        incident
+                            .estimated_return_to_service
+                            .map(|date_time| date_time.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Path { from, to } => {
+            let path = rail.path_from(from, to).await?;
+
+            print(json, &path, |path| {
+                let mut table = Table::new(&["#", "LINE", "STATION", "NAME", "FEET"]);
+                for stop in path.path.iter() {
+                    table.row(vec![
+                        stop.sequence_number.to_string(),
+                        stop.line.to_string(),
+                        stop.station.to_string(),
+                        stop.station_name.clone(),
+                        stop.distance_to_previous_station.to_string(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Fare { from, to } => {
+            let fares = rail.station_to_station(Some(from), Some(to)).await?;
+
+            print(json, &fares, |fares| {
+                let mut table = Table::new(&[
+                    "FROM",
+                    "TO",
+                    "PEAK",
+                    "OFF-PEAK",
+                    "SENIOR/DISABLED",
+                    "MIN",
+                    "MILES",
+                ]);
+                for info in fares.station_to_station_infos.iter() {
+                    table.row(vec![
+                        info.source_station.name(),
+                        info.destination_station.name(),
+                        format!("${:.2}", info.rail_fare.peak_time),
+                        format!("${:.2}", info.rail_fare.off_peak_time),
+                        format!("${:.2}", info.rail_fare.senior_disabled),
+                        info.rail_time.to_string(),
+                        info.composite_miles.to_string(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Routes => {
+            let routes = bus.routes().await?;
+
+            print(json, &routes, |routes| {
+                let mut table = Table::new(&["ROUTE", "NAME", "DESCRIPTION"]);
+                for route in routes.routes.iter() {
+                    table.row(vec![
+                        route.route.to_string(),
+                        route.name.clone(),
+                        route.line_description.clone(),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Stops { near } => {
+            let stops = bus.stops(near).await?;
+
+            print(json, &stops, |stops| {
+                let mut table = Table::new(&["STOP", "NAME", "ROUTES"]);
+                for stop in stops.stops.iter() {
+                    table.row(vec![
+                        stop.stop
+                            .as_ref()
+                            .map(|stop| stop.0.clone())
+                            .unwrap_or_default(),
+                        stop.name.clone(),
+                        routes(&stop.routes),
+                    ]);
+                }
+                table
+            })
+        }
+        Command::Schedule { route, date } => {
+            let schedule = bus.route_schedule(route, date, false).await?;
+
+            print(json, &schedule, |schedule| {
+                let mut table = Table::new(&["DIR", "HEADSIGN", "TRIP", "START", "END"]);
+                for trip in schedule
+                    .direction_zero
+                    .iter()
+                    .chain(schedule.direction_one.iter())
+                {
+                    table.row(trip_row(trip));
+                }
+                table
+            })
+        }
+        #[cfg(feature = "tui")]
+        Command::Board { boards } => {
+            let path = boards
+                .or_else(|| config::directory().map(|directory| directory.join("boards.json")))
+                .ok_or_else(|| Error::new("No boards file: pass --boards".to_string()))?;
+
+            board::show(&rail, &bus, &board::Boards::read(&path)?).await
+        }
+    }
+}
+
+/// Prints `response` as pretty JSON, or as the table built from it.
+fn print<T: Serialize>(
+    json: bool,
+    response: &T,
+    table: impl FnOnce(&T) -> Table,
+) -> Result<(), Error> {
+    if json {
+        let json =
+            serde_json::to_string_pretty(response).map_err(|err| Error::new(err.to_string()))?;
+        println!("{}", json);
+    } else {
+        print!("{}", table(response));
+    }
+
+    Ok(())
+}
+
+fn routes(routes: &[Route]) -> String {
+    routes
+        .iter()
+        .map(|route| route.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn trip_row(trip: &RouteInfo) -> Vec<String> {
+    vec![
+        trip.direction_number.clone(),
+        trip.trip_headsign.clone(),
+        trip.trip_id.clone(),
+        trip.start_time.format("%H:%M").to_string(),
+        trip.end_time.format("%H:%M").to_string(),
+    ]
+}
+
+fn parse_radius_at_lat_long(s: &str) -> Result<RadiusAtLatLong, String> {
+    let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+
+    match parts.as_slice() {
+        [latitude, longitude, radius] => Ok(RadiusAtLatLong::new(
+            radius
+                .parse()
+                .map_err(|_| format!("invalid radius: {}", radius))?,
+            latitude
+                .parse()
+                .map_err(|_| format!("invalid latitude: {}", latitude))?,
+            longitude
+                .parse()
+                .map_err(|_| format!("invalid longitude: {}", longitude))?,
+        )),
+        _ => Err("expected latitude,longitude,radius".to_string()),
+    }
+}
+
+fn parse_date(s: &str) -> Result<Date, String> {
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|err| err.to_string())?;
+
+    Ok(Date::new(
+        u16::try_from(date.year()).map_err(|_| format!("year out of range: {}", date.year()))?,
+        date.month() as u8,
+        date.day() as u8,
+    ))
+}
@@ -0,0 +1,35 @@
+//! Tests for tables
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_table() {
+    let mut table = Table::new(&["LINE", "DESTINATION", "MIN"]);
+    table.row(vec![
+        "RD".to_string(),
+        "Shady Grove".to_string(),
+        "3".to_string(),
+    ]);
+    table.row(vec!["BL".to_string(), "Franconia-Springfield".to_string()]);
+
+    assert_eq!(
+        table.to_string(),
+        "LINE  DESTINATION            MIN\n\
+         ----  ---------------------  ---\n\
+         RD    Shady Grove            3\n\
+         BL    Franconia-Springfield\n"
+    );
+}
+
+#[test]
+fn test_empty_table() {
+    assert_eq!(Table::new(&["ROUTE"]).to_string(), "ROUTE\n-----\n");
+}
+
+#[test]
+fn test_wide_characters() {
+    let mut table = Table::new(&["NAME", "ID"]);
+    table.row(vec!["Café".to_string(), "1".to_string()]);
+
+    assert_eq!(table.to_string(), "NAME  ID\n----  --\nCafé  1\n");
+}
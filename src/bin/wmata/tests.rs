@@ -0,0 +1,46 @@
+//! Tests for argument parsing
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_parse_radius_at_lat_long() {
+    assert_eq!(
+        parse_radius_at_lat_long("38.8817596, -77.0166426, 500").unwrap(),
+        RadiusAtLatLong::new(500, 38.8817596, -77.0166426)
+    );
+    assert!(parse_radius_at_lat_long("38.8817596,-77.0166426").is_err());
+    assert!(parse_radius_at_lat_long("38.8817596,-77.0166426,far").is_err());
+}
+
+#[test]
+fn test_parse_date() {
+    assert_eq!(parse_date("2019-10-02").unwrap(), Date::new(2019, 10, 2));
+    assert!(parse_date("2019-02-30").is_err());
+    assert!(parse_date("10/02/2019").is_err());
+}
+
+#[test]
+fn test_cli() {
+    let cli = Cli::try_parse_from([
+        "wmata",
+        "stops",
+        "--near",
+        "38.8817596,-77.0166426,500",
+        "--json",
+    ])
+    .unwrap();
+
+    assert!(cli.json);
+    match cli.command {
+        Command::Stops { near } => {
+            assert_eq!(
+                near,
+                Some(RadiusAtLatLong::new(500, 38.8817596, -77.0166426))
+            )
+        }
+        _ => panic!("expected stops"),
+    }
+
+    assert!(Cli::try_parse_from(["wmata", "trains", "Z99"]).is_err());
+    assert!(Cli::try_parse_from(["wmata", "incidents", "--line", "RD"]).is_ok());
+}
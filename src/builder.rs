@@ -0,0 +1,229 @@
+//! Builder for clients with timeouts, a User-Agent, default headers, a proxy,
+//! and cache, retry, and rate limit policies.
+//!
+//! Start one with `MetroRail::builder`, `MetroBus::builder`, or `Wmata::builder`. Built clients are cheap to clone,
+//! and clones share one connection pool, cache, and rate limit, so may be handed to separate tasks.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use wmata::{cache::CachePolicy, retry::RetryPolicy, MetroRail};
+//!
+//! let client = MetroRail::builder("9e38c3eab34c4e6c990828002828f5ed")
+//!     .timeout(Duration::from_secs(10))
+//!     .user_agent("departures/1.0")
+//!     .cache(CachePolicy::default())
+//!     .retry(RetryPolicy::default())
+//!     .build()
+//!     .unwrap();
+//! ```
+mod tests;
+
+use crate::{
+    cache::{Cache, CachePolicy},
+    client,
+    error::Error,
+    keys::KeyPool,
+    limit::RateLimit,
+    requests::{Transport, WMATA_API},
+    retry::RetryPolicy,
+    MetroBus, MetroRail, Wmata,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+/// Builder for a client of type `C`: [`MetroRail`], [`MetroBus`], or [`Wmata`].
+pub struct Builder<C> {
+    api_key: String,
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    headers: Vec<(String, String)>,
+    proxy: Option<String>,
+    keys: Option<KeyPool>,
+    cache: Option<CachePolicy>,
+    retry: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
+    client: PhantomData<fn() -> C>,
+}
+
+impl<C> Builder<C> {
+    pub(crate) fn new(api_key: &str) -> Self {
+        Builder {
+            api_key: api_key.to_string(),
+            base_url: WMATA_API.to_string(),
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            headers: vec![],
+            proxy: None,
+            keys: None,
+            cache: None,
+            retry: None,
+            rate_limit: None,
+            client: PhantomData,
+        }
+    }
+
+    /// Builder for a client sending keys from `pool`, rather than a single API key. See [`crate::keys`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use wmata::{builder::Builder, keys::KeyPool, Wmata};
+    ///
+    /// let pool = KeyPool::from_env("WMATA_API_KEYS").unwrap();
+    /// let client = Builder::<Wmata>::from_key_pool(pool).build().unwrap();
+    /// ```
+    pub fn from_key_pool(pool: KeyPool) -> Self {
+        Builder::new("").key_pool(pool)
+    }
+
+    /// Sends requests somewhere other than the WMATA API, like a mirror or the fake server in `wmata::testing`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Fails requests taking longer than `timeout`, from connecting until the response is read.
+    /// No timeout, unless set.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails requests taking longer than `timeout` to connect. No timeout, unless set.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends `user_agent` as the User-Agent of every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Sends a header with every request. May be called more than once, including for the same header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sends requests through the HTTP proxy at `proxy`, e.g.: `http://proxy.internal:3128`.
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
+        self
+    }
+
+    /// Sends keys from `pool` in place of the key the builder was started with. See [`crate::keys`].
+    pub fn key_pool(mut self, pool: KeyPool) -> Self {
+        self.keys = Some(pool);
+        self
+    }
+
+    /// Caches responses in memory, as long as `policy` allows. Identical concurrent requests make one call.
+    /// Nothing is cached, unless set.
+    pub fn cache(mut self, policy: CachePolicy) -> Self {
+        self.cache = Some(policy);
+        self
+    }
+
+    /// Retries failed requests, as `policy` allows. Requests aren't retried, unless set.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Limits the rate of requests, including retries. Not rate limited at all, if None.
+    /// Only [`Wmata`] is rate limited unless set, to WMATA's default tier.
+    pub fn rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// API key, base URL, and transport for the client being built.
+    fn parts(self) -> Result<(String, String, Transport), Error> {
+        if self.api_key.trim().is_empty() && self.keys.is_none() {
+            return Err(Error::new(
+                "An API key or a key pool is required.".to_string(),
+            ));
+        }
+
+        let mut http = reqwest::Client::builder();
+
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent = HeaderValue::from_str(user_agent)
+                .map_err(|err| Error::new(format!("Invalid User-Agent {}: {}", user_agent, err)))?;
+            http = http.user_agent(user_agent);
+        }
+
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in self.headers.iter() {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|err| Error::new(format!("Invalid header name {}: {}", name, err)))?;
+            let header_value = HeaderValue::from_str(value)
+                .map_err(|err| Error::new(format!("Invalid value for header {}: {}", name, err)))?;
+
+            headers.append(header_name, header_value);
+        }
+
+        http = http.default_headers(headers);
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str())
+                .map_err(|err| Error::new(format!("Invalid proxy {}: {}", proxy, err)))?;
+            http = http.proxy(proxy);
+        }
+
+        let transport = Transport {
+            http: http.build().map_err(|err| Error::new(err.to_string()))?,
+            keys: self.keys,
+            rate_limit: self.rate_limit.map(Arc::new),
+            retry: self.retry,
+            cache: self
+                .cache
+                .map(|policy| (policy, Arc::new(Cache::default()))),
+        };
+
+        Ok((self.api_key, self.base_url, transport))
+    }
+}
+
+impl Builder<MetroRail> {
+    /// Builds the MetroRail client. Fails without an API key or key pool, or if a header, the User-Agent, or the proxy is invalid.
+    pub fn build(self) -> Result<MetroRail, Error> {
+        let (api_key, base_url, transport) = self.parts()?;
+
+        Ok(MetroRail::with_base_url(&api_key, &base_url).with_transport(transport))
+    }
+}
+
+impl Builder<MetroBus> {
+    /// Builds the MetroBus client. Fails without an API key or key pool, or if a header, the User-Agent, or the proxy is invalid.
+    pub fn build(self) -> Result<MetroBus, Error> {
+        let (api_key, base_url, transport) = self.parts()?;
+
+        Ok(MetroBus::with_base_url(&api_key, &base_url).with_transport(transport))
+    }
+}
+
+impl Builder<Wmata> {
+    /// Builds the client for both MetroRail and MetroBus. Fails without an API key or key pool, or if a header, the User-Agent, or the proxy is invalid.
+    pub fn build(self) -> Result<Wmata, Error> {
+        let (api_key, base_url, transport) = self.parts()?;
+
+        Ok(client::Client::from_transport(
+            &api_key, &base_url, transport,
+        ))
+    }
+}
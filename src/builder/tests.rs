@@ -0,0 +1,191 @@
+//! Tests for the client builder
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{
+    requests::Fetch,
+    testing::{Endpoint, FakeServer, Scripted},
+    Station,
+};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[test]
+fn test_defaults() {
+    let client = MetroRail::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .build()
+        .unwrap();
+    let transport = client.transport().unwrap();
+
+    assert_eq!(client.key, "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.base_url(), WMATA_API);
+    assert!(transport.rate_limit.is_none());
+    assert!(transport.retry.is_none());
+    assert!(transport.cache.is_none());
+
+    // Only the combined client is rate limited by default
+    let client = Wmata::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .build()
+        .unwrap();
+    assert!(client.rail().transport().unwrap().rate_limit.is_some());
+}
+
+#[test]
+fn test_invalid() {
+    assert!(MetroBus::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .header("X Forwarded", "value")
+        .build()
+        .is_err());
+    assert!(MetroBus::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .header("X-Forwarded-For", "line\nbreak")
+        .build()
+        .is_err());
+    assert!(MetroBus::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .proxy("not a proxy")
+        .build()
+        .is_err());
+    assert!(MetroBus::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .user_agent("departures/1.0")
+        .header("X-Forwarded-For", "10.0.0.1")
+        .proxy("http://127.0.0.1:3128")
+        .build()
+        .is_ok());
+}
+
+#[cfg(test)]
+fn assert_shareable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+#[test]
+fn test_clones_share_transport() {
+    let client = MetroRail::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .cache(CachePolicy::default())
+        .build()
+        .unwrap();
+    let clone = client.clone();
+    assert_shareable(&client);
+
+    let (_, cache) = client.transport().unwrap().cache.clone().unwrap();
+    let (_, cloned_cache) = clone.transport().unwrap().cache.clone().unwrap();
+
+    assert!(Arc::ptr_eq(&cache, &cloned_cache));
+}
+
+#[test]
+fn test_cache() {
+    let server = FakeServer::start();
+    let client = MetroRail::builder(server.api_key())
+        .base_url(&server.url())
+        .cache(CachePolicy::new(
+            Duration::from_secs(60),
+            Duration::from_secs(0),
+        ))
+        .build()
+        .unwrap();
+
+    block_on(async {
+        client.lines().await.unwrap();
+        client.lines().await.unwrap();
+        client.clone().lines().await.unwrap();
+    });
+    assert_eq!(server.requests().len(), 1);
+
+    // Live data isn't cached past its own TTL
+    block_on(async {
+        client.next_trains(Station::A01).await.unwrap();
+        client.next_trains(Station::A01).await.unwrap();
+    });
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_cache_skips_errors() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::Lines,
+        Scripted::error(500, "Internal Server Error"),
+    );
+    let client = MetroRail::builder(server.api_key())
+        .base_url(&server.url())
+        .cache(CachePolicy::default())
+        .build()
+        .unwrap();
+
+    let err = block_on(async { client.lines().await }).unwrap_err();
+    assert_eq!(err.message, "Internal Server Error");
+
+    block_on(async { client.lines().await }).unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_retry() {
+    let server = FakeServer::start();
+    server.script(Endpoint::BusRoutes, Scripted::error(503, "Unavailable"));
+    server.script(
+        Endpoint::BusRoutes,
+        Scripted::error(429, "Rate limit is exceeded."),
+    );
+    let client = MetroBus::builder(server.api_key())
+        .base_url(&server.url())
+        .retry(RetryPolicy::new(2, Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    block_on(async { client.routes().await }).unwrap();
+    assert_eq!(server.requests().len(), 3);
+
+    // Other errors aren't retried
+    server.script(Endpoint::BusRoutes, Scripted::error(400, "Bad Request"));
+    let err = block_on(async { client.routes().await }).unwrap_err();
+    assert_eq!(err.message, "Bad Request");
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn test_retries_exhausted() {
+    let server = FakeServer::start();
+    for _ in 0..2 {
+        server.script(Endpoint::BusRoutes, Scripted::error(503, "Unavailable"));
+    }
+    let client = MetroBus::builder(server.api_key())
+        .base_url(&server.url())
+        .retry(RetryPolicy::new(1, Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let err = block_on(async { client.routes().await }).unwrap_err();
+
+    assert_eq!(err.message, "Unavailable");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_timeout() {
+    let server = FakeServer::start();
+    server.set_latency(Duration::from_millis(500));
+    let client = MetroRail::builder(server.api_key())
+        .base_url(&server.url())
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    assert!(block_on(async { client.lines().await }).is_err());
+}
+
+#[test]
+fn test_rate_limit() {
+    let server = FakeServer::start();
+    let client = Wmata::builder(server.api_key())
+        .base_url(&server.url())
+        .rate_limit(Some(
+            RateLimit::new(0.1, 1.0, Duration::from_secs(1)).unwrap(),
+        ))
+        .build()
+        .unwrap();
+
+    block_on(async { client.rail().lines().await }).unwrap();
+    let err = block_on(async { client.bus().routes().await }).unwrap_err();
+
+    assert!(err.message.starts_with("Rate limit exceeded"));
+}
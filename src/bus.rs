@@ -1,7 +1,13 @@
 //! Client and responses for MetroBus endpoints.
+pub mod adherence;
+pub mod bunching;
 pub mod client;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod progress;
 pub mod route;
+pub mod shape;
 pub mod stop;
 
 mod traits;
-mod urls;
+pub(crate) mod urls;
@@ -0,0 +1,287 @@
+//! Schedule adherence, from the deviation reported with each bus position.
+mod tests;
+
+use crate::{
+    bus::client::responses::{BusPositions, RouteInfo, RouteSchedule},
+    Route,
+};
+use chrono::{DateTime, FixedOffset};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// Range of deviations (in minutes) considered on time.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OnTimeWindow {
+    /// Minutes early a bus can be and still be on time.
+    pub early: f64,
+    /// Minutes late a bus can be and still be on time.
+    pub late: f64,
+}
+
+impl Default for OnTimeWindow {
+    /// WMATA's definition of on time: no more than 2 minutes early or 7 minutes late.
+    fn default() -> Self {
+        OnTimeWindow {
+            early: 2.0,
+            late: 7.0,
+        }
+    }
+}
+
+/// A single deviation reported by a bus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// [`Route`] the bus was on.
+    pub route: Route,
+    /// General direction of the bus' trip (e.g.: NORTH).
+    pub direction_text: String,
+    /// Trip the bus was serving.
+    pub trip_id: String,
+    /// Bus that reported this deviation.
+    pub vehicle_id: String,
+    /// When the bus reported this deviation.
+    pub date_time: DateTime<FixedOffset>,
+    /// Minutes behind schedule. Negative when early.
+    pub deviation: f64,
+}
+
+/// Deviations summarized over a set of observations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdherenceSummary {
+    /// Number of observations summarized.
+    pub observations: usize,
+    /// Observations within the [`OnTimeWindow`].
+    pub on_time: usize,
+    /// Observations earlier than the [`OnTimeWindow`].
+    pub early: usize,
+    /// Observations later than the [`OnTimeWindow`].
+    pub late: usize,
+    /// Mean deviation, in minutes.
+    pub mean_deviation: f64,
+    /// Every deviation, in ascending order.
+    deviations: Vec<f64>,
+}
+
+impl AdherenceSummary {
+    fn new(deviations: Vec<f64>, window: &OnTimeWindow) -> Self {
+        let mut deviations = deviations;
+        deviations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let early = deviations
+            .iter()
+            .filter(|deviation| **deviation < -window.early)
+            .count();
+        let late = deviations
+            .iter()
+            .filter(|deviation| **deviation > window.late)
+            .count();
+        let mean_deviation = if deviations.is_empty() {
+            0.0
+        } else {
+            deviations.iter().sum::<f64>() / deviations.len() as f64
+        };
+
+        AdherenceSummary {
+            observations: deviations.len(),
+            on_time: deviations.len() - early - late,
+            early,
+            late,
+            mean_deviation,
+            deviations,
+        }
+    }
+
+    /// Percentage, from 0 to 100, of observations which were on time.
+    pub fn on_time_percent(&self) -> f64 {
+        if self.observations == 0 {
+            return 0.0;
+        }
+
+        self.on_time as f64 / self.observations as f64 * 100.0
+    }
+
+    /// Deviation (in minutes) at a percentile from 0 to 100, using the nearest-rank method.
+    /// Returns None if there are no observations.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::bus::adherence::Adherence;
+    ///
+    /// let adherence = Adherence::default();
+    /// assert_eq!(adherence.overall().percentile(90.0), None);
+    /// ```
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        if self.deviations.is_empty() {
+            return None;
+        }
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * self.deviations.len() as f64).ceil();
+        let index = (rank as usize).max(1) - 1;
+
+        Some(self.deviations[index])
+    }
+
+    /// Greatest deviation (in minutes), if there are any observations.
+    pub fn worst(&self) -> Option<f64> {
+        self.deviations.last().copied()
+    }
+}
+
+/// Adherence of a single scheduled trip.
+#[derive(Debug)]
+pub struct TripAdherence<'a> {
+    /// The scheduled trip.
+    pub trip: &'a RouteInfo,
+    /// See [`AdherenceSummary`].
+    pub summary: AdherenceSummary,
+}
+
+/// Observed deviations, collected over a series of bus position snapshots.
+#[derive(Debug, Default)]
+pub struct Adherence {
+    /// Range of deviations considered on time.
+    pub window: OnTimeWindow,
+    observations: Vec<Observation>,
+    /// Vehicle and time of every observation, to skip updates already recorded.
+    recorded: HashSet<(String, DateTime<FixedOffset>)>,
+}
+
+impl Adherence {
+    /// Constructor for an empty set of observations with a given on time window.
+    pub fn new(window: OnTimeWindow) -> Self {
+        Adherence {
+            window,
+            observations: vec![],
+            recorded: HashSet::new(),
+        }
+    }
+
+    /// Every observation recorded so far.
+    pub fn observations(&self) -> &[Observation] {
+        &self.observations
+    }
+
+    /// Records every position in a snapshot. A bus reporting the same update in more than one snapshot is only recorded once.
+    pub fn record(&mut self, positions: &BusPositions) {
+        for position in positions.bus_positions.iter() {
+            if !self
+                .recorded
+                .insert((position.vehicle_id.clone(), position.date_time))
+            {
+                continue;
+            }
+
+            self.observations.push(Observation {
+                route: position.route,
+                direction_text: position.direction_text.clone(),
+                trip_id: position.trip_id.clone(),
+                vehicle_id: position.vehicle_id.clone(),
+                date_time: position.date_time,
+                deviation: position.deviation,
+            });
+        }
+    }
+
+    /// Summary of every observation.
+    pub fn overall(&self) -> AdherenceSummary {
+        self.summarize(|_| true)
+    }
+
+    /// Summary of each route, in the order routes were first observed.
+    pub fn by_route(&self) -> Vec<(Route, AdherenceSummary)> {
+        self.group_by(|observation| observation.route)
+    }
+
+    /// Summary of each direction of each route, in the order they were first observed.
+    pub fn by_direction(&self) -> Vec<((Route, String), AdherenceSummary)> {
+        self.group_by(|observation| (observation.route, observation.direction_text.clone()))
+    }
+
+    /// Summary of each trip, in the order trips were first observed.
+    pub fn by_trip(&self) -> Vec<(String, AdherenceSummary)> {
+        self.group_by(|observation| observation.trip_id.clone())
+    }
+
+    /// Up to `count` trips with the greatest mean deviation, latest first.
+    pub fn worst_trips(&self, count: usize) -> Vec<(String, AdherenceSummary)> {
+        let mut trips = self.by_trip();
+        trips.sort_by(|(_, a), (_, b)| {
+            b.mean_deviation
+                .partial_cmp(&a.mean_deviation)
+                .unwrap_or(Ordering::Equal)
+        });
+        trips.truncate(count);
+
+        trips
+    }
+
+    /// Adherence of each trip in a route's schedule which has been observed, in schedule order.
+    pub fn scheduled_trips<'a>(&self, schedule: &'a RouteSchedule) -> Vec<TripAdherence<'a>> {
+        let mut deviations: HashMap<&str, Vec<f64>> = HashMap::new();
+
+        for observation in self.observations.iter() {
+            deviations
+                .entry(&observation.trip_id)
+                .or_default()
+                .push(observation.deviation);
+        }
+
+        schedule
+            .direction_zero
+            .iter()
+            .chain(schedule.direction_one.iter())
+            .filter_map(|trip| {
+                let deviations = deviations.get(trip.trip_id.as_str())?.clone();
+
+                Some(TripAdherence {
+                    trip,
+                    summary: AdherenceSummary::new(deviations, &self.window),
+                })
+            })
+            .collect()
+    }
+
+    fn summarize<F>(&self, include: F) -> AdherenceSummary
+    where
+        F: Fn(&Observation) -> bool,
+    {
+        AdherenceSummary::new(
+            self.observations
+                .iter()
+                .filter(|observation| include(observation))
+                .map(|observation| observation.deviation)
+                .collect(),
+            &self.window,
+        )
+    }
+
+    fn group_by<K, F>(&self, key: F) -> Vec<(K, AdherenceSummary)>
+    where
+        K: Eq + Hash + Clone,
+        F: Fn(&Observation) -> K,
+    {
+        // Groups in the order first observed, and the position of each group's key
+        let mut groups: Vec<(K, Vec<f64>)> = vec![];
+        let mut positions: HashMap<K, usize> = HashMap::new();
+
+        for observation in self.observations.iter() {
+            let observation_key = key(observation);
+
+            match positions.get(&observation_key) {
+                Some(position) => groups[*position].1.push(observation.deviation),
+                None => {
+                    positions.insert(observation_key.clone(), groups.len());
+                    groups.push((observation_key, vec![observation.deviation]));
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, deviations)| (key, AdherenceSummary::new(deviations, &self.window)))
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+//! Tests for schedule adherence
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::bus::fixtures::{self, PositionBuilder};
+
+#[cfg(test)]
+fn position(vehicle: &str, trip: &str, route: &str, time: &str, deviation: f64) -> PositionBuilder {
+    fixtures::position()
+        .vehicle(vehicle)
+        .trip(trip)
+        .route(route)
+        .direction(0, "NORTH")
+        .time(time)
+        .deviation(deviation)
+}
+
+#[cfg(test)]
+fn adherence() -> Adherence {
+    let mut adherence = Adherence::default();
+
+    adherence.record(&fixtures::positions(vec![
+        position("1", "100", "A2", "08:15:00", 1.0),
+        position("2", "200", "A2", "08:15:00", 10.0),
+        position("3", "300", "10A", "08:15:00", -3.0),
+    ]));
+    adherence.record(&fixtures::positions(vec![
+        // Repeated from the first snapshot
+        position("1", "100", "A2", "08:15:00", 1.0),
+        position("1", "100", "A2", "08:16:00", 3.0),
+        position("2", "200", "A2", "08:16:00", 12.0),
+    ]));
+
+    adherence
+}
+
+#[test]
+fn test_record_ignores_repeated_updates() {
+    assert_eq!(adherence().observations().len(), 5);
+}
+
+#[test]
+fn test_overall() {
+    let summary = adherence().overall();
+
+    assert_eq!(summary.observations, 5);
+    assert_eq!(summary.on_time, 2);
+    assert_eq!(summary.early, 1);
+    assert_eq!(summary.late, 2);
+    assert_eq!(summary.on_time_percent(), 40.0);
+    assert_eq!(summary.mean_deviation, 4.6);
+    assert_eq!(summary.worst(), Some(12.0));
+}
+
+#[test]
+fn test_percentile() {
+    let summary = adherence().overall();
+
+    assert_eq!(summary.percentile(0.0), Some(-3.0));
+    assert_eq!(summary.percentile(50.0), Some(3.0));
+    assert_eq!(summary.percentile(90.0), Some(12.0));
+}
+
+#[test]
+fn test_custom_window() {
+    let mut adherence = Adherence::new(OnTimeWindow {
+        early: 5.0,
+        late: 15.0,
+    });
+    adherence.record(&fixtures::positions(vec![
+        position("2", "200", "A2", "08:15:00", 10.0),
+        position("3", "300", "10A", "08:15:00", -3.0),
+    ]));
+
+    assert_eq!(adherence.overall().on_time_percent(), 100.0);
+}
+
+#[test]
+fn test_by_route() {
+    let routes = adherence().by_route();
+
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].0, Route::A2);
+    assert_eq!(routes[0].1.observations, 4);
+    assert_eq!(routes[1].0, Route::One0A);
+}
+
+#[test]
+fn test_by_direction() {
+    let directions = adherence().by_direction();
+
+    assert_eq!(directions[0].0, (Route::A2, "NORTH".to_string()));
+}
+
+#[test]
+fn test_worst_trips() {
+    let worst = adherence().worst_trips(2);
+
+    assert_eq!(worst.len(), 2);
+    assert_eq!(worst[0].0, "200");
+    assert_eq!(worst[0].1.mean_deviation, 11.0);
+    assert_eq!(worst[1].0, "100");
+}
+
+#[test]
+fn test_scheduled_trips() {
+    let schedule: RouteSchedule = serde_json::from_str(
+        r#"{
+            "Name": "A2",
+            "Direction0": [
+                {"RouteID": "A2", "DirectionNum": "0", "TripDirectionText": "NORTH", "TripHeadsign": "ANACOSTIA",
+                 "StartTime": "2019-10-01T08:00:00", "EndTime": "2019-10-01T09:00:00", "StopTimes": [], "TripID": "200"},
+                {"RouteID": "A2", "DirectionNum": "0", "TripDirectionText": "NORTH", "TripHeadsign": "ANACOSTIA",
+                 "StartTime": "2019-10-01T09:00:00", "EndTime": "2019-10-01T10:00:00", "StopTimes": [], "TripID": "999"}
+            ],
+            "Direction1": []
+        }"#,
+    )
+    .unwrap();
+
+    let adherence = adherence();
+    let trips = adherence.scheduled_trips(&schedule);
+
+    assert_eq!(trips.len(), 1);
+    assert_eq!(trips[0].trip.trip_id, "200");
+    assert_eq!(trips[0].summary.late, 2);
+}
@@ -0,0 +1,224 @@
+//! Bus bunching: buses on the same route running too close together, or too far apart.
+mod tests;
+
+use crate::{
+    bus::client::responses::{BusPosition, BusPositions, PathDetails},
+    Route,
+};
+use chrono::Duration;
+use std::{cmp::Ordering, ptr};
+
+/// Spacing between consecutive buses at which they are considered bunched, or to have a gap between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BunchingThresholds {
+    /// Buses closer than this many meters are bunched.
+    pub bunched_distance: f64,
+    /// Buses closer than this in time are bunched.
+    pub bunched_time: Duration,
+    /// Buses further apart than this many meters have a gap between them.
+    pub gap_distance: f64,
+    /// Buses further apart than this in time have a gap between them.
+    pub gap_time: Duration,
+}
+
+impl Default for BunchingThresholds {
+    /// Bunched within 400 meters or 2 minutes, with a gap beyond 5 kilometers or 20 minutes.
+    fn default() -> Self {
+        BunchingThresholds {
+            bunched_distance: 400.0,
+            bunched_time: Duration::minutes(2),
+            gap_distance: 5000.0,
+            gap_time: Duration::minutes(20),
+        }
+    }
+}
+
+/// How the spacing between two consecutive buses compares to [`BunchingThresholds`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpacingStatus {
+    Bunched,
+    Normal,
+    Gap,
+}
+
+/// A bus along one direction of a route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vehicle {
+    /// Unique identifier for the bus.
+    pub vehicle_id: String,
+    /// Trip the bus is serving.
+    pub trip_id: String,
+    /// Distance (in meters) along the route.
+    pub distance_along: f64,
+    /// Average speed (in meters per second) the bus is scheduled to travel at, based on the length of the
+    /// route and the scheduled length of the trip.
+    pub scheduled_speed: Option<f64>,
+}
+
+/// Spacing between a bus and the bus ahead of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spacing {
+    /// Vehicle ID of the bus further along the route.
+    pub leading: String,
+    /// Vehicle ID of the bus behind it.
+    pub following: String,
+    /// Distance (in meters) between the buses, along the route.
+    pub distance: f64,
+    /// Approximate time for the following bus to reach the leading bus' position, at its scheduled speed.
+    pub time: Option<Duration>,
+    /// See [`SpacingStatus`].
+    pub status: SpacingStatus,
+}
+
+/// Buses along one direction of a route, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionReport {
+    /// Direction number of this direction, usually 0 or 1.
+    pub direction_number: String,
+    /// General direction of this direction (e.g.: NORTH).
+    pub direction_text: String,
+    /// Where buses in this direction are headed.
+    pub trip_headsign: String,
+    /// Buses in this direction, furthest along the route first.
+    pub vehicles: Vec<Vehicle>,
+    /// Spacing between each consecutive pair of buses, in the same order as `vehicles`.
+    pub spacings: Vec<Spacing>,
+}
+
+/// Spacing of buses along both directions of a route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BunchingReport {
+    /// [`Route`] of this report.
+    pub route: Route,
+    /// See [`DirectionReport`].
+    pub directions: Vec<DirectionReport>,
+    /// Vehicle IDs of buses which couldn't be placed on either direction of the route.
+    pub unmatched: Vec<String>,
+}
+
+impl BunchingReport {
+    /// Builds a report from the path of a route and the positions of buses along it.
+    pub fn new(
+        path: &PathDetails,
+        positions: &BusPositions,
+        thresholds: &BunchingThresholds,
+    ) -> Self {
+        let mut directions: Vec<DirectionReport> = [&path.direction_zero, &path.direction_one]
+            .iter()
+            .map(|direction| DirectionReport {
+                direction_number: direction.direction_number.clone(),
+                direction_text: direction.direction_text.clone(),
+                trip_headsign: direction.trip_headsign.clone(),
+                vehicles: vec![],
+                spacings: vec![],
+            })
+            .collect();
+
+        let mut unmatched = vec![];
+
+        for position in positions.bus_positions.iter() {
+            let progress = match position.progress_along(path) {
+                Some(progress) => progress,
+                None => {
+                    unmatched.push(position.vehicle_id.clone());
+                    continue;
+                }
+            };
+
+            let index = if ptr::eq(progress.direction, &path.direction_zero) {
+                0
+            } else {
+                1
+            };
+
+            directions[index].vehicles.push(Vehicle {
+                vehicle_id: position.vehicle_id.clone(),
+                trip_id: position.trip_id.clone(),
+                distance_along: progress.position.distance_along,
+                scheduled_speed: scheduled_speed(
+                    position,
+                    progress.direction.ordered_shape().length(),
+                ),
+            });
+        }
+
+        for direction in directions.iter_mut() {
+            direction.vehicles.sort_by(|a, b| {
+                b.distance_along
+                    .partial_cmp(&a.distance_along)
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            direction.spacings = direction
+                .vehicles
+                .windows(2)
+                .map(|pair| spacing(&pair[0], &pair[1], thresholds))
+                .collect();
+        }
+
+        BunchingReport {
+            route: path.route,
+            directions,
+            unmatched,
+        }
+    }
+
+    /// Every spacing in this report which is bunched.
+    pub fn bunched(&self) -> Vec<&Spacing> {
+        self.with_status(SpacingStatus::Bunched)
+    }
+
+    /// Every spacing in this report which is a gap.
+    pub fn gaps(&self) -> Vec<&Spacing> {
+        self.with_status(SpacingStatus::Gap)
+    }
+
+    fn with_status(&self, status: SpacingStatus) -> Vec<&Spacing> {
+        self.directions
+            .iter()
+            .flat_map(|direction| direction.spacings.iter())
+            .filter(|spacing| spacing.status == status)
+            .collect()
+    }
+}
+
+fn scheduled_speed(position: &BusPosition, length: f64) -> Option<f64> {
+    let seconds = position
+        .trip_end_time
+        .signed_duration_since(position.trip_start_time)
+        .num_seconds();
+
+    if seconds > 0 && length > 0.0 {
+        Some(length / seconds as f64)
+    } else {
+        None
+    }
+}
+
+fn spacing(leading: &Vehicle, following: &Vehicle, thresholds: &BunchingThresholds) -> Spacing {
+    let distance = leading.distance_along - following.distance_along;
+    let time = following
+        .scheduled_speed
+        .map(|speed| Duration::milliseconds((distance / speed * 1000.0).round() as i64));
+
+    let (bunched_in_time, gap_in_time) = match time {
+        Some(time) => (time < thresholds.bunched_time, time > thresholds.gap_time),
+        None => (false, false),
+    };
+
+    let status = if distance < thresholds.bunched_distance || bunched_in_time {
+        SpacingStatus::Bunched
+    } else if distance > thresholds.gap_distance || gap_in_time {
+        SpacingStatus::Gap
+    } else {
+        SpacingStatus::Normal
+    };
+
+    Spacing {
+        leading: leading.vehicle_id.clone(),
+        following: following.vehicle_id.clone(),
+        distance,
+        time,
+        status,
+    }
+}
@@ -0,0 +1,128 @@
+//! Tests for bus bunching
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::bus::fixtures::{self, PositionBuilder};
+
+#[cfg(test)]
+fn path() -> PathDetails {
+    serde_json::from_str(
+        r#"{
+            "RouteID": "10A",
+            "Name": "10A - PENTAGON",
+            "Direction0": {
+                "TripHeadsign": "PENTAGON",
+                "DirectionText": "WEST",
+                "DirectionNum": "0",
+                "Shape": [
+                    {"Lat": 38.9, "Lon": -77.0, "SeqNum": 1},
+                    {"Lat": 38.9, "Lon": -77.1, "SeqNum": 2}
+                ],
+                "Stops": []
+            },
+            "Direction1": {
+                "TripHeadsign": "HUNTINGTON",
+                "DirectionText": "EAST",
+                "DirectionNum": "1",
+                "Shape": [
+                    {"Lat": 38.9, "Lon": -77.1, "SeqNum": 1},
+                    {"Lat": 38.9, "Lon": -77.0, "SeqNum": 2}
+                ],
+                "Stops": []
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+fn position(vehicle: &str, direction_number: i32, longitude: f64) -> PositionBuilder {
+    fixtures::position()
+        .vehicle(vehicle)
+        .direction(direction_number, "")
+        .longitude(longitude)
+}
+
+#[test]
+fn test_order() {
+    let report = BunchingReport::new(
+        &path(),
+        &fixtures::positions(vec![
+            position("1", 0, -77.01),
+            position("2", 0, -77.05),
+            position("3", 1, -77.05),
+        ]),
+        &BunchingThresholds::default(),
+    );
+
+    assert_eq!(report.route, Route::One0A);
+    assert_eq!(report.directions[0].vehicles[0].vehicle_id, "2");
+    assert_eq!(report.directions[0].vehicles[1].vehicle_id, "1");
+    assert_eq!(report.directions[1].vehicles.len(), 1);
+    assert!(report.directions[1].spacings.is_empty());
+}
+
+#[test]
+fn test_bunched_by_distance() {
+    let report = BunchingReport::new(
+        &path(),
+        &fixtures::positions(vec![position("1", 0, -77.05), position("2", 0, -77.053)]),
+        &BunchingThresholds::default(),
+    );
+
+    let bunched = report.bunched();
+    assert_eq!(bunched.len(), 1);
+    assert_eq!(bunched[0].leading, "2");
+    assert_eq!(bunched[0].following, "1");
+    assert!((bunched[0].distance - 260.0).abs() < 1.0);
+}
+
+#[test]
+fn test_bunched_by_time() {
+    // The whole route is about 8.7 kilometers over an hour, so 500 meters is a little under 4 minutes
+    let thresholds = BunchingThresholds {
+        bunched_time: Duration::minutes(4),
+        ..BunchingThresholds::default()
+    };
+    let report = BunchingReport::new(
+        &path(),
+        &fixtures::positions(vec![position("1", 0, -77.05), position("2", 0, -77.0558)]),
+        &thresholds,
+    );
+
+    assert_eq!(
+        report.directions[0].spacings[0].status,
+        SpacingStatus::Bunched
+    );
+    assert!(report.directions[0].spacings[0].distance > thresholds.bunched_distance);
+}
+
+#[test]
+fn test_gap() {
+    let report = BunchingReport::new(
+        &path(),
+        &fixtures::positions(vec![
+            position("1", 0, -77.01),
+            position("2", 0, -77.03),
+            position("3", 0, -77.09),
+        ]),
+        &BunchingThresholds::default(),
+    );
+
+    let spacings = &report.directions[0].spacings;
+    assert_eq!(spacings[0].status, SpacingStatus::Gap);
+    assert_eq!(spacings[1].status, SpacingStatus::Normal);
+    assert_eq!(report.gaps().len(), 1);
+}
+
+#[test]
+fn test_unmatched() {
+    let report = BunchingReport::new(
+        &path(),
+        &fixtures::positions(vec![position("1", 7, -77.01)]),
+        &BunchingThresholds::default(),
+    );
+
+    assert_eq!(report.unmatched, vec!["1".to_string()]);
+}
@@ -4,23 +4,68 @@ pub mod responses;
 mod tests;
 
 use crate::{
+    builder::Builder,
     bus::{
+        bunching::{BunchingReport, BunchingThresholds},
         traits::{NeedsRoute, NeedsStop},
         urls::URLs,
     },
+    drift::Recorder,
     error::Error,
-    requests::{Fetch, Request as WMATARequest},
+    keys::REDACTED,
+    requests::{Fetch, Request as WMATARequest, Transport, WMATA_API},
+    spatial::SpatialIndex,
     Date, RadiusAtLatLong, Route, Stop,
 };
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "testing")]
+use crate::testing::Cassette;
 
 /// MetroBus client. Used to fetch MetroBus-related information from the WMATA API.
+/// Cheap to clone, with clones sharing a connection pool, and any cache or rate limit.
+#[derive(Clone)]
 pub struct Client {
     /// The WMATA API key to use for all requests routed through this client.
+    /// Empty for clients built with [`Builder::from_key_pool`], which send keys from the pool instead.
     pub key: String,
+    /// Base URL requests are sent to. The WMATA API, unless set with [`Client::with_base_url`].
+    base_url: String,
+    transport: Transport,
+    #[cfg(feature = "testing")]
+    cassette: Option<Cassette>,
+    drift_recorder: Option<Recorder>,
+}
+
+impl Fetch for Client {
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn transport(&self) -> Option<&Transport> {
+        Some(&self.transport)
+    }
+
+    #[cfg(feature = "testing")]
+    fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_ref()
+    }
+
+    fn drift_recorder(&self) -> Option<&Recorder> {
+        self.drift_recorder.as_ref()
+    }
 }
 
-impl Fetch for Client {}
+impl fmt::Debug for Client {
+    /// Never shows the API key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetroBus")
+            .field("key", &REDACTED)
+            .field("base_url", &self.base_url)
+            .field("transport", &self.transport)
+            .finish()
+    }
+}
 
 // Constructor
 impl Client {
@@ -33,10 +78,81 @@ impl Client {
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
     /// ```
     pub fn new(api_key: &str) -> Self {
+        Client::with_base_url(api_key, WMATA_API)
+    }
+
+    /// Constructor for a MetroBus client sending requests somewhere other than the WMATA API,
+    /// like a mirror or the fake server in `wmata::testing`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::MetroBus;
+    ///
+    /// let client = MetroBus::with_base_url("9e38c3eab34c4e6c990828002828f5ed", "http://127.0.0.1:8080");
+    /// ```
+    pub fn with_base_url(api_key: &str, base_url: &str) -> Self {
         Client {
             key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            transport: Transport::default(),
+            #[cfg(feature = "testing")]
+            cassette: None,
+            drift_recorder: None,
         }
     }
+
+    /// Builder for a MetroBus client with timeouts, headers, a proxy, and cache, retry, and rate limit policies.
+    /// See [`crate::builder`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::MetroBus;
+    ///
+    /// let client = MetroBus::builder("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(api_key: &str) -> Builder<Self> {
+        Builder::new(api_key)
+    }
+
+    /// This client, sending requests through `transport`, shared with other clients.
+    pub(crate) fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// This client, deserializing responses strictly and recording schema drift to `recorder`.
+    /// See [`crate::drift`].
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{drift::Recorder, MetroBus};
+    ///
+    /// let recorder = Recorder::default();
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed").with_drift_recorder(recorder.clone());
+    /// ```
+    pub fn with_drift_recorder(mut self, recorder: Recorder) -> Self {
+        self.drift_recorder = Some(recorder);
+        self
+    }
+
+    /// This client, recording its requests to or replaying them from `cassette`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{testing::Cassette, MetroBus};
+    ///
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .with_cassette(Cassette::record("target/cassettes/example.json"));
+    /// ```
+    #[cfg(feature = "testing")]
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
 }
 
 // These don't take Route IDs or Stop IDs
@@ -50,6 +166,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let routes = block_on(async { client.routes().await });
     /// assert!(routes.is_ok());
     /// ```
@@ -71,6 +189,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let stops = block_on(async { client.stops(Some(RadiusAtLatLong::new(1000, 38.8817596, -77.0166426))).await });
     /// assert!(stops.is_ok());
     /// ```
@@ -84,7 +204,7 @@ impl Client {
                 &URLs::Stops.to_string(),
                 Some(
                     radius_at_lat_long
-                        .to_query()
+                        .to_query()?
                         .iter()
                         .map(|(key, value)| (key.as_str(), value.clone()))
                         .collect(),
@@ -115,6 +235,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let positions = block_on(async {
     ///     client.positions_along(
     ///         Some(Route::A2),
@@ -140,6 +262,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let incidents = block_on(async { client.incidents_along(Some(Route::A2)).await });
     /// assert!(incidents.is_ok());
     /// ```
@@ -163,6 +287,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let path = block_on(async { client.path(Route::A2, None).await });
     /// assert!(path.is_ok());
     /// ```
@@ -172,6 +298,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let path = block_on(async { client.path(Route::A2, Some(Date::new(2019, 10, 2))).await });
     /// assert!(path.is_ok());
     /// ```
@@ -200,6 +328,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let route_schedule = block_on(async { client.route_schedule(Route::A2, None, false).await });
     /// assert!(route_schedule.is_ok());
     /// ```
@@ -210,6 +340,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let route_schedule = block_on(async { client.route_schedule(Route::A2, Some(Date::new(2019, 10, 2)), true).await });
     /// assert!(route_schedule.is_ok());
     /// ```
@@ -237,6 +369,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let next_buses = block_on(async { client.next_buses(Stop::new("1001195")).await });
     /// assert!(next_buses.is_ok());
     /// ```
@@ -256,6 +390,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let stop_schedule = block_on(async { client.stop_schedule(Stop::new("1001195"), None).await });
     /// assert!(stop_schedule.is_ok());
     /// ```
@@ -266,6 +402,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
     /// let stop_schedule = block_on(async { client.stop_schedule(Stop::new("1001195"), Some(Date::new(2019, 10, 2))).await });
     /// assert!(stop_schedule.is_ok());
     /// ```
@@ -278,6 +416,119 @@ impl Client {
     }
 }
 
+// Built from multiple endpoints
+impl Client {
+    /// Spacing between buses along both directions of a route, flagging bunched buses and gaps.
+    /// See [`BunchingReport`].
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::{MetroBus, Route};
+    /// use wmata::bus::bunching::BunchingThresholds;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
+    /// let report = block_on(async { client.bunching(Route::A2, &BunchingThresholds::default()).await });
+    /// assert!(report.is_ok());
+    /// ```
+    pub async fn bunching(
+        &self,
+        route: Route,
+        thresholds: &BunchingThresholds,
+    ) -> Result<BunchingReport, Error> {
+        let path = self.path(route, None).await?;
+        let positions = self.positions_along(Some(route), None).await?;
+
+        Ok(BunchingReport::new(&path, &positions, thresholds))
+    }
+
+    /// A [`SpatialIndex`] over every bus stop, for nearest-stop searches without further requests.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::{MetroBus, RadiusAtLatLong};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
+    /// let index = block_on(async { client.stop_index().await }).unwrap();
+    /// let nearby = index.within(&RadiusAtLatLong::new(1000, 38.8664, -76.9859));
+    /// assert!(!nearby.is_empty());
+    /// ```
+    pub async fn stop_index(&self) -> Result<SpatialIndex<responses::StopResponse>, Error> {
+        Ok(SpatialIndex::new(self.stops(None).await?.stops.into_vec()))
+    }
+}
+
+// Built from multiple endpoints
+#[cfg(feature = "gtfs")]
+impl Client {
+    /// A GTFS static feed of every route for a given date. See [`Feed`](crate::gtfs::Feed).
+    ///
+    /// Requests every route's schedule (including variations) and every route variant's path, so this makes
+    /// several hundred requests.
+    pub async fn gtfs(&self, date: Date) -> Result<crate::gtfs::Feed, Error> {
+        let routes = self.routes().await?;
+        let stops = self.stops(None).await?;
+
+        let mut schedules = vec![];
+        let mut paths = vec![];
+        let mut variants: Vec<Route> = vec![];
+
+        for route in routes.routes.iter() {
+            // Variants (e.g.: 10Av1) are included in their base route's schedule
+            if route.route.to_string().contains('v') {
+                continue;
+            }
+
+            let schedule = self.route_schedule(route.route, Some(date), true).await?;
+
+            for trip in schedule
+                .direction_zero
+                .iter()
+                .chain(schedule.direction_one.iter())
+            {
+                if !variants.contains(&trip.route) {
+                    variants.push(trip.route);
+                }
+            }
+
+            schedules.push(schedule);
+        }
+
+        for variant in variants {
+            paths.push(self.path(variant, Some(date)).await?);
+        }
+
+        crate::gtfs::Feed::new(date, &routes, &stops, &paths, &schedules)
+    }
+}
+
+// GTFS Realtime
+#[cfg(feature = "gtfs-rt")]
+impl Client {
+    /// Fetches and decodes one of WMATA's GTFS Realtime feeds. See [`crate::gtfs_rt`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use wmata::{gtfs_rt::RealtimeFeed, MetroBus};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// let feed = block_on(async { client.realtime(RealtimeFeed::BusVehiclePositions).await });
+    /// assert!(feed.is_ok());
+    /// ```
+    pub async fn realtime(
+        &self,
+        feed: crate::gtfs_rt::RealtimeFeed,
+    ) -> Result<crate::gtfs_rt::proto::FeedMessage, Error> {
+        crate::gtfs_rt::fetch(self, &self.key, feed).await
+    }
+}
+
 impl FromStr for Client {
     type Err = Error;
 
@@ -291,6 +542,6 @@ impl FromStr for Client {
     /// assert_eq!(client.key, "9e38c3eab34c4e6c990828002828f5ed");
     /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Client { key: s.to_string() })
+        Ok(Client::new(s))
     }
 }
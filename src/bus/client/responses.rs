@@ -1,20 +1,23 @@
 //! Responses from MetroBus related methods from the WMATA API.
 use crate::{Route, Stop};
 use chrono::{DateTime, FixedOffset};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct BusPositions {
     /// See [`BusPosition`].
     pub bus_positions: Box<[BusPosition]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct BusPosition {
     /// Date and time (Eastern Standard Time) of last position update.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub date_time: DateTime<FixedOffset>,
     /// Deviation, in minutes, from schedule. Positive values indicate that the bus is running late while negative ones are for buses running ahead of schedule.
     pub deviation: f64,
@@ -33,7 +36,10 @@ pub struct BusPosition {
     #[serde(rename = "RouteID")]
     pub route: Route,
     /// Scheduled end date and time (Eastern Standard Time) of the bus's current trip.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub trip_end_time: DateTime<FixedOffset>,
     /// Destination of the bus.
     pub trip_headsign: String,
@@ -41,21 +47,24 @@ pub struct BusPosition {
     #[serde(rename = "TripID")]
     pub trip_id: String,
     /// Scheduled start date and time (Eastern Standard Time) of the bus's current trip.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub trip_start_time: DateTime<FixedOffset>,
     /// Unique identifier for the bus. This is usually visible on the bus itself.
     #[serde(rename = "VehicleID")]
     pub vehicle_id: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Routes {
     /// See [`Route`].
     pub routes: Box<[RouteResponse]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RouteResponse {
     /// Unique identifier for a given route variant. Can be used in various other bus-related methods.
@@ -67,14 +76,14 @@ pub struct RouteResponse {
     pub line_description: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Stops {
     /// See [`Stop`].
     pub stops: Box<[StopResponse]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StopResponse {
     /// 7-digit regional ID which can be used in various bus-related methods. If unavailable, the StopID will be 0 or NULL.
@@ -92,7 +101,7 @@ pub struct StopResponse {
     pub routes: Box<[Route]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Incidents {
     /// See [`Incident`]
@@ -100,11 +109,14 @@ pub struct Incidents {
     pub incidents: Box<[Incident]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Incident {
     /// Date and time (Eastern Standard Time) of last update.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub date_updated: DateTime<FixedOffset>,
     /// Free-text description of the delay or incident.
     pub description: String,
@@ -117,7 +129,7 @@ pub struct Incident {
     pub routes_affected: Box<[Route]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct PathDetails {
     /// [`Route`] of the route.
@@ -137,7 +149,7 @@ pub struct PathDetails {
     pub direction_one: PathDirection,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct PathDirection {
     /// Descriptive text of where the bus is headed. This is similar, but not necessarily identical, to what is displayed on the bus.
@@ -153,7 +165,7 @@ pub struct PathDirection {
     pub stops: Box<[StopRoutes]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct PathShape {
     /// Latitude of stop.
@@ -167,7 +179,7 @@ pub struct PathShape {
     pub sequence_number: i32,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Predictions {
     /// See [`Prediction`].
@@ -176,7 +188,7 @@ pub struct Predictions {
     pub stop_name: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Prediction {
     /// Denotes a binary direction (0 or 1) of the bus. There is no specific mapping to direction, but a different value for the same route signifies that the buses are traveling in opposite directions. Use the DirectionText element to show the actual destination of the bus.
@@ -197,7 +209,7 @@ pub struct Prediction {
     pub vehicle_id: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StopSchedule {
     /// See [`Arrival`].
@@ -207,20 +219,29 @@ pub struct StopSchedule {
     pub stop: StopRoutes,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Arrival {
     /// Date and time (Eastern Standard Time) when the bus is scheduled to stop at this location.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub schedule_time: DateTime<FixedOffset>,
     /// Denotes a binary direction (0 or 1) of the bus. There is no specific mapping to direction, but a different value for the same route signifies that the buses are traveling in opposite directions. Use the TripDirectionText element to show the actual destination of the bus.
     #[serde(rename = "DirectionNum")]
     pub direction_number: String,
     /// Scheduled start date and time (Eastern Standard Time) for this trip.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub start_time: DateTime<FixedOffset>,
     /// Scheduled end date and time (Eastern Standard Time) for this trip.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub end_time: DateTime<FixedOffset>,
     /// [`Route`] of the bus. Bus route variant identifier (pattern). This variant can be used in several other bus methods which accept variants. Note that customers will never see anything other than the base route name, so variants 10A, 10Av1, 10Av2, etc. will be displayed as 10A on the bus.
     #[serde(rename = "RouteID")]
@@ -234,7 +255,7 @@ pub struct Arrival {
     pub trip_id: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StopRoutes {
     /// 7-digit regional ID which can be used in various bus-related methods. If unavailable, the StopID will be 0 or NULL.
@@ -252,7 +273,7 @@ pub struct StopRoutes {
     pub routes: Box<[Route]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RouteSchedule {
     /// Descriptive name for the route.
@@ -265,7 +286,7 @@ pub struct RouteSchedule {
     pub direction_one: Box<[RouteInfo]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RouteInfo {
     /// [`Route`] of the route. Bus route variant. This can be used in several other bus methods which accept variants.
@@ -279,10 +300,16 @@ pub struct RouteInfo {
     /// Descriptive text of where the bus is headed. This is similar, but not necessarily identical, to what is displayed on the bus.
     pub trip_headsign: String,
     /// Scheduled start date and time (Eastern Standard Time) for this trip. Will be in YYYY-MM-DDTHH:mm:ss format (e.g.: 2014-10-27T13:17:00).
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub start_time: DateTime<FixedOffset>,
     /// Scheduled end date and time (Eastern Standard Time) for this trip. Will be in YYYY-MM-DDTHH:mm:ss format (e.g.: 2014-10-27T13:17:00).
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub end_time: DateTime<FixedOffset>,
     /// See [`StopInfo`].
     pub stop_times: Box<[StopInfo]>,
@@ -291,7 +318,7 @@ pub struct RouteInfo {
     pub trip_id: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StopInfo {
     /// 7-digit regional ID which can be used in various bus-related methods. If unavailable, the StopID will be 0 or NULL.
@@ -303,6 +330,9 @@ pub struct StopInfo {
     #[serde(rename = "StopSeq")]
     pub stop_sequence: i32,
     /// Scheduled departure date and time (Eastern Standard Time) from this stop.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub time: DateTime<FixedOffset>,
 }
@@ -1,8 +1,20 @@
+//! Tests for MetroBus Client
 #[cfg(test)]
 use super::*;
+
+#[cfg(test)]
+use crate::testing::FakeServer;
 #[cfg(test)]
 use tokio_test::block_on;
 
+#[cfg(test)]
+fn query(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
 #[test]
 fn test_constructor() {
     let client = Client::new("9e38c3eab34c4e6c990828002828f5ed");
@@ -11,36 +23,57 @@ fn test_constructor() {
 }
 
 #[test]
-fn test_routes() {
+fn test_from_str() {
     let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+
+    assert_eq!(client.key, "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.base_url, "https://api.wmata.com");
+}
+
+#[test]
+fn test_routes() {
+    let server = FakeServer::start();
+    let client = server.bus();
     let routes = block_on(async { client.routes().await });
 
-    assert_eq!(routes.unwrap().routes.len(), 470);
+    assert_eq!(routes.unwrap().routes.len(), 3);
 }
 
 #[test]
 fn test_stops() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let stops = block_on(async { client.stops(None).await });
 
-    assert_eq!(stops.unwrap().stops.len(), 10299);
+    assert_eq!(stops.unwrap().stops.len(), 3);
+    assert!(server.requests()[0].query.is_empty());
 }
 
 #[test]
 fn test_stops_lat_long_radius() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let stops = block_on(async {
         client
             .stops(Some(RadiusAtLatLong::new(1000, 38.8817596, -77.0166426)))
             .await
     });
 
-    assert_eq!(stops.unwrap().stops.len(), 58);
+    assert_eq!(stops.unwrap().stops.len(), 3);
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[
+            ("Radius", "1000"),
+            ("Lat", "38.8817596"),
+            ("Lon", "-77.0166426")
+        ])
+    );
 }
 
 #[test]
 fn test_positions_along() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let positions = block_on(async { client.positions_along(None, None).await });
 
     assert!(positions.is_ok());
@@ -48,15 +81,18 @@ fn test_positions_along() {
 
 #[test]
 fn test_positions_along_with_route() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let positions = block_on(async { client.positions_along(Some(Route::One0A), None).await });
 
-    assert!(positions.is_ok());
+    assert_eq!(positions.unwrap().bus_positions[0].route, Route::One0A);
+    assert_eq!(server.requests()[0].query, query(&[("RouteID", "10A")]));
 }
 
 #[test]
 fn test_positions_along_with_route_and_lat_long_radius() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let positions = block_on(async {
         client
             .positions_along(
@@ -67,11 +103,21 @@ fn test_positions_along_with_route_and_lat_long_radius() {
     });
 
     assert!(positions.is_ok());
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[
+            ("RouteID", "10A"),
+            ("Radius", "1000"),
+            ("Lat", "38.8817596"),
+            ("Lon", "-77.0166426")
+        ])
+    );
 }
 
 #[test]
 fn test_incidents_along() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let incidents = block_on(async { client.incidents_along(None).await });
 
     assert!(incidents.is_ok());
@@ -79,15 +125,21 @@ fn test_incidents_along() {
 
 #[test]
 fn test_incidents_along_route() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let incidents = block_on(async { client.incidents_along(Some(Route::One0A)).await });
 
-    assert!(incidents.is_ok());
+    assert_eq!(
+        incidents.unwrap().incidents[0].routes_affected[0],
+        Route::One0A
+    );
+    assert_eq!(server.requests()[0].query, query(&[("Route", "10A")]));
 }
 
 #[test]
 fn test_path() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let path = block_on(async { client.path(Route::One0A, None).await });
 
     assert_eq!(path.unwrap().route, Route::One0A);
@@ -95,7 +147,8 @@ fn test_path() {
 
 #[test]
 fn test_path_with_date() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let path = block_on(async {
         client
             .path(Route::One0A, Some(Date::new(2019, 10, 1)))
@@ -103,11 +156,16 @@ fn test_path_with_date() {
     });
 
     assert_eq!(path.unwrap().route, Route::One0A);
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[("RouteID", "10A"), ("Date", "2019-10-01")])
+    );
 }
 
 #[test]
 fn test_route_schedule() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let route_schedule = block_on(async { client.route_schedule(Route::One0A, None, false).await });
 
     assert_eq!(
@@ -118,18 +176,24 @@ fn test_route_schedule() {
 
 #[test]
 fn test_route_schedule_with_variations() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let route_schedule = block_on(async { client.route_schedule(Route::One0A, None, true).await });
 
     assert_eq!(
         route_schedule.unwrap().name,
         "10A - PENTAGON - HUNTINGTON STA"
     );
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[("RouteID", "10A"), ("IncludingVariations", "true")])
+    );
 }
 
 #[test]
 fn test_route_schedule_with_date() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let route_schedule = block_on(async {
         client
             .route_schedule(Route::One0A, Some(Date::new(2019, 10, 2)), true)
@@ -144,7 +208,8 @@ fn test_route_schedule_with_date() {
 
 #[test]
 fn test_stop_schedule() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let stop_schedule = block_on(async { client.stop_schedule(Stop::new("1001195"), None).await });
 
     assert_eq!(
@@ -155,7 +220,8 @@ fn test_stop_schedule() {
 
 #[test]
 fn test_stop_schedule_with_date() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.bus();
     let stop_schedule = block_on(async {
         client
             .stop_schedule(Stop::new("1001195"), Some(Date::new(2019, 10, 2)))
@@ -166,4 +232,18 @@ fn test_stop_schedule_with_date() {
         stop_schedule.unwrap().stop.stop.unwrap(),
         Stop("1001195".to_string())
     );
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[("StopID", "1001195"), ("Date", "2019-10-02")])
+    );
+}
+
+#[test]
+fn test_next_buses() {
+    let server = FakeServer::start();
+    let client = server.bus();
+    let predictions = block_on(async { client.next_buses(Stop::new("1001195")).await });
+
+    assert_eq!(predictions.unwrap().predictions[0].route, Route::A2);
+    assert_eq!(server.requests()[0].query, query(&[("StopID", "1001195")]));
 }
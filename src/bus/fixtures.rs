@@ -0,0 +1,113 @@
+//! Bus positions for tests, setting only the fields each test cares about.
+use crate::bus::client::responses::{BusPosition, BusPositions};
+use serde_json::json;
+
+/// Builder for a [`BusPosition`]. Unless set, vehicle 1 on trip 1 of route 10A, direction 0,
+/// on time at 38.9, -77.0 at 08:15 on October 1st, 2019.
+pub(crate) struct PositionBuilder {
+    vehicle_id: String,
+    trip_id: String,
+    route: String,
+    direction_number: i32,
+    direction_text: String,
+    trip_headsign: String,
+    latitude: f64,
+    longitude: f64,
+    time: String,
+    deviation: f64,
+}
+
+/// A [`PositionBuilder`] with the defaults.
+pub(crate) fn position() -> PositionBuilder {
+    PositionBuilder {
+        vehicle_id: "1".to_string(),
+        trip_id: "1".to_string(),
+        route: "10A".to_string(),
+        direction_number: 0,
+        direction_text: String::new(),
+        trip_headsign: String::new(),
+        latitude: 38.9,
+        longitude: -77.0,
+        time: "08:15:00".to_string(),
+        deviation: 0.0,
+    }
+}
+
+/// Every position built, as a single response.
+pub(crate) fn positions(positions: Vec<PositionBuilder>) -> BusPositions {
+    BusPositions {
+        bus_positions: positions.into_iter().map(PositionBuilder::build).collect(),
+    }
+}
+
+impl PositionBuilder {
+    /// Vehicle ID, also used as the trip ID unless the trip is set afterwards.
+    pub fn vehicle(mut self, vehicle_id: &str) -> Self {
+        self.vehicle_id = vehicle_id.to_string();
+        self.trip_id = vehicle_id.to_string();
+        self
+    }
+
+    pub fn trip(mut self, trip_id: &str) -> Self {
+        self.trip_id = trip_id.to_string();
+        self
+    }
+
+    /// Route ID, e.g.: `A2`.
+    pub fn route(mut self, route: &str) -> Self {
+        self.route = route.to_string();
+        self
+    }
+
+    pub fn direction(mut self, direction_number: i32, direction_text: &str) -> Self {
+        self.direction_number = direction_number;
+        self.direction_text = direction_text.to_string();
+        self
+    }
+
+    pub fn headsign(mut self, trip_headsign: &str) -> Self {
+        self.trip_headsign = trip_headsign.to_string();
+        self
+    }
+
+    pub fn at(mut self, latitude: f64, longitude: f64) -> Self {
+        self.latitude = latitude;
+        self.longitude = longitude;
+        self
+    }
+
+    pub fn longitude(mut self, longitude: f64) -> Self {
+        self.longitude = longitude;
+        self
+    }
+
+    /// Time of day on October 1st, 2019, e.g.: `08:15:00`.
+    pub fn time(mut self, time: &str) -> Self {
+        self.time = time.to_string();
+        self
+    }
+
+    /// Minutes behind schedule.
+    pub fn deviation(mut self, deviation: f64) -> Self {
+        self.deviation = deviation;
+        self
+    }
+
+    pub fn build(self) -> BusPosition {
+        serde_json::from_value(json!({
+            "DateTime": format!("2019-10-01T{}", self.time),
+            "Deviation": self.deviation,
+            "DirectionNum": self.direction_number,
+            "DirectionText": self.direction_text,
+            "Lat": self.latitude,
+            "Lon": self.longitude,
+            "RouteID": self.route,
+            "TripEndTime": "2019-10-01T09:00:00",
+            "TripHeadsign": self.trip_headsign,
+            "TripID": self.trip_id,
+            "TripStartTime": "2019-10-01T08:00:00",
+            "VehicleID": self.vehicle_id,
+        }))
+        .unwrap()
+    }
+}
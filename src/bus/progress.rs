@@ -0,0 +1,93 @@
+//! Where a bus is along its route: the stops around it, and how much of its trip is complete.
+mod tests;
+
+use crate::bus::{
+    client::responses::{BusPosition, PathDetails, PathDirection, StopRoutes},
+    shape::SnappedPosition,
+};
+
+/// Progress of a bus along one direction of its route.
+#[derive(Debug)]
+pub struct BusProgress<'a> {
+    /// Direction of the route the bus is traveling in.
+    pub direction: &'a PathDirection,
+    /// The bus, snapped onto the shape of the direction.
+    pub position: SnappedPosition,
+    /// Last stop the bus has passed, if any.
+    pub previous_stop: Option<&'a StopRoutes>,
+    /// Next stop the bus will reach, if any.
+    pub next_stop: Option<&'a StopRoutes>,
+    /// Distance (in meters) along the route to the next stop.
+    pub distance_to_next_stop: Option<f64>,
+    /// Percentage, from 0 to 100, of the route's length the bus has traveled.
+    pub percent_complete: f64,
+}
+
+impl PathDetails {
+    /// Direction of this route that a bus is traveling in, matched by direction number
+    /// and then by direction text (e.g.: NORTH).
+    pub fn direction_of(&self, position: &BusPosition) -> Option<&PathDirection> {
+        let directions = [&self.direction_zero, &self.direction_one];
+
+        directions
+            .iter()
+            .find(|direction| {
+                direction.direction_number.trim() == position.direction_number.to_string()
+            })
+            .or_else(|| {
+                directions.iter().find(|direction| {
+                    direction
+                        .direction_text
+                        .eq_ignore_ascii_case(&position.direction_text)
+                })
+            })
+            .copied()
+    }
+}
+
+impl BusPosition {
+    /// Where this bus is along a route, given the route's path details.
+    ///
+    /// Returns None if the bus' direction is not part of this route, or the direction has no shape.
+    pub fn progress_along<'a>(&self, path: &'a PathDetails) -> Option<BusProgress<'a>> {
+        let direction = path.direction_of(self)?;
+        let shape = direction.ordered_shape();
+        let position = shape.snap(self)?;
+
+        let mut previous_stop = None;
+        let mut next_stop = None;
+        let mut segment = 0;
+
+        for stop in direction.stops.iter() {
+            let snapped = match shape.snap_from(stop, segment) {
+                Some(snapped) => snapped,
+                None => continue,
+            };
+
+            segment = snapped.segment;
+
+            if snapped.distance_along <= position.distance_along {
+                previous_stop = Some(stop);
+            } else {
+                next_stop = Some((stop, snapped.distance_along - position.distance_along));
+                break;
+            }
+        }
+
+        let length = shape.length();
+        let percent_complete = if length > 0.0 {
+            position.distance_along / length * 100.0
+        } else {
+            0.0
+        };
+
+        Some(BusProgress {
+            direction,
+            position,
+            previous_stop,
+            next_stop: next_stop.map(|(stop, _)| stop),
+            distance_to_next_stop: next_stop.map(|(_, distance)| distance),
+            percent_complete,
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! Tests for bus progress along a route
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::bus::fixtures;
+
+#[cfg(test)]
+fn stop(id: &str, longitude: f64) -> String {
+    format!(
+        r#"{{"StopID": "{id}", "Name": "STOP {id}", "Lat": 38.9, "Lon": {longitude}, "Routes": ["10A"]}}"#,
+        id = id,
+        longitude = longitude
+    )
+}
+
+#[cfg(test)]
+fn path() -> PathDetails {
+    serde_json::from_str(&format!(
+        r#"{{
+            "RouteID": "10A",
+            "Name": "10A - PENTAGON",
+            "Direction0": {{
+                "TripHeadsign": "PENTAGON",
+                "DirectionText": "WEST",
+                "DirectionNum": "0",
+                "Shape": [
+                    {{"Lat": 38.9, "Lon": -77.0, "SeqNum": 1}},
+                    {{"Lat": 38.9, "Lon": -77.01, "SeqNum": 2}},
+                    {{"Lat": 38.9, "Lon": -77.02, "SeqNum": 3}}
+                ],
+                "Stops": [{}, {}, {}]
+            }},
+            "Direction1": {{
+                "TripHeadsign": "HUNTINGTON",
+                "DirectionText": "EAST",
+                "DirectionNum": "1",
+                "Shape": [],
+                "Stops": []
+            }}
+        }}"#,
+        stop("1000001", -77.0),
+        stop("1000002", -77.01),
+        stop("1000003", -77.02)
+    ))
+    .unwrap()
+}
+
+#[cfg(test)]
+fn position(direction_number: i32, direction_text: &str, longitude: f64) -> BusPosition {
+    fixtures::position()
+        .direction(direction_number, direction_text)
+        .headsign("PENTAGON")
+        .at(38.9001, longitude)
+        .build()
+}
+
+#[test]
+fn test_progress() {
+    let path = path();
+    let progress = position(0, "WEST", -77.015).progress_along(&path).unwrap();
+
+    assert_eq!(progress.direction.trip_headsign, "PENTAGON");
+    assert_eq!(progress.previous_stop.unwrap().name, "STOP 1000002");
+    assert_eq!(progress.next_stop.unwrap().name, "STOP 1000003");
+    assert!((progress.percent_complete - 75.0).abs() < 0.1);
+    assert!((progress.distance_to_next_stop.unwrap() - 433.0).abs() < 1.0);
+}
+
+#[test]
+fn test_progress_before_first_stop() {
+    let path = path();
+    let progress = position(0, "WEST", -76.99).progress_along(&path).unwrap();
+
+    assert_eq!(progress.previous_stop.unwrap().name, "STOP 1000001");
+    assert_eq!(progress.next_stop.unwrap().name, "STOP 1000002");
+    assert_eq!(progress.percent_complete, 0.0);
+}
+
+#[test]
+fn test_progress_past_last_stop() {
+    let path = path();
+    let progress = position(0, "WEST", -77.03).progress_along(&path).unwrap();
+
+    assert_eq!(progress.previous_stop.unwrap().name, "STOP 1000003");
+    assert!(progress.next_stop.is_none());
+    assert!(progress.distance_to_next_stop.is_none());
+}
+
+#[test]
+fn test_direction_by_text() {
+    let path = path();
+
+    assert_eq!(
+        path.direction_of(&position(5, "west", -77.0))
+            .unwrap()
+            .trip_headsign,
+        "PENTAGON"
+    );
+    assert!(path.direction_of(&position(5, "NORTH", -77.0)).is_none());
+}
+
+#[test]
+fn test_progress_without_shape() {
+    let path = path();
+
+    assert!(position(1, "EAST", -77.0).progress_along(&path).is_none());
+}
@@ -8,7 +8,7 @@ use crate::{
 };
 use serde::{
     de::{Deserializer, Error as SerdeError},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 use std::{error, fmt, str::FromStr};
 
@@ -17,7 +17,7 @@ use std::{error, fmt, str::FromStr};
 /// Some routes' name begins with a number (i.e. 10A). This is not allowed
 /// by Rust naming conventions. As a fix, the first number of the route
 /// has been replaced by a word. So, `10A` => `One0A`. This is unfortunate.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Route {
     One0A,
     One0B,
@@ -515,7 +515,7 @@ impl Route {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/54763629281d83086473f231/operations/5476362a281d830c946a3d68?)
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// use wmata::{Route, RadiusAtLatLong};
     /// use tokio_test::block_on;
     ///
@@ -542,7 +542,7 @@ impl Route {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/54763641281d83086473f232/operations/54763641281d830c946a3d75)
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Route;
     /// use tokio_test::block_on;
     ///
@@ -561,7 +561,7 @@ impl Route {
     /// Omit date for current date
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Route;
     /// use tokio_test::block_on;
     ///
@@ -569,7 +569,7 @@ impl Route {
     /// assert!(path.is_ok());
     /// ```
     /// With a date
-    /// ```
+    /// ```no_run
     /// use wmata::{Route, Date};
     /// use tokio_test::block_on;
     ///
@@ -596,7 +596,7 @@ impl Route {
     /// data for all variations of B30 such as B30v1, B30v2, etc. will be returned.
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Route;
     /// use tokio_test::block_on;
     ///
@@ -605,7 +605,7 @@ impl Route {
     /// ```
     ///
     /// with date and variations
-    /// ```
+    /// ```no_run
     /// use wmata::{Route, Date};
     /// use tokio_test::block_on;
     ///
@@ -634,6 +634,15 @@ impl<'de> Deserialize<'de> for Route {
     }
 }
 
+impl Serialize for Route {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl ToString for Route {
     fn to_string(&self) -> String {
         match self {
@@ -0,0 +1,355 @@
+//! Route shapes: ordering, simplification, encoded polylines, and snapping buses onto a route.
+mod tests;
+
+use crate::{
+    bus::client::responses::{BusPosition, PathDirection},
+    location::{LatLong, EARTH_RADIUS},
+};
+use std::{error, fmt};
+
+/// Scale of coordinates in the encoded polyline format, which keeps five decimal places.
+const POLYLINE_PRECISION: f64 = 1e5;
+
+/// An ordered list of (latitude, longitude) points along a route.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shape {
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Where a point lies along a [`Shape`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SnappedPosition {
+    /// Closest (latitude, longitude) on the shape.
+    pub point: (f64, f64),
+    /// Index of the segment of the shape the point was snapped onto.
+    pub segment: usize,
+    /// Distance (in meters) from the start of the shape to the snapped point.
+    pub distance_along: f64,
+    /// Distance (in meters) from the original point to the snapped point.
+    pub distance_from: f64,
+}
+
+impl Shape {
+    /// Constructor for a shape from points already in order.
+    pub fn new(points: Vec<(f64, f64)>) -> Self {
+        Shape { points }
+    }
+
+    /// Length (in meters) of this shape.
+    pub fn length(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|pair| pair[0].distance_to(&pair[1]))
+            .sum()
+    }
+
+    /// Simplifies this shape with the Douglas–Peucker algorithm, dropping points
+    /// which are within `tolerance` meters of the simplified line. The first and last points are always kept.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::bus::shape::Shape;
+    ///
+    /// let shape = Shape::new(vec![(38.9, -77.0), (38.90001, -77.005), (38.9, -77.01)]);
+    /// assert_eq!(shape.simplify(5.0).points, vec![(38.9, -77.0), (38.9, -77.01)]);
+    /// ```
+    pub fn simplify(&self, tolerance: f64) -> Shape {
+        if self.points.len() < 3 {
+            return self.clone();
+        }
+
+        let projection = Projection::around(&self.points);
+        let projected: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|point| projection.project(point))
+            .collect();
+
+        let mut keep = vec![false; self.points.len()];
+        keep[0] = true;
+        keep[self.points.len() - 1] = true;
+
+        let mut ranges = vec![(0, self.points.len() - 1)];
+
+        while let Some((start, end)) = ranges.pop() {
+            let mut farthest = (0.0, start);
+
+            for index in (start + 1)..end {
+                let (_, distance) =
+                    closest_on_segment(projected[index], projected[start], projected[end]);
+
+                if distance > farthest.0 {
+                    farthest = (distance, index);
+                }
+            }
+
+            if farthest.0 > tolerance {
+                keep[farthest.1] = true;
+                ranges.push((start, farthest.1));
+                ranges.push((farthest.1, end));
+            }
+        }
+
+        Shape::new(
+            self.points
+                .iter()
+                .zip(keep)
+                .filter(|(_, keep)| *keep)
+                .map(|(point, _)| *point)
+                .collect(),
+        )
+    }
+
+    /// Encodes this shape in [Google's encoded polyline format](https://developers.google.com/maps/documentation/utilities/polylinealgorithm).
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::bus::shape::Shape;
+    ///
+    /// let shape = Shape::new(vec![(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)]);
+    /// assert_eq!(shape.encode(), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    /// ```
+    pub fn encode(&self) -> String {
+        let mut encoded = String::new();
+        let mut previous = (0, 0);
+
+        for (latitude, longitude) in self.points.iter() {
+            let current = (
+                (latitude * POLYLINE_PRECISION).round() as i64,
+                (longitude * POLYLINE_PRECISION).round() as i64,
+            );
+
+            encode_value(current.0 - previous.0, &mut encoded);
+            encode_value(current.1 - previous.1, &mut encoded);
+
+            previous = current;
+        }
+
+        encoded
+    }
+
+    /// Decodes a shape from Google's encoded polyline format.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::bus::shape::Shape;
+    ///
+    /// let shape = Shape::decode("_p~iF~ps|U_ulLnnqC_mqNvxq`@").unwrap();
+    /// assert_eq!(shape.points, vec![(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)]);
+    /// ```
+    pub fn decode(encoded: &str) -> Result<Self, InvalidPolylineError> {
+        let mut values = vec![];
+        let mut value: i64 = 0;
+        let mut shift = 0;
+
+        for byte in encoded.bytes() {
+            if !(63..=126).contains(&byte) || shift > 60 {
+                return Err(InvalidPolylineError);
+            }
+
+            let chunk = i64::from(byte - 63);
+            value |= (chunk & 0x1f) << shift;
+            shift += 5;
+
+            if chunk < 0x20 {
+                values.push(if value & 1 == 1 {
+                    !(value >> 1)
+                } else {
+                    value >> 1
+                });
+                value = 0;
+                shift = 0;
+            }
+        }
+
+        if shift != 0 || values.len() % 2 != 0 {
+            return Err(InvalidPolylineError);
+        }
+
+        let mut current = (0, 0);
+
+        Ok(Shape::new(
+            values
+                .chunks(2)
+                .map(|pair| {
+                    current = (current.0 + pair[0], current.1 + pair[1]);
+
+                    (
+                        current.0 as f64 / POLYLINE_PRECISION,
+                        current.1 as f64 / POLYLINE_PRECISION,
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Closest position on this shape to a point. Returns None if this shape has no points.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::bus::shape::Shape;
+    ///
+    /// let shape = Shape::new(vec![(38.9, -77.0), (38.9, -77.01)]);
+    /// let snapped = shape.snap(&(38.9001, -77.005)).unwrap();
+    ///
+    /// assert_eq!(snapped.segment, 0);
+    /// assert!(snapped.distance_from < 12.0);
+    /// ```
+    pub fn snap<T>(&self, point: &T) -> Option<SnappedPosition>
+    where
+        T: LatLong + ?Sized,
+    {
+        self.snap_from(point, 0)
+    }
+
+    /// Closest position on this shape to a point, considering only segments from `segment` onward.
+    /// Useful for snapping points which are known to be in order along the shape (e.g.: stops),
+    /// so that routes which double back on themselves are handled correctly.
+    pub fn snap_from<T>(&self, point: &T, segment: usize) -> Option<SnappedPosition>
+    where
+        T: LatLong + ?Sized,
+    {
+        let first = *self.points.first()?;
+
+        if self.points.len() == 1 {
+            return Some(SnappedPosition {
+                point: first,
+                segment: 0,
+                distance_along: 0.0,
+                distance_from: point.distance_to(&first),
+            });
+        }
+
+        let projection = Projection::around(&self.points);
+        let target = projection.project(&(point.latitude(), point.longitude()));
+
+        let mut best: Option<(f64, usize, f64)> = None;
+
+        for (segment, pair) in self.points.windows(2).enumerate().skip(segment) {
+            let (fraction, distance) = closest_on_segment(
+                target,
+                projection.project(&pair[0]),
+                projection.project(&pair[1]),
+            );
+
+            let closer = match best {
+                Some((best_distance, _, _)) => distance < best_distance,
+                None => true,
+            };
+
+            if closer {
+                best = Some((distance, segment, fraction));
+            }
+        }
+
+        let (_, segment, fraction) = best?;
+        let (start, end) = (self.points[segment], self.points[segment + 1]);
+        let snapped = (
+            start.0 + (end.0 - start.0) * fraction,
+            start.1 + (end.1 - start.1) * fraction,
+        );
+
+        let distance_along = self.points[..=segment]
+            .windows(2)
+            .map(|pair| pair[0].distance_to(&pair[1]))
+            .sum::<f64>()
+            + start.distance_to(&snapped);
+
+        Some(SnappedPosition {
+            point: snapped,
+            segment,
+            distance_along,
+            distance_from: point.distance_to(&snapped),
+        })
+    }
+}
+
+impl PathDirection {
+    /// The shape of this direction, ordered by sequence number.
+    pub fn ordered_shape(&self) -> Shape {
+        let mut points: Vec<_> = self.shape.iter().collect();
+        points.sort_by_key(|point| point.sequence_number);
+
+        Shape::new(
+            points
+                .iter()
+                .map(|point| (point.latitude, point.longitude))
+                .collect(),
+        )
+    }
+}
+
+impl BusPosition {
+    /// Snaps this bus onto the shape of a route direction, giving its distance along the route.
+    pub fn snap_to(&self, direction: &PathDirection) -> Option<SnappedPosition> {
+        direction.ordered_shape().snap(self)
+    }
+}
+
+/// An error indicating that the provided string is not a valid encoded polyline.
+#[derive(Debug, Clone)]
+pub struct InvalidPolylineError;
+
+impl fmt::Display for InvalidPolylineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Provided string is not a valid encoded polyline.")
+    }
+}
+
+impl error::Error for InvalidPolylineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+fn encode_value(value: i64, encoded: &mut String) {
+    let mut value = if value < 0 { !(value << 1) } else { value << 1 };
+
+    while value >= 0x20 {
+        encoded.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+        value >>= 5;
+    }
+
+    encoded.push((value as u8 + 63) as char);
+}
+
+/// An equirectangular projection to meters, accurate over the span of a single route.
+struct Projection {
+    meters_per_radian_longitude: f64,
+}
+
+impl Projection {
+    fn around(points: &[(f64, f64)]) -> Self {
+        let latitude = points.iter().map(|point| point.0).sum::<f64>() / points.len() as f64;
+
+        Projection {
+            meters_per_radian_longitude: EARTH_RADIUS * latitude.to_radians().cos(),
+        }
+    }
+
+    fn project(&self, point: &(f64, f64)) -> (f64, f64) {
+        (
+            point.1.to_radians() * self.meters_per_radian_longitude,
+            point.0.to_radians() * EARTH_RADIUS,
+        )
+    }
+}
+
+/// Fraction along a segment of the closest point to `point`, and the distance to it.
+fn closest_on_segment(point: (f64, f64), start: (f64, f64), end: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length_squared = dx * dx + dy * dy;
+
+    let fraction = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    let closest = (start.0 + dx * fraction, start.1 + dy * fraction);
+
+    (
+        fraction,
+        ((point.0 - closest.0).powi(2) + (point.1 - closest.1).powi(2)).sqrt(),
+    )
+}
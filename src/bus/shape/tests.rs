@@ -0,0 +1,110 @@
+//! Tests for route shapes
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn direction() -> PathDirection {
+    serde_json::from_str(
+        r#"{
+            "TripHeadsign": "PENTAGON",
+            "DirectionText": "EAST",
+            "DirectionNum": "0",
+            "Shape": [
+                {"Lat": 38.9, "Lon": -77.01, "SeqNum": 3},
+                {"Lat": 38.9, "Lon": -77.0, "SeqNum": 1},
+                {"Lat": 38.9, "Lon": -77.005, "SeqNum": 2}
+            ],
+            "Stops": []
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ordered_shape() {
+    assert_eq!(
+        direction().ordered_shape().points,
+        vec![(38.9, -77.0), (38.9, -77.005), (38.9, -77.01)]
+    );
+}
+
+#[test]
+fn test_simplify_keeps_corners() {
+    let shape = Shape::new(vec![
+        (38.9, -77.0),
+        (38.9, -77.005),
+        (38.905, -77.005),
+        (38.905, -77.01),
+    ]);
+
+    assert_eq!(shape.simplify(1.0), shape);
+    assert_eq!(
+        shape.simplify(10_000.0).points,
+        vec![(38.9, -77.0), (38.905, -77.01)]
+    );
+}
+
+#[test]
+fn test_simplify_short_shape() {
+    let shape = Shape::new(vec![(38.9, -77.0), (38.9, -77.01)]);
+
+    assert_eq!(shape.simplify(100.0), shape);
+}
+
+#[test]
+fn test_encode_negative_and_small_values() {
+    let shape = Shape::new(vec![(0.0, 0.0), (-0.00001, 0.00001)]);
+
+    assert_eq!(Shape::decode(&shape.encode()).unwrap(), shape);
+}
+
+#[test]
+fn test_round_trip() {
+    let shape = direction().ordered_shape();
+
+    assert_eq!(Shape::decode(&shape.encode()).unwrap(), shape);
+}
+
+#[test]
+fn test_decode_invalid() {
+    // Truncated in the middle of a value
+    assert!(Shape::decode("_p~iF~ps|U_").is_err());
+    // Odd number of values
+    assert!(Shape::decode("_p~iF").is_err());
+    // Outside of the encoding's alphabet
+    assert!(Shape::decode("_p~iF ps|U").is_err());
+}
+
+#[test]
+fn test_snap() {
+    let shape = direction().ordered_shape();
+    let snapped = shape.snap(&(38.9001, -77.0075)).unwrap();
+
+    assert_eq!(snapped.segment, 1);
+    assert!((snapped.point.1 + 77.0075).abs() < 1e-6);
+    assert!((snapped.distance_from - 11.1).abs() < 0.5);
+    assert!((snapped.distance_along - shape.length() * 0.75).abs() < 1.0);
+}
+
+#[test]
+fn test_snap_before_start() {
+    let snapped = direction().ordered_shape().snap(&(38.9, -76.99)).unwrap();
+
+    assert_eq!(snapped.point, (38.9, -77.0));
+    assert_eq!(snapped.distance_along, 0.0);
+}
+
+#[test]
+fn test_snap_empty() {
+    assert!(Shape::default().snap(&(38.9, -77.0)).is_none());
+}
+
+#[test]
+fn test_snap_from() {
+    // Out and back along the same street
+    let shape = Shape::new(vec![(38.9, -77.0), (38.9, -77.01), (38.9, -77.0)]);
+
+    assert_eq!(shape.snap(&(38.9, -77.005)).unwrap().segment, 0);
+    assert_eq!(shape.snap_from(&(38.9, -77.005), 1).unwrap().segment, 1);
+    assert!(shape.snap_from(&(38.9, -77.005), 2).is_none());
+}
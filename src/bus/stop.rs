@@ -7,7 +7,7 @@ use crate::{
 };
 use serde::{
     de::{Deserializer, Error as SerdeError},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 
 #[derive(Debug)]
@@ -29,7 +29,7 @@ impl Stop {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/5476365e031f590f38092508/operations/5476365e031f5909e4fe331d)
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Stop;
     /// use tokio_test::block_on;
     ///
@@ -47,7 +47,7 @@ impl Stop {
     /// Omit date for current date
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Stop;
     /// use tokio_test::block_on;
     ///
@@ -57,7 +57,7 @@ impl Stop {
     /// ```
     ///
     /// with date
-    /// ```
+    /// ```no_run
     /// use wmata::{Stop, Date};
     /// use tokio_test::block_on;
     ///
@@ -89,6 +89,15 @@ impl<'de> Deserialize<'de> for Stop {
     }
 }
 
+impl Serialize for Stop {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
 impl PartialEq for Stop {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
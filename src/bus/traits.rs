@@ -18,11 +18,11 @@ pub trait NeedsRoute: Fetch {
         let mut query = vec![];
 
         if let Some(route) = route {
-            query.push(("Route".to_string(), route.to_string()));
+            query.push(("RouteID".to_string(), route.to_string()));
         }
 
         if let Some(radius_at_lat_long) = radius_at_lat_long {
-            query.append(&mut radius_at_lat_long.to_query());
+            query.append(&mut radius_at_lat_long.to_query()?);
         }
 
         let query: Vec<(&str, String)> = query
@@ -1,4 +1,5 @@
 //! URLs of MetroBus-related endpoints
+#[derive(Debug, Copy, Clone)]
 pub enum URLs {
     Routes,
     Stops,
@@ -10,6 +11,21 @@ pub enum URLs {
     StopSchedule,
 }
 
+impl URLs {
+    /// Every endpoint, for naming a request by its URL.
+    #[cfg(feature = "tracing")]
+    pub const ALL: [URLs; 8] = [
+        URLs::Routes,
+        URLs::Stops,
+        URLs::Incidents,
+        URLs::Positions,
+        URLs::PathDetails,
+        URLs::RouteSchedule,
+        URLs::NextBuses,
+        URLs::StopSchedule,
+    ];
+}
+
 impl ToString for URLs {
     fn to_string(&self) -> String {
         match self {
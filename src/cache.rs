@@ -0,0 +1,164 @@
+//! In-memory response cache, which also coalesces identical concurrent requests.
+//!
+//! Each key has a slot guarded by an async lock. The first request for a stale key fetches
+//! while holding the lock; identical requests arriving meanwhile wait on it, then share the result.
+mod tests;
+
+use crate::{bus::urls::URLs as BusURLs, rail::urls::URLs as RailURLs, requests::WMATA_API};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Number of keys above which expired slots are dropped.
+const PURGE_THRESHOLD: usize = 10_000;
+
+/// How long a client caches responses. See [`crate::builder::Builder::cache`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CachePolicy {
+    /// How long to cache data changing with WMATA's schedules, like stations, routes, and schedules.
+    pub ttl: Duration,
+    /// How long to cache live data, like predictions, positions, and incidents.
+    pub live_ttl: Duration,
+}
+
+impl CachePolicy {
+    /// A policy caching live data for `live_ttl`, and everything else for `ttl`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::cache::CachePolicy;
+    ///
+    /// let policy = CachePolicy::new(Duration::from_secs(3600), Duration::from_secs(10));
+    /// ```
+    pub fn new(ttl: Duration, live_ttl: Duration) -> Self {
+        CachePolicy { ttl, live_ttl }
+    }
+
+    /// How long to cache responses from the endpoint at `path`.
+    pub(crate) fn ttl_for(&self, path: &str) -> Duration {
+        if is_live(path) {
+            self.live_ttl
+        } else {
+            self.ttl
+        }
+    }
+}
+
+impl Default for CachePolicy {
+    /// Live data for 10 seconds, everything else for an hour.
+    fn default() -> Self {
+        CachePolicy::new(Duration::from_secs(3600), Duration::from_secs(10))
+    }
+}
+
+/// Whether the endpoint at `path` changes minute to minute, rather than with WMATA's schedules.
+/// `path` may be on the WMATA API, or a mirror of it.
+fn is_live(path: &str) -> bool {
+    let live = [
+        RailURLs::NextTrains.to_string(),
+        RailURLs::Positions.to_string(),
+        RailURLs::Incidents.to_string(),
+        RailURLs::ElevatorAndEscalatorIncidents.to_string(),
+        RailURLs::ParkingInformation.to_string(),
+        BusURLs::NextBuses.to_string(),
+        BusURLs::Positions.to_string(),
+        BusURLs::Incidents.to_string(),
+    ];
+
+    live.iter()
+        .any(|url| path.contains(url.trim_start_matches(WMATA_API)))
+}
+
+/// A cached response body.
+#[derive(Debug)]
+struct Entry {
+    body: Arc<Vec<u8>>,
+    expires: Instant,
+}
+
+type Slot = Arc<AsyncMutex<Option<Entry>>>;
+
+/// A response body, and whether it came from the cache.
+pub struct Cached {
+    pub body: Arc<Vec<u8>>,
+    pub hit: bool,
+}
+
+/// Response bodies by key, each cached for its own TTL.
+#[derive(Debug, Default)]
+pub struct Cache {
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+impl Cache {
+    /// The cached body for `key` if younger than `ttl`, otherwise the body from `fetch`, which is then cached.
+    /// Failures aren't cached.
+    pub async fn get_or_fetch<F, Fut, E>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        fetch: F,
+    ) -> Result<Cached, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<u8>, E>>,
+    {
+        let slot = self.slot(key);
+        let mut entry = slot.lock().await;
+
+        if let Some(entry) = entry
+            .as_ref()
+            .filter(|entry| entry.expires > Instant::now())
+        {
+            return Ok(Cached {
+                body: entry.body.clone(),
+                hit: true,
+            });
+        }
+
+        let body = Arc::new(fetch().await?);
+        *entry = Some(Entry {
+            body: body.clone(),
+            expires: Instant::now() + ttl,
+        });
+
+        Ok(Cached { body, hit: false })
+    }
+
+    fn slot(&self, key: &str) -> Slot {
+        let mut slots = self.slots.lock().unwrap_or_else(|err| err.into_inner());
+
+        if slots.len() >= PURGE_THRESHOLD && !slots.contains_key(key) {
+            purge(&mut slots);
+        }
+
+        slots.entry(key.to_string()).or_default().clone()
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.slots
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+}
+
+/// Drops slots that are expired and not in use.
+fn purge(slots: &mut HashMap<String, Slot>) {
+    let now = Instant::now();
+
+    // Slots held by another request are in use, and are never locked otherwise
+    slots.retain(|_, slot| {
+        Arc::strong_count(slot) > 1
+            || slot.try_lock().map_or(
+                true,
+                |entry| matches!(entry.as_ref(), Some(entry) if entry.expires > now),
+            )
+    });
+}
@@ -0,0 +1,148 @@
+//! Tests for the response cache
+#[cfg(test)]
+use super::*;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+async fn fetch(calls: &AtomicUsize, body: &str) -> Result<Vec<u8>, String> {
+    calls.fetch_add(1, Ordering::SeqCst);
+    tokio::time::delay_for(Duration::from_millis(10)).await;
+
+    Ok(body.as_bytes().to_vec())
+}
+
+#[test]
+fn test_cached() {
+    let cache = Cache::default();
+    let calls = AtomicUsize::new(0);
+    let ttl = Duration::from_secs(60);
+
+    block_on(async {
+        let first = cache
+            .get_or_fetch("lines", ttl, || fetch(&calls, "[1]"))
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch("lines", ttl, || fetch(&calls, "[2]"))
+            .await
+            .unwrap();
+
+        assert!(!first.hit);
+        assert!(second.hit);
+        assert_eq!(second.body.as_slice(), b"[1]");
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_expired() {
+    let cache = Cache::default();
+    let calls = AtomicUsize::new(0);
+
+    block_on(async {
+        cache
+            .get_or_fetch("positions", Duration::from_secs(0), || fetch(&calls, "[1]"))
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch("positions", Duration::from_secs(0), || fetch(&calls, "[2]"))
+            .await
+            .unwrap();
+
+        assert!(!second.hit);
+        assert_eq!(second.body.as_slice(), b"[2]");
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_coalesced() {
+    let cache = Cache::default();
+    let calls = AtomicUsize::new(0);
+    let ttl = Duration::from_secs(60);
+
+    let (first, second, other) = block_on(async {
+        tokio::join!(
+            cache.get_or_fetch("A01", ttl, || fetch(&calls, "[1]")),
+            cache.get_or_fetch("A01", ttl, || fetch(&calls, "[2]")),
+            cache.get_or_fetch("A02", ttl, || fetch(&calls, "[3]")),
+        )
+    });
+
+    assert_eq!(first.unwrap().body.as_slice(), b"[1]");
+    assert!(second.as_ref().unwrap().hit);
+    assert_eq!(second.unwrap().body.as_slice(), b"[1]");
+    assert_eq!(other.unwrap().body.as_slice(), b"[3]");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_failures_not_cached() {
+    let cache = Cache::default();
+    let calls = AtomicUsize::new(0);
+    let ttl = Duration::from_secs(60);
+
+    block_on(async {
+        let failed = cache
+            .get_or_fetch("lines", ttl, || async {
+                Err::<Vec<u8>, String>("upstream".to_string())
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let retried = cache
+            .get_or_fetch("lines", ttl, || fetch(&calls, "[1]"))
+            .await
+            .unwrap();
+        assert!(!retried.hit);
+    });
+}
+
+#[test]
+fn test_purge() {
+    let mut slots: HashMap<String, Slot> = HashMap::new();
+    let expired: Slot = Arc::new(AsyncMutex::new(Some(Entry {
+        body: Arc::new(vec![]),
+        expires: Instant::now(),
+    })));
+    let fresh: Slot = Arc::new(AsyncMutex::new(Some(Entry {
+        body: Arc::new(vec![]),
+        expires: Instant::now() + Duration::from_secs(60),
+    })));
+    let waiting: Slot = Arc::new(AsyncMutex::new(None));
+    let _waiter = waiting.clone();
+
+    slots.insert("expired".to_string(), expired);
+    slots.insert("fresh".to_string(), fresh);
+    slots.insert("waiting".to_string(), waiting);
+    slots.insert("empty".to_string(), Slot::default());
+
+    purge(&mut slots);
+
+    let mut keys: Vec<&String> = slots.keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["fresh", "waiting"]);
+}
+
+#[test]
+fn test_len() {
+    let cache = Cache::default();
+
+    block_on(async {
+        for key in ["A01", "A02", "A01"].iter() {
+            cache
+                .get_or_fetch(key, Duration::from_secs(60), || async {
+                    Ok::<_, ()>(vec![])
+                })
+                .await
+                .unwrap();
+        }
+    });
+
+    assert_eq!(cache.len(), 2);
+}
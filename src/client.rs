@@ -0,0 +1,306 @@
+//! Client for both MetroRail and MetroBus. Contains the client sharing one API key,
+//! connection pool, and rate limit between the two, and data structures returned from
+//! its calls spanning both.
+pub mod responses;
+mod tests;
+
+use crate::{
+    builder::Builder,
+    bus::client::responses::Prediction,
+    drift::Recorder,
+    error::Error,
+    limit::RateLimit,
+    location::LatLong,
+    rail::client::responses::RailPrediction,
+    requests::{Transport, WMATA_API},
+    MetroBus, MetroRail, RadiusAtLatLong, Station, Stop,
+};
+use futures_util::future::{join_all, try_join};
+use std::{cmp::Ordering, collections::HashMap, iter, str::FromStr, sync::Arc, time::Duration};
+
+#[cfg(feature = "testing")]
+use crate::testing::Cassette;
+
+/// Calls allowed per second by WMATA's default tier.
+pub const DEFAULT_RATE_LIMIT: f64 = 10.0;
+
+/// Longest a call will wait for its turn under the default rate limit, before failing.
+pub const DEFAULT_MAXIMUM_WAIT: Duration = Duration::from_secs(60);
+
+/// Client for both MetroRail and MetroBus. [`Client::rail`] and [`Client::bus`] share one API key,
+/// connection pool, and rate limit. Clones share them too.
+#[derive(Debug, Clone)]
+pub struct Client {
+    rail: MetroRail,
+    bus: MetroBus,
+    transport: Transport,
+}
+
+// Constructor
+impl Client {
+    /// Constructor for the client, rate limited to WMATA's default tier. See [`DEFAULT_RATE_LIMIT`].
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Wmata;
+    ///
+    /// let client = Wmata::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// ```
+    pub fn new(api_key: &str) -> Self {
+        Client::with_base_url(api_key, WMATA_API)
+    }
+
+    /// Constructor for a client sending requests somewhere other than the WMATA API,
+    /// like a mirror or the fake server in `wmata::testing`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Wmata;
+    ///
+    /// let client = Wmata::with_base_url("9e38c3eab34c4e6c990828002828f5ed", "http://127.0.0.1:8080");
+    /// ```
+    pub fn with_base_url(api_key: &str, base_url: &str) -> Self {
+        let transport = Transport {
+            rate_limit: Some(Arc::new(default_rate_limit())),
+            ..Transport::default()
+        };
+
+        Client::from_transport(api_key, base_url, transport)
+    }
+
+    /// Builder for a client with timeouts, headers, a proxy, and cache, retry, and rate limit policies.
+    /// Rate limited to WMATA's default tier, unless set. See [`crate::builder`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::Wmata;
+    ///
+    /// let client = Wmata::builder("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(api_key: &str) -> Builder<Self> {
+        Builder::new(api_key).rate_limit(Some(default_rate_limit()))
+    }
+
+    /// Constructor for a client sending requests through `transport`.
+    pub(crate) fn from_transport(api_key: &str, base_url: &str, transport: Transport) -> Self {
+        Client {
+            rail: MetroRail::with_base_url(api_key, base_url).with_transport(transport.clone()),
+            bus: MetroBus::with_base_url(api_key, base_url).with_transport(transport.clone()),
+            transport,
+        }
+    }
+
+    /// This client, with `rate_limit` shared by MetroRail and MetroBus calls in place of the default.
+    /// Not rate limited at all, if None.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::{limit::RateLimit, Wmata};
+    ///
+    /// let client = Wmata::new("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .with_rate_limit(Some(RateLimit::new(5.0, 5.0, Duration::from_secs(10)).unwrap()));
+    /// ```
+    pub fn with_rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.transport.rate_limit = rate_limit.map(Arc::new);
+        self.rail = self.rail.with_transport(self.transport.clone());
+        self.bus = self.bus.with_transport(self.transport.clone());
+        self
+    }
+
+    /// This client, deserializing responses strictly and recording schema drift to `recorder`.
+    /// See [`crate::drift`].
+    pub fn with_drift_recorder(mut self, recorder: Recorder) -> Self {
+        self.rail = self.rail.with_drift_recorder(recorder.clone());
+        self.bus = self.bus.with_drift_recorder(recorder);
+        self
+    }
+
+    /// This client, recording its requests to or replaying them from `cassette`.
+    #[cfg(feature = "testing")]
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.rail = self.rail.with_cassette(cassette.clone());
+        self.bus = self.bus.with_cassette(cassette);
+        self
+    }
+}
+
+// Views
+impl Client {
+    /// The WMATA API key used for all requests routed through this client.
+    /// Empty for clients built with [`Builder::from_key_pool`], which send keys from the pool instead.
+    pub fn key(&self) -> &str {
+        &self.rail.key
+    }
+
+    /// MetroRail methods, sharing this client's connection pool and rate limit.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Wmata;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = Wmata::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.wmata();
+    /// let lines = block_on(async { client.rail().lines().await });
+    /// assert!(lines.is_ok());
+    /// ```
+    pub fn rail(&self) -> &MetroRail {
+        &self.rail
+    }
+
+    /// MetroBus methods, sharing this client's connection pool and rate limit.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Wmata;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = Wmata::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.wmata();
+    /// let routes = block_on(async { client.bus().routes().await });
+    /// assert!(routes.is_ok());
+    /// ```
+    pub fn bus(&self) -> &MetroBus {
+        &self.bus
+    }
+}
+
+// Both MetroRail and MetroBus
+impl Client {
+    /// Station entrances and bus stops within a radius (meters) of a latitude and longitude, nearest first.
+    /// Entrances come with next trains at their stations, and stops with their next buses.
+    ///
+    /// Makes one call for entrances, one for stops, then one per station and one per stop at once.
+    /// Places whose predictions couldn't be fetched are still included, with the error.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{RadiusAtLatLong, Wmata};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = Wmata::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.wmata();
+    /// let nearby = block_on(async { client.nearby(RadiusAtLatLong::new(500, 38.898303, -77.028099)).await });
+    /// assert!(nearby.is_ok());
+    /// ```
+    pub async fn nearby(
+        &self,
+        radius_at_lat_long: RadiusAtLatLong,
+    ) -> Result<responses::Nearby, Error> {
+        let (entrances, stops) = try_join(
+            self.rail.entrances(radius_at_lat_long),
+            self.bus.stops(Some(radius_at_lat_long)),
+        )
+        .await?;
+
+        let mut stations: Vec<Station> = vec![];
+
+        for entrance in entrances.entrances.iter() {
+            for station in iter::once(entrance.first_station).chain(entrance.second_station) {
+                if !stations.contains(&station) {
+                    stations.push(station);
+                }
+            }
+        }
+
+        let trains: HashMap<Station, Result<Box<[RailPrediction]>, Error>> = stations
+            .iter()
+            .copied()
+            .zip(
+                join_all(stations.iter().map(|station| async move {
+                    Ok(self.rail.next_trains(*station).await?.trains)
+                }))
+                .await,
+            )
+            .collect();
+
+        // Stops without a regional ID have an ID of 0, or none
+        let buses: Vec<Result<Box<[Prediction]>, Error>> =
+            join_all(stops.stops.iter().map(|stop| async move {
+                match &stop.stop {
+                    Some(Stop(id)) if id != "0" => {
+                        Ok(self.bus.next_buses(Stop::new(id)).await?.predictions)
+                    }
+                    _ => Ok(Box::new([]) as Box<[Prediction]>),
+                }
+            }))
+            .await;
+
+        let mut places = vec![];
+
+        for entrance in entrances.entrances.into_vec() {
+            let mut entrance_trains = vec![];
+            let mut error = None;
+
+            for station in iter::once(entrance.first_station).chain(entrance.second_station) {
+                match &trains[&station] {
+                    Ok(trains) => entrance_trains.extend(trains.iter().cloned()),
+                    Err(err) => error = Some(Error::new(err.message.clone())),
+                }
+            }
+
+            places.push(responses::Place::Entrance {
+                distance: radius_at_lat_long.distance_to(&entrance),
+                entrance,
+                trains: entrance_trains.into_boxed_slice(),
+                error,
+            });
+        }
+
+        for (stop, buses) in stops.stops.into_vec().into_iter().zip(buses) {
+            let (buses, error) = match buses {
+                Ok(buses) => (buses, None),
+                Err(err) => (Box::new([]) as Box<[Prediction]>, Some(err)),
+            };
+
+            places.push(responses::Place::Stop {
+                distance: radius_at_lat_long.distance_to(&stop),
+                stop,
+                buses,
+                error,
+            });
+        }
+
+        places.sort_by(|place, other| {
+            place
+                .distance()
+                .partial_cmp(&other.distance())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Ok(responses::Nearby {
+            places: places.into_boxed_slice(),
+        })
+    }
+}
+
+/// A rate limit for WMATA's default tier. See [`DEFAULT_RATE_LIMIT`].
+fn default_rate_limit() -> RateLimit {
+    RateLimit::new_unchecked(DEFAULT_RATE_LIMIT, DEFAULT_RATE_LIMIT, DEFAULT_MAXIMUM_WAIT)
+}
+
+impl FromStr for Client {
+    type Err = Error;
+
+    /// Converts a string into a Client.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::Wmata;
+    ///
+    /// let client: Wmata = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    ///
+    /// assert_eq!(client.key(), "9e38c3eab34c4e6c990828002828f5ed");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Client::new(s))
+    }
+}
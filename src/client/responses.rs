@@ -0,0 +1,48 @@
+//! Responses spanning both MetroRail and MetroBus.
+use crate::{
+    bus::client::responses::{Prediction, StopResponse},
+    error::Error,
+    rail::client::responses::{RailPrediction, StationEntrance},
+};
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct Nearby {
+    /// See [`Place`]. Nearest first.
+    pub places: Box<[Place]>,
+}
+
+#[derive(Serialize, Debug)]
+pub enum Place {
+    /// A station entrance, with next trains at its stations.
+    Entrance {
+        /// See [`StationEntrance`].
+        entrance: StationEntrance,
+        /// Distance (in meters) from the point searched around.
+        distance: f64,
+        /// See [`RailPrediction`].
+        trains: Box<[RailPrediction]>,
+        /// Why next trains couldn't be fetched for one of the entrance's stations, if they couldn't.
+        error: Option<Error>,
+    },
+    /// A bus stop, with its next buses.
+    Stop {
+        /// See [`StopResponse`].
+        stop: StopResponse,
+        /// Distance (in meters) from the point searched around.
+        distance: f64,
+        /// See [`Prediction`].
+        buses: Box<[Prediction]>,
+        /// Why next buses couldn't be fetched, if they couldn't. `buses` is empty then.
+        error: Option<Error>,
+    },
+}
+
+impl Place {
+    /// Distance (in meters) from the point searched around.
+    pub fn distance(&self) -> f64 {
+        match self {
+            Place::Entrance { distance, .. } | Place::Stop { distance, .. } => *distance,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Tests for the combined MetroRail and MetroBus Client
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{
+    requests::Fetch,
+    testing::{Endpoint, FakeServer, Scripted},
+};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[test]
+fn test_from_str() {
+    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+
+    assert_eq!(client.key(), "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.rail().key, "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.bus().key, "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.rail().base_url(), "https://api.wmata.com");
+    assert_eq!(client.bus().base_url(), "https://api.wmata.com");
+}
+
+#[test]
+fn test_views_share_rate_limit() {
+    let client = Client::new("9e38c3eab34c4e6c990828002828f5ed");
+    let rail = client.rail().transport().unwrap().rate_limit.clone();
+    let bus = client.bus().transport().unwrap().rate_limit.clone();
+
+    assert!(Arc::ptr_eq(&rail.unwrap(), &bus.unwrap()));
+
+    let client = client.with_rate_limit(None);
+
+    assert!(client.rail().transport().unwrap().rate_limit.is_none());
+    assert!(client.bus().transport().unwrap().rate_limit.is_none());
+}
+
+#[test]
+fn test_rate_limit() {
+    let server = FakeServer::start();
+    let client = server.wmata().with_rate_limit(Some(
+        RateLimit::new(0.1, 1.0, Duration::from_secs(1)).unwrap(),
+    ));
+
+    block_on(async { client.rail().lines().await }).unwrap();
+    let err = block_on(async { client.bus().routes().await }).unwrap_err();
+
+    assert!(err.message.starts_with("Rate limit exceeded"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_nearby() {
+    let server = FakeServer::start();
+    let client = server.wmata();
+
+    let nearby = block_on(async {
+        client
+            .nearby(RadiusAtLatLong::new(10_000, 38.898303, -77.028099))
+            .await
+    })
+    .unwrap();
+
+    assert_eq!(nearby.places.len(), 4);
+    assert!(nearby
+        .places
+        .windows(2)
+        .all(|places| places[0].distance() <= places[1].distance()));
+
+    match &nearby.places[0] {
+        responses::Place::Entrance {
+            entrance, trains, ..
+        } => {
+            assert_eq!(entrance.first_station, Station::A01);
+            // Trains at both Metro Center platforms
+            assert!(!trains.is_empty());
+        }
+        place => panic!("Expected the Metro Center entrance first, got {:?}", place),
+    }
+
+    // Entrances, stops, next trains at A01 and C01, and next buses at the two stops with IDs
+    let paths: Vec<String> = server
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert_eq!(paths.len(), 6);
+    assert!(paths.contains(&"/StationPrediction.svc/json/GetPrediction/C01".to_string()));
+}
+
+#[test]
+fn test_nearby_prediction_error() {
+    let server = FakeServer::start();
+    server.script(Endpoint::NextBuses, Scripted::error(500, "Internal error"));
+    let client = server.wmata();
+
+    let nearby = block_on(async {
+        client
+            .nearby(RadiusAtLatLong::new(10_000, 38.898303, -77.028099))
+            .await
+    })
+    .unwrap();
+
+    // The other places are still there, and only one stop is missing its buses
+    assert_eq!(nearby.places.len(), 4);
+
+    let errors: Vec<&str> = nearby
+        .places
+        .iter()
+        .filter_map(|place| match place {
+            responses::Place::Stop {
+                buses,
+                error: Some(err),
+                ..
+            } => {
+                assert!(buses.is_empty());
+                Some(err.message.as_str())
+            }
+            responses::Place::Stop { error: None, .. } => None,
+            responses::Place::Entrance { error, .. } => {
+                assert!(error.is_none());
+                None
+            }
+        })
+        .collect();
+    assert_eq!(errors, ["Internal error"]);
+}
@@ -1,10 +1,12 @@
 //! Date related structs and implementations
-use chrono::{DateTime, FixedOffset, ParseResult, TimeZone};
-use serde::{self, Deserialize, Deserializer};
+use chrono::{DateTime, FixedOffset, NaiveTime, ParseResult, TimeZone};
+use serde::{self, Deserialize, Deserializer, Serializer};
 
 const FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const HOUR_MINUTE_FORMAT: &str = "%H%M";
 const HOUR: i32 = 3600;
 
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Date {
     pub year: u16,
     pub month: u8,
@@ -23,8 +25,13 @@ impl ToString for Date {
     }
 }
 
+/// Offset of the times returned by this crate, and of the times WMATA returns: UTC-5, regardless of daylight saving time.
+pub(crate) fn eastern() -> FixedOffset {
+    FixedOffset::west(HOUR * 5)
+}
+
 fn string_time_to_date(time: &str) -> ParseResult<DateTime<FixedOffset>> {
-    FixedOffset::west(HOUR * 5).datetime_from_str(time, FORMAT)
+    eastern().datetime_from_str(time, FORMAT)
 }
 
 pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
@@ -40,5 +47,52 @@ pub(crate) fn deserialize_option<'de, D>(
 where
     D: Deserializer<'de>,
 {
-    String::deserialize(deserializer).map(|s| string_time_to_date(&s).ok())
+    Ok(Option::<String>::deserialize(deserializer)?
+        .and_then(|time| string_time_to_date(&time).ok()))
+}
+
+pub(crate) fn deserialize_hour_minute<'de, D>(
+    deserializer: D,
+) -> Result<Option<NaiveTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?
+        .and_then(|time| NaiveTime::parse_from_str(&time, HOUR_MINUTE_FORMAT).ok()))
+}
+
+pub(crate) fn serialize<S>(
+    date_time: &DateTime<FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&date_time.format(FORMAT).to_string())
+}
+
+pub(crate) fn serialize_option<S>(
+    date_time: &Option<DateTime<FixedOffset>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match date_time {
+        Some(date_time) => serialize(date_time, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn serialize_hour_minute<S>(
+    time: &Option<NaiveTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match time {
+        Some(time) => serializer.serialize_str(&time.format(HOUR_MINUTE_FORMAT).to_string()),
+        None => serializer.serialize_none(),
+    }
 }
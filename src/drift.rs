@@ -0,0 +1,420 @@
+//! Schema drift detection, for finding out when WMATA adds, removes, or renames fields.
+//!
+//! Responses are normally deserialized leniently: unknown fields are ignored, and absent optional fields
+//! become `None`. A [`Recorder`] attached to a client with `with_drift_recorder` deserializes strictly instead,
+//! noting per response type the fields WMATA sent that the type doesn't have, and the fields the type has
+//! that WMATA didn't send.
+//!
+//! [`report`] calls every endpoint once and collects the drift. Point the clients at a mirror,
+//! or a recorded cassette, to run it without touching the live API.
+//!
+//! # Example
+//! ```
+//! use wmata::{drift, MetroBus, MetroRail};
+//! use tokio_test::block_on;
+//!
+//! let rail = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+//! let bus = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+//! # let server = wmata::testing::FakeServer::start();
+//! # let (rail, bus) = (server.rail(), server.bus());
+//! let report = block_on(drift::report(rail, bus));
+//!
+//! if !report.is_clean() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+mod tests;
+
+use crate::{error::Error, Line, MetroBus, MetroRail, RadiusAtLatLong, Route, Station, Stop};
+use serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer, Serialize,
+};
+use serde_json::{Map, Value};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Drift found in one response type.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct TypeDrift {
+    /// Fields WMATA sent which the type doesn't have. Likely additions or renames.
+    pub unknown_fields: BTreeSet<String>,
+    /// Fields the type has which WMATA didn't send. Likely removals or renames.
+    pub missing_fields: BTreeSet<String>,
+}
+
+impl TypeDrift {
+    pub fn is_empty(&self) -> bool {
+        self.unknown_fields.is_empty() && self.missing_fields.is_empty()
+    }
+}
+
+/// Drift by response type name, e.g.: `StationResponse`.
+type Drift = BTreeMap<String, TypeDrift>;
+
+/// Collects drift from strictly deserialized responses. Clones share the same collection.
+#[derive(Debug, Default, Clone)]
+pub struct Recorder {
+    drift: Arc<Mutex<Drift>>,
+}
+
+impl Recorder {
+    /// Deserializes `json` as `T`, recording any drift, even if deserializing fails.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{drift::Recorder, rail::client::responses::Lines};
+    ///
+    /// let recorder = Recorder::default();
+    /// let lines: Lines = recorder.deserialize(r#"{"Lines": [], "Updated": "2019-10-01"}"#).unwrap();
+    ///
+    /// assert!(recorder.drift()["Lines"].unknown_fields.contains("Updated"));
+    /// ```
+    pub fn deserialize<T: DeserializeOwned>(&self, json: &str) -> Result<T, Error> {
+        let value: Value = serde_json::from_str(json).map_err(|err| Error::new(err.to_string()))?;
+        let found = RefCell::new(Drift::new());
+
+        // Drift is recorded even if deserializing fails, since it's likely why
+        let deserialized = T::deserialize(Tracked {
+            value,
+            drift: &found,
+        })
+        .map_err(|err| Error::new(err.to_string()));
+
+        let mut drift = self.drift.lock().unwrap_or_else(|err| err.into_inner());
+        for (name, found) in found.into_inner() {
+            let type_drift = drift.entry(name).or_default();
+            type_drift.unknown_fields.extend(found.unknown_fields);
+            type_drift.missing_fields.extend(found.missing_fields);
+        }
+
+        deserialized
+    }
+
+    /// Drift recorded so far, leaving out types without any.
+    pub fn drift(&self) -> Drift {
+        self.drift
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .filter(|(_, type_drift)| !type_drift.is_empty())
+            .map(|(name, type_drift)| (name.clone(), type_drift.clone()))
+            .collect()
+    }
+}
+
+/// Drift found by calling every endpoint once.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// Drift by response type name. Types without drift are left out.
+    pub types: Drift,
+    /// Endpoints which failed, with their error. A response which no longer deserializes shows up here.
+    pub failures: BTreeMap<String, String>,
+}
+
+impl Report {
+    /// Whether every endpoint succeeded, without drift.
+    pub fn is_clean(&self) -> bool {
+        self.types.is_empty() && self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return writeln!(f, "No drift");
+        }
+
+        for (name, type_drift) in self.types.iter() {
+            writeln!(f, "{}", name)?;
+
+            for field in type_drift.unknown_fields.iter() {
+                writeln!(f, "  + {}", field)?;
+            }
+
+            for field in type_drift.missing_fields.iter() {
+                writeln!(f, "  - {}", field)?;
+            }
+        }
+
+        for (endpoint, message) in self.failures.iter() {
+            writeln!(f, "{} failed: {}", endpoint, message)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Calls every endpoint once with strict deserialization, reporting drift and failures.
+///
+/// Endpoints taking a station, line, route, stop, or location are called with
+/// Metro Center, the Red Line, the 10A, stop 1001195, and 1km around Metro Center.
+pub async fn report(rail: MetroRail, bus: MetroBus) -> Report {
+    let recorder = Recorder::default();
+    let rail = rail.with_drift_recorder(recorder.clone());
+    let bus = bus.with_drift_recorder(recorder.clone());
+
+    let metro_center = RadiusAtLatLong::new(1000, 38.898303, -77.028099);
+    let stop = || Stop::new("1001195");
+
+    let results = vec![
+        ("rail.lines", rail.lines().await.map(drop)),
+        (
+            "rail.entrances",
+            rail.entrances(metro_center).await.map(drop),
+        ),
+        ("rail.positions", rail.positions().await.map(drop)),
+        ("rail.routes", rail.routes().await.map(drop)),
+        ("rail.circuits", rail.circuits().await.map(drop)),
+        (
+            "rail.station_to_station",
+            rail.station_to_station(Some(Station::A01), Some(Station::A02))
+                .await
+                .map(drop),
+        ),
+        (
+            "rail.elevator_and_escalator_incidents_at",
+            rail.elevator_and_escalator_incidents_at(None)
+                .await
+                .map(drop),
+        ),
+        ("rail.incidents_at", rail.incidents_at(None).await.map(drop)),
+        (
+            "rail.next_trains",
+            rail.next_trains(Station::A01).await.map(drop),
+        ),
+        (
+            "rail.station_information",
+            rail.station_information(Station::A01).await.map(drop),
+        ),
+        (
+            "rail.parking_information",
+            rail.parking_information(Station::A01).await.map(drop),
+        ),
+        (
+            "rail.path_from",
+            rail.path_from(Station::A01, Station::A02).await.map(drop),
+        ),
+        ("rail.timings", rail.timings(Station::A01).await.map(drop)),
+        (
+            "rail.stations_on",
+            rail.stations_on(Some(Line::Red)).await.map(drop),
+        ),
+        ("bus.routes", bus.routes().await.map(drop)),
+        ("bus.stops", bus.stops(Some(metro_center)).await.map(drop)),
+        (
+            "bus.positions_along",
+            bus.positions_along(Some(Route::One0A), None)
+                .await
+                .map(drop),
+        ),
+        (
+            "bus.incidents_along",
+            bus.incidents_along(None).await.map(drop),
+        ),
+        ("bus.path", bus.path(Route::One0A, None).await.map(drop)),
+        (
+            "bus.route_schedule",
+            bus.route_schedule(Route::One0A, None, false)
+                .await
+                .map(drop),
+        ),
+        ("bus.next_buses", bus.next_buses(stop()).await.map(drop)),
+        (
+            "bus.stop_schedule",
+            bus.stop_schedule(stop(), None).await.map(drop),
+        ),
+    ];
+
+    Report {
+        types: recorder.drift(),
+        failures: results
+            .into_iter()
+            .filter_map(|(endpoint, result)| {
+                result.err().map(|err| (endpoint.to_string(), err.message))
+            })
+            .collect(),
+    }
+}
+
+/// Deserializes a JSON value, noting the fields of each struct deserialized from it.
+struct Tracked<'a> {
+    value: Value,
+    drift: &'a RefCell<Drift>,
+}
+
+impl<'a> Tracked<'a> {
+    fn new(value: Value, drift: &'a RefCell<Drift>) -> Self {
+        Tracked { value, drift }
+    }
+
+    fn visit_array<'de, V: Visitor<'de>>(
+        array: Vec<Value>,
+        drift: &'a RefCell<Drift>,
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        visitor.visit_seq(TrackedSeq {
+            values: array.into_iter(),
+            drift,
+        })
+    }
+
+    fn visit_object<'de, V: Visitor<'de>>(
+        object: Map<String, Value>,
+        drift: &'a RefCell<Drift>,
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        visitor.visit_map(TrackedMap {
+            entries: object.into_iter(),
+            value: None,
+            drift,
+        })
+    }
+}
+
+impl<'de, 'a> Deserializer<'de> for Tracked<'a> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Array(array) => Tracked::visit_array(array, self.drift, visitor),
+            Value::Object(object) => Tracked::visit_object(object, self.drift, visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let object = match self.value {
+            Value::Object(object) => object,
+            value => return value.deserialize_struct(name, fields, visitor),
+        };
+
+        {
+            let mut drift = self.drift.borrow_mut();
+            let type_drift = drift.entry(name.to_string()).or_default();
+
+            type_drift.unknown_fields.extend(
+                object
+                    .keys()
+                    .filter(|key| !fields.contains(&key.as_str()))
+                    .cloned(),
+            );
+            type_drift.missing_fields.extend(
+                fields
+                    .iter()
+                    .filter(|field| !object.contains_key(**field))
+                    .map(|field| field.to_string()),
+            );
+        }
+
+        Tracked::visit_object(object, self.drift, visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Array(array) => Tracked::visit_array(array, self.drift, visitor),
+            value => value.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(object) => Tracked::visit_object(object, self.drift, visitor),
+            value => value.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+struct TrackedSeq<'a> {
+    values: std::vec::IntoIter<Value>,
+    drift: &'a RefCell<Drift>,
+}
+
+impl<'de, 'a> SeqAccess<'de> for TrackedSeq<'a> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.values
+            .next()
+            .map(|value| seed.deserialize(Tracked::new(value, self.drift)))
+            .transpose()
+    }
+}
+
+struct TrackedMap<'a> {
+    entries: serde_json::map::IntoIter,
+    value: Option<Value>,
+    drift: &'a RefCell<Drift>,
+}
+
+impl<'de, 'a> MapAccess<'de> for TrackedMap<'a> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+
+        seed.deserialize(Tracked::new(value, self.drift))
+    }
+}
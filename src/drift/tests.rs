@@ -0,0 +1,109 @@
+//! Tests for schema drift detection
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{
+    rail::client::responses::Lines,
+    testing::{Endpoint, FakeServer, Scripted},
+};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+const LINE: &str = r#"{
+    "LineCode": "RD",
+    "DisplayName": "Red",
+    "StartStationCode": "A15",
+    "EndStationCode": "B11",
+    "InternalDestination1": "A11",
+    "InternalDestination2": "B08"
+}"#;
+
+#[test]
+fn test_no_drift() {
+    let recorder = Recorder::default();
+
+    let lines: Lines = recorder
+        .deserialize(&format!(r#"{{"Lines": [{}]}}"#, LINE))
+        .unwrap();
+
+    assert_eq!(lines.lines.len(), 1);
+    assert!(recorder.drift().is_empty());
+}
+
+#[test]
+fn test_unknown_and_missing_fields() {
+    let recorder = Recorder::default();
+    let line = LINE.replace(r#""DisplayName""#, r#""Name""#).replace(
+        r#""InternalDestination2": "B08""#,
+        r#""InternalDestination2": null"#,
+    );
+
+    let result: Result<Lines, Error> =
+        recorder.deserialize(&format!(r#"{{"Lines": [{}], "Updated": "today"}}"#, line));
+
+    // A renamed required field no longer deserializes, but is still recorded
+    assert!(result.is_err());
+
+    let drift = recorder.drift();
+    assert_eq!(
+        drift["Lines"].unknown_fields,
+        vec!["Updated".to_string()].into_iter().collect()
+    );
+    assert_eq!(
+        drift["LineResponse"].unknown_fields,
+        vec!["Name".to_string()].into_iter().collect()
+    );
+    assert_eq!(
+        drift["LineResponse"].missing_fields,
+        vec!["DisplayName".to_string()].into_iter().collect()
+    );
+}
+
+#[test]
+fn test_report_clean() {
+    let server = FakeServer::start();
+
+    let report = block_on(report(server.rail(), server.bus()));
+
+    assert!(report.is_clean(), "{}", report);
+    assert_eq!(report.to_string(), "No drift\n");
+}
+
+#[test]
+fn test_report_drift() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::Lines,
+        Scripted::json(&format!(r#"{{"Lines": [{}], "Updated": "today"}}"#, LINE)),
+    );
+    server.script(
+        Endpoint::BusRoutes,
+        Scripted::error(500, "Internal Server Error"),
+    );
+
+    let report = block_on(report(server.rail(), server.bus()));
+
+    assert!(!report.is_clean());
+    assert_eq!(
+        report.to_string(),
+        "Lines\n  + Updated\nbus.routes failed: Internal Server Error\n"
+    );
+}
+
+#[test]
+fn test_client_with_drift_recorder() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::Lines,
+        Scripted::json(&format!(r#"{{"Lines": [{}], "Updated": "today"}}"#, LINE)),
+    );
+    let recorder = Recorder::default();
+    let client = server.rail().with_drift_recorder(recorder.clone());
+
+    let lines = block_on(async { client.lines().await }).unwrap();
+
+    assert_eq!(lines.lines.len(), 1);
+    assert!(recorder.drift()["Lines"].unknown_fields.contains("Updated"));
+}
@@ -30,5 +30,7 @@ impl error::Error for Error {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ErrorResponse<'a> {
+    /// Lowercase in errors from WMATA's API gateway, like an invalid key.
+    #[serde(alias = "message")]
     pub message: &'a str,
 }
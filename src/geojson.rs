@@ -0,0 +1,273 @@
+//! GeoJSON export of stations, entrances, bus stops, vehicle positions, and route shapes.
+//!
+//! Only available with the `geojson` feature.
+mod tests;
+
+use crate::{
+    bus::client::responses::{BusPositions, PathDetails, PathDirection, Stops},
+    location::LatLong,
+    rail::{
+        circuit::CircuitLocations,
+        client::responses::{StationEntrances, Stations, TrainPositions},
+    },
+    Line,
+};
+use ::geojson::{Feature, FeatureCollection, Geometry, JsonObject, JsonValue, Value};
+
+/// A type which can be converted into a GeoJSON [`FeatureCollection`].
+pub trait ToFeatureCollection {
+    /// Converts this response into a [`FeatureCollection`].
+    fn to_feature_collection(&self) -> FeatureCollection;
+}
+
+impl ToFeatureCollection for Stations {
+    /// A Point for each station, with its code, name, lines, and the color of its first line.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::geojson::ToFeatureCollection;
+    /// use wmata::rail::client::responses::Stations;
+    ///
+    /// let stations: Stations = serde_json::from_str(r#"{"Stations": []}"#).unwrap();
+    /// assert!(stations.to_feature_collection().features.is_empty());
+    /// ```
+    fn to_feature_collection(&self) -> FeatureCollection {
+        collection(
+            self.stations
+                .iter()
+                .map(|station| {
+                    let lines: Vec<Line> = [
+                        Some(station.first_line),
+                        station.second_line,
+                        station.third_line,
+                        station.fourth_line,
+                    ]
+                    .iter()
+                    .filter_map(|line| *line)
+                    .collect();
+
+                    let mut properties = JsonObject::new();
+                    properties.insert("station".to_string(), station.station.to_string().into());
+                    properties.insert("name".to_string(), station.name.clone().into());
+                    properties.insert(
+                        "lines".to_string(),
+                        lines
+                            .iter()
+                            .map(|line| JsonValue::from(line.to_string()))
+                            .collect(),
+                    );
+                    properties.insert("color".to_string(), station.first_line.color().into());
+
+                    point(station, properties)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeatureCollection for StationEntrances {
+    /// A Point for each entrance, with its name, description, and stations.
+    fn to_feature_collection(&self) -> FeatureCollection {
+        collection(
+            self.entrances
+                .iter()
+                .map(|entrance| {
+                    let mut properties = JsonObject::new();
+                    properties.insert("name".to_string(), entrance.name.clone().into());
+                    properties.insert(
+                        "description".to_string(),
+                        entrance.description.clone().into(),
+                    );
+                    properties.insert(
+                        "station".to_string(),
+                        entrance.first_station.to_string().into(),
+                    );
+                    properties.insert(
+                        "second_station".to_string(),
+                        optional(entrance.second_station.map(|station| station.to_string())),
+                    );
+
+                    point(entrance, properties)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeatureCollection for Stops {
+    /// A Point for each stop, with its ID, name, and routes.
+    fn to_feature_collection(&self) -> FeatureCollection {
+        collection(
+            self.stops
+                .iter()
+                .map(|stop| {
+                    let mut properties = JsonObject::new();
+                    properties.insert(
+                        "stop".to_string(),
+                        optional(stop.stop.as_ref().map(|stop| stop.0.clone())),
+                    );
+                    properties.insert("name".to_string(), stop.name.clone().into());
+                    properties.insert(
+                        "routes".to_string(),
+                        stop.routes
+                            .iter()
+                            .map(|route| JsonValue::from(route.to_string()))
+                            .collect(),
+                    );
+
+                    point(stop, properties)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeatureCollection for BusPositions {
+    /// A Point for each bus, with its vehicle, route, headsign, direction, and deviation from schedule.
+    fn to_feature_collection(&self) -> FeatureCollection {
+        collection(
+            self.bus_positions
+                .iter()
+                .map(|position| {
+                    let mut properties = JsonObject::new();
+                    properties.insert("vehicle".to_string(), position.vehicle_id.clone().into());
+                    properties.insert("route".to_string(), position.route.to_string().into());
+                    properties.insert(
+                        "headsign".to_string(),
+                        position.trip_headsign.clone().into(),
+                    );
+                    properties.insert(
+                        "direction".to_string(),
+                        position.direction_text.clone().into(),
+                    );
+                    properties.insert("trip".to_string(), position.trip_id.clone().into());
+                    properties.insert("deviation".to_string(), position.deviation.into());
+                    properties.insert(
+                        "updated".to_string(),
+                        position.date_time.to_rfc3339().into(),
+                    );
+
+                    point(position, properties)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeatureCollection for PathDetails {
+    /// An ordered LineString for each direction of this route, with the route, name, direction, and headsign.
+    /// Directions with fewer than two shape points are left out.
+    fn to_feature_collection(&self) -> FeatureCollection {
+        collection(
+            [(0, &self.direction_zero), (1, &self.direction_one)]
+                .iter()
+                .filter_map(|(number, direction)| self.direction_feature(*number, direction))
+                .collect(),
+        )
+    }
+}
+
+impl PathDetails {
+    fn direction_feature(&self, number: i32, direction: &PathDirection) -> Option<Feature> {
+        let mut shape: Vec<_> = direction.shape.iter().collect();
+        shape.sort_by_key(|point| point.sequence_number);
+
+        if shape.len() < 2 {
+            return None;
+        }
+
+        let mut properties = JsonObject::new();
+        properties.insert("route".to_string(), self.route.to_string().into());
+        properties.insert("name".to_string(), self.name.clone().into());
+        properties.insert("direction".to_string(), number.into());
+        properties.insert(
+            "direction_text".to_string(),
+            direction.direction_text.clone().into(),
+        );
+        properties.insert(
+            "headsign".to_string(),
+            direction.trip_headsign.clone().into(),
+        );
+
+        Some(feature(
+            Value::LineString(shape.iter().map(|point| position(*point)).collect()),
+            properties,
+        ))
+    }
+}
+
+impl TrainPositions {
+    /// A Point for each train whose circuit can be located, with its ID, line, color, destination, and car count.
+    /// Trains on circuits outside of [`CircuitLocations`] are left out.
+    pub fn to_feature_collection(&self, locations: &CircuitLocations) -> FeatureCollection {
+        collection(
+            self.train_positions
+                .iter()
+                .filter_map(|train| {
+                    let location = locations.locate_train(train)?;
+
+                    let mut properties = JsonObject::new();
+                    properties.insert("train".to_string(), train.train_id.clone().into());
+                    properties.insert("number".to_string(), train.train_number.clone().into());
+                    properties.insert(
+                        "line".to_string(),
+                        optional(train.line.map(|line| line.to_string())),
+                    );
+                    properties.insert(
+                        "color".to_string(),
+                        optional(train.line.map(|line| line.color())),
+                    );
+                    properties.insert(
+                        "destination".to_string(),
+                        optional(train.destination_station.map(|station| station.to_string())),
+                    );
+                    properties.insert("cars".to_string(), train.car_count.into());
+                    properties.insert("direction".to_string(), train.direction_number.into());
+                    properties.insert("service".to_string(), train.service_type.clone().into());
+
+                    Some(point(&location, properties))
+                })
+                .collect(),
+        )
+    }
+}
+
+fn collection(features: Vec<Feature>) -> FeatureCollection {
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+}
+
+fn point<T>(location: &T, properties: JsonObject) -> Feature
+where
+    T: LatLong,
+{
+    feature(Value::Point(position(location)), properties)
+}
+
+fn feature(value: Value, properties: JsonObject) -> Feature {
+    Feature {
+        bbox: None,
+        geometry: Some(Geometry::new(value)),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+/// GeoJSON positions are longitude first.
+fn position<T>(location: &T) -> Vec<f64>
+where
+    T: LatLong,
+{
+    vec![location.longitude(), location.latitude()]
+}
+
+fn optional(value: Option<String>) -> JsonValue {
+    match value {
+        Some(value) => value.into(),
+        None => JsonValue::Null,
+    }
+}
@@ -0,0 +1,148 @@
+//! Tests for GeoJSON export
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn direction(headsign: &str, shape: &str) -> String {
+    format!(
+        r#"{{
+            "TripHeadsign": "{headsign}",
+            "DirectionText": "NORTH",
+            "DirectionNum": "0",
+            "Shape": [{shape}],
+            "Stops": []
+        }}"#,
+        headsign = headsign,
+        shape = shape
+    )
+}
+
+#[test]
+fn test_path_details() {
+    let path: PathDetails = serde_json::from_str(&format!(
+        r#"{{"RouteID": "10A", "Name": "10A - PENTAGON", "Direction0": {}, "Direction1": {}}}"#,
+        direction(
+            "PENTAGON",
+            r#"{"Lat": 38.2, "Lon": -77.2, "SeqNum": 2},
+               {"Lat": 38.1, "Lon": -77.1, "SeqNum": 1},
+               {"Lat": 38.3, "Lon": -77.3, "SeqNum": 3}"#
+        ),
+        direction("HUNTINGTON", r#"{"Lat": 38.1, "Lon": -77.1, "SeqNum": 1}"#)
+    ))
+    .unwrap();
+
+    let collection = path.to_feature_collection();
+    assert_eq!(collection.features.len(), 1);
+
+    let feature = &collection.features[0];
+    assert_eq!(
+        feature.geometry.as_ref().unwrap().value,
+        Value::LineString(vec![
+            vec![-77.1, 38.1],
+            vec![-77.2, 38.2],
+            vec![-77.3, 38.3]
+        ])
+    );
+    assert_eq!(feature.property("route"), Some(&JsonValue::from("10A")));
+    assert_eq!(feature.property("direction"), Some(&JsonValue::from(0)));
+    assert_eq!(
+        feature.property("headsign"),
+        Some(&JsonValue::from("PENTAGON"))
+    );
+}
+
+#[test]
+fn test_stations() {
+    let stations: Stations = serde_json::from_str(
+        r#"{
+            "Stations": [{
+                "Address": {"City": "Washington", "State": "DC", "Street": "607 13th St. NW", "Zip": "20005"},
+                "Code": "A01",
+                "Lat": 38.8983144732,
+                "Lon": -77.0280779971,
+                "LineCode1": "RD",
+                "LineCode2": null,
+                "LineCode3": null,
+                "LineCode4": null,
+                "Name": "Metro Center",
+                "StationTogether1": "C01",
+                "StationTogether2": ""
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    let collection = stations.to_feature_collection();
+    let feature = &collection.features[0];
+
+    assert_eq!(
+        feature.geometry.as_ref().unwrap().value,
+        Value::Point(vec![-77.0280779971, 38.8983144732])
+    );
+    assert_eq!(feature.property("station"), Some(&JsonValue::from("A01")));
+    assert_eq!(
+        feature.property("lines"),
+        Some(&JsonValue::from(vec!["RD"]))
+    );
+    assert_eq!(
+        feature.property("color"),
+        Some(&JsonValue::from(Line::Red.color()))
+    );
+}
+
+#[test]
+fn test_train_positions() {
+    let routes = serde_json::from_str(
+        r#"{
+            "StandardRoutes": [{
+                "LineCode": "RD",
+                "TrackNum": 1,
+                "TrackCircuits": [{"SeqNum": 0, "CircuitId": 1, "StationCode": "A01"}]
+            }]
+        }"#,
+    )
+    .unwrap();
+    let stations = serde_json::from_str(
+        r#"{
+            "Stations": [{
+                "Address": {"City": "", "State": "", "Street": "", "Zip": ""},
+                "Code": "A01",
+                "Lat": 38.9,
+                "Lon": -77.0,
+                "LineCode1": "RD",
+                "LineCode2": null,
+                "LineCode3": null,
+                "LineCode4": null,
+                "Name": "Metro Center",
+                "StationTogether1": "",
+                "StationTogether2": ""
+            }]
+        }"#,
+    )
+    .unwrap();
+    let locations = CircuitLocations::new(&routes, &stations);
+
+    let positions: TrainPositions = serde_json::from_str(
+        r#"{
+            "TrainPositions": [
+                {"TrainId": "100", "TrainNumber": "301", "CarCount": 6, "DirectionNum": 1, "CircuitId": 1,
+                 "DestinationStationCode": "A15", "LineCode": "RD", "SecondsAtLocation": 0, "ServiceType": "Normal"},
+                {"TrainId": "200", "TrainNumber": "302", "CarCount": 0, "DirectionNum": 2, "CircuitId": 999,
+                 "DestinationStationCode": null, "LineCode": null, "SecondsAtLocation": 0, "ServiceType": "NoPassengers"}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let collection = positions.to_feature_collection(&locations);
+
+    assert_eq!(collection.features.len(), 1);
+    assert_eq!(
+        collection.features[0].geometry.as_ref().unwrap().value,
+        Value::Point(vec![-77.0, 38.9])
+    );
+    assert_eq!(
+        collection.features[0].property("destination"),
+        Some(&JsonValue::from("A15"))
+    );
+}
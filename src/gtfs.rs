@@ -0,0 +1,540 @@
+//! GTFS static feeds built from MetroBus routes, stops, paths, and schedules.
+//!
+//! Only available with the `gtfs` feature.
+mod import;
+mod tests;
+
+use crate::{
+    bus::client::responses::{PathDetails, PathDirection, RouteInfo, RouteSchedule, Routes, Stops},
+    error::Error,
+    Date,
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    io::{Seek, Write},
+    path::Path,
+};
+use zip::{write::FileOptions, ZipWriter};
+
+/// Agency ID used for every route.
+pub const AGENCY_ID: &str = "WMATA";
+
+/// GTFS route type for buses.
+const BUS_ROUTE_TYPE: u8 = 3;
+
+/// A row of `agency.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Agency {
+    #[serde(default)]
+    pub agency_id: String,
+    pub agency_name: String,
+    pub agency_url: String,
+    pub agency_timezone: String,
+}
+
+impl Default for Agency {
+    fn default() -> Self {
+        Agency {
+            agency_id: AGENCY_ID.to_string(),
+            agency_name: "Washington Metropolitan Area Transit Authority".to_string(),
+            agency_url: "https://wmata.com".to_string(),
+            agency_timezone: "America/New_York".to_string(),
+        }
+    }
+}
+
+/// A row of `stops.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsStop {
+    pub stop_id: String,
+    /// Rider-facing code of this stop. For MetroBus, the 7-digit regional ID.
+    #[serde(default)]
+    pub stop_code: Option<String>,
+    #[serde(default)]
+    pub stop_name: String,
+    /// Only left out for generic nodes and boarding areas.
+    #[serde(default)]
+    pub stop_lat: Option<f64>,
+    #[serde(default)]
+    pub stop_lon: Option<f64>,
+    /// 0 or empty for a stop or platform, 1 for a station, 2 for an entrance, 3 for a generic node,
+    /// and 4 for a boarding area.
+    #[serde(default)]
+    pub location_type: Option<u8>,
+    /// Station containing this stop, if any.
+    #[serde(default)]
+    pub parent_station: Option<String>,
+}
+
+/// A row of `routes.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsRoute {
+    pub route_id: String,
+    #[serde(default)]
+    pub agency_id: String,
+    #[serde(default)]
+    pub route_short_name: String,
+    #[serde(default)]
+    pub route_long_name: String,
+    #[serde(default)]
+    pub route_desc: String,
+    pub route_type: u8,
+}
+
+/// A row of `trips.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsTrip {
+    pub route_id: String,
+    pub service_id: String,
+    pub trip_id: String,
+    #[serde(default)]
+    pub trip_headsign: String,
+    #[serde(default)]
+    pub direction_id: Option<u8>,
+    #[serde(default)]
+    pub shape_id: Option<String>,
+}
+
+/// A row of `stop_times.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsStopTime {
+    pub trip_id: String,
+    /// Time since midnight of the service date, as HH:MM:SS. May exceed 24:00:00 for trips past midnight.
+    pub arrival_time: String,
+    /// See `arrival_time`.
+    pub departure_time: String,
+    pub stop_id: String,
+    pub stop_sequence: u32,
+}
+
+/// A row of `shapes.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsShapePoint {
+    pub shape_id: String,
+    pub shape_pt_lat: f64,
+    pub shape_pt_lon: f64,
+    pub shape_pt_sequence: u32,
+}
+
+/// A row of `calendar.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsCalendar {
+    pub service_id: String,
+    pub monday: u8,
+    pub tuesday: u8,
+    pub wednesday: u8,
+    pub thursday: u8,
+    pub friday: u8,
+    pub saturday: u8,
+    pub sunday: u8,
+    /// First service date, as YYYYMMDD.
+    pub start_date: String,
+    /// Last service date, as YYYYMMDD.
+    pub end_date: String,
+}
+
+/// A row of `calendar_dates.txt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GtfsCalendarDate {
+    pub service_id: String,
+    /// Service date, as YYYYMMDD.
+    pub date: String,
+    /// 1 when service is added on this date, 2 when it is removed.
+    pub exception_type: u8,
+}
+
+/// A reference in a feed to a record which does not exist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityError {
+    /// File containing the reference.
+    pub file: &'static str,
+    /// Field containing the reference.
+    pub field: &'static str,
+    /// The missing ID.
+    pub id: String,
+}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} references {} {}, which does not exist.",
+            self.file, self.field, self.id
+        )
+    }
+}
+
+impl std::error::Error for IntegrityError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// A GTFS static feed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Feed {
+    pub agencies: Vec<Agency>,
+    pub stops: Vec<GtfsStop>,
+    pub routes: Vec<GtfsRoute>,
+    pub trips: Vec<GtfsTrip>,
+    pub stop_times: Vec<GtfsStopTime>,
+    pub shapes: Vec<GtfsShapePoint>,
+    pub calendars: Vec<GtfsCalendar>,
+    pub calendar_dates: Vec<GtfsCalendarDate>,
+}
+
+impl Feed {
+    /// Builds a feed for a single service date from MetroBus responses. Fails if the date is invalid.
+    ///
+    /// Stops come from both `stops` and the stops along each path, so that every stop a path serves is present.
+    /// Trips are taken from every schedule, ignoring repeated trip IDs.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Date;
+    /// use wmata::gtfs::Feed;
+    ///
+    /// let routes = serde_json::from_str(r#"{"Routes": []}"#).unwrap();
+    /// let stops = serde_json::from_str(r#"{"Stops": []}"#).unwrap();
+    ///
+    /// let feed = Feed::new(Date::new(2019, 10, 1), &routes, &stops, &[], &[]).unwrap();
+    /// assert!(feed.validate().is_empty());
+    /// ```
+    pub fn new(
+        date: Date,
+        routes: &Routes,
+        stops: &Stops,
+        paths: &[PathDetails],
+        schedules: &[RouteSchedule],
+    ) -> Result<Self, Error> {
+        let service_id = format!("{:04}{:02}{:02}", date.year, date.month, date.day);
+        let midnight = NaiveDate::from_ymd_opt(
+            i32::from(date.year),
+            u32::from(date.month),
+            u32::from(date.day),
+        )
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .ok_or_else(|| Error::new(format!("{} is not a valid date.", date.to_string())))?;
+
+        let mut feed = Feed {
+            agencies: vec![Agency::default()],
+            calendar_dates: vec![GtfsCalendarDate {
+                service_id: service_id.clone(),
+                date: service_id.clone(),
+                exception_type: 1,
+            }],
+            ..Feed::default()
+        };
+
+        let mut stop_ids = HashSet::new();
+
+        for stop in stops.stops.iter() {
+            if let Some(stop_id) = stop_id(stop.stop.as_ref().map(|stop| stop.0.as_str())) {
+                if stop_ids.insert(stop_id.clone()) {
+                    feed.stops.push(GtfsStop {
+                        stop_id,
+                        stop_code: None,
+                        stop_name: stop.name.clone(),
+                        stop_lat: Some(stop.latitude),
+                        stop_lon: Some(stop.longitude),
+                        location_type: None,
+                        parent_station: None,
+                    });
+                }
+            }
+        }
+
+        for route in routes.routes.iter() {
+            feed.routes.push(GtfsRoute {
+                route_id: route.route.to_string(),
+                agency_id: AGENCY_ID.to_string(),
+                route_short_name: route.route.to_string(),
+                route_long_name: route.name.clone(),
+                route_desc: route.line_description.clone(),
+                route_type: BUS_ROUTE_TYPE,
+            });
+        }
+
+        for path in paths {
+            for direction in [&path.direction_zero, &path.direction_one].iter() {
+                for stop in direction.stops.iter() {
+                    if let Some(stop_id) = stop_id(stop.stop.as_ref().map(|stop| stop.0.as_str())) {
+                        if stop_ids.insert(stop_id.clone()) {
+                            feed.stops.push(GtfsStop {
+                                stop_id,
+                                stop_code: None,
+                                stop_name: stop.name.clone(),
+                                stop_lat: Some(stop.latitude),
+                                stop_lon: Some(stop.longitude),
+                                location_type: None,
+                                parent_station: None,
+                            });
+                        }
+                    }
+                }
+
+                let shape_id = shape_id(path, direction);
+
+                for (sequence, point) in direction.ordered_shape().points.iter().enumerate() {
+                    feed.shapes.push(GtfsShapePoint {
+                        shape_id: shape_id.clone(),
+                        shape_pt_lat: point.0,
+                        shape_pt_lon: point.1,
+                        shape_pt_sequence: sequence as u32,
+                    });
+                }
+            }
+        }
+
+        let mut trip_ids = HashSet::new();
+
+        for schedule in schedules {
+            for trip in schedule
+                .direction_zero
+                .iter()
+                .chain(schedule.direction_one.iter())
+            {
+                if !trip_ids.insert(trip.trip_id.clone()) {
+                    continue;
+                }
+
+                feed.trips.push(GtfsTrip {
+                    route_id: trip.route.to_string(),
+                    service_id: service_id.clone(),
+                    trip_id: trip.trip_id.clone(),
+                    trip_headsign: trip.trip_headsign.clone(),
+                    direction_id: trip.direction_number.trim().parse().ok(),
+                    shape_id: trip_shape_id(trip, paths),
+                });
+
+                let mut stop_times: Vec<_> = trip.stop_times.iter().collect();
+                stop_times.sort_by_key(|stop_time| stop_time.stop_sequence);
+
+                for stop_time in stop_times {
+                    let time = gtfs_time(midnight, stop_time.time);
+
+                    feed.stop_times.push(GtfsStopTime {
+                        trip_id: trip.trip_id.clone(),
+                        arrival_time: time.clone(),
+                        departure_time: time,
+                        stop_id: stop_time.stop.0.clone(),
+                        stop_sequence: stop_time.stop_sequence.max(0) as u32,
+                    });
+                }
+            }
+        }
+
+        Ok(feed)
+    }
+
+    /// Every reference in this feed to an agency, route, service, trip, stop, or shape which does not exist.
+    pub fn validate(&self) -> Vec<IntegrityError> {
+        let agency_ids: HashSet<&str> = self
+            .agencies
+            .iter()
+            .map(|agency| agency.agency_id.as_str())
+            .collect();
+        let stop_ids: HashSet<&str> = self
+            .stops
+            .iter()
+            .map(|stop| stop.stop_id.as_str())
+            .collect();
+        let route_ids: HashSet<&str> = self
+            .routes
+            .iter()
+            .map(|route| route.route_id.as_str())
+            .collect();
+        let trip_ids: HashSet<&str> = self
+            .trips
+            .iter()
+            .map(|trip| trip.trip_id.as_str())
+            .collect();
+        let shape_ids: HashSet<&str> = self
+            .shapes
+            .iter()
+            .map(|shape| shape.shape_id.as_str())
+            .collect();
+        let service_ids: HashSet<&str> = self
+            .calendar_dates
+            .iter()
+            .map(|calendar_date| calendar_date.service_id.as_str())
+            .chain(
+                self.calendars
+                    .iter()
+                    .map(|calendar| calendar.service_id.as_str()),
+            )
+            .collect();
+
+        let mut errors = vec![];
+        let mut check = |ids: &HashSet<&str>, file: &'static str, field: &'static str, id: &str| {
+            if !ids.contains(id) {
+                errors.push(IntegrityError {
+                    file,
+                    field,
+                    id: id.to_string(),
+                });
+            }
+        };
+
+        for stop in self.stops.iter() {
+            if let Some(parent_station) = &stop.parent_station {
+                check(&stop_ids, "stops.txt", "parent_station", parent_station);
+            }
+        }
+
+        for route in self.routes.iter() {
+            // Feeds with a single agency may leave agency_id out
+            if !route.agency_id.is_empty() || self.agencies.len() != 1 {
+                check(&agency_ids, "routes.txt", "agency_id", &route.agency_id);
+            }
+        }
+
+        for trip in self.trips.iter() {
+            check(&route_ids, "trips.txt", "route_id", &trip.route_id);
+            check(&service_ids, "trips.txt", "service_id", &trip.service_id);
+
+            if let Some(shape_id) = &trip.shape_id {
+                check(&shape_ids, "trips.txt", "shape_id", shape_id);
+            }
+        }
+
+        for stop_time in self.stop_times.iter() {
+            check(&trip_ids, "stop_times.txt", "trip_id", &stop_time.trip_id);
+            check(&stop_ids, "stop_times.txt", "stop_id", &stop_time.stop_id);
+        }
+
+        errors
+    }
+
+    /// Contents of each file in this feed, as (file name, CSV).
+    /// `shapes.txt`, `calendar_dates.txt`, and `calendar.txt` are optional, and only included when they have rows,
+    /// as a CSV without rows has no header either.
+    pub fn files(&self) -> Result<Vec<(&'static str, String)>, Error> {
+        let mut files = vec![
+            ("agency.txt", to_csv(&self.agencies)?),
+            ("stops.txt", to_csv(&self.stops)?),
+            ("routes.txt", to_csv(&self.routes)?),
+            ("trips.txt", to_csv(&self.trips)?),
+            ("stop_times.txt", to_csv(&self.stop_times)?),
+        ];
+
+        if !self.shapes.is_empty() {
+            files.push(("shapes.txt", to_csv(&self.shapes)?));
+        }
+
+        if !self.calendar_dates.is_empty() {
+            files.push(("calendar_dates.txt", to_csv(&self.calendar_dates)?));
+        }
+
+        if !self.calendars.is_empty() {
+            files.push(("calendar.txt", to_csv(&self.calendars)?));
+        }
+
+        Ok(files)
+    }
+
+    /// Writes each file in this feed to a directory, creating it if needed.
+    pub fn write_to_directory<P>(&self, directory: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        fs::create_dir_all(&directory).map_err(|err| Error::new(err.to_string()))?;
+
+        for (name, contents) in self.files()? {
+            fs::write(directory.as_ref().join(name), contents)
+                .map_err(|err| Error::new(err.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes this feed as a zip archive.
+    pub fn write_zip<W>(&self, writer: W) -> Result<(), Error>
+    where
+        W: Write + Seek,
+    {
+        let mut zip = ZipWriter::new(writer);
+
+        for (name, contents) in self.files()? {
+            zip.start_file(name, FileOptions::default())
+                .map_err(|err| Error::new(err.to_string()))?;
+            zip.write_all(contents.as_bytes())
+                .map_err(|err| Error::new(err.to_string()))?;
+        }
+
+        zip.finish().map_err(|err| Error::new(err.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// A usable stop ID. WMATA uses 0 or null for stops without one.
+fn stop_id(stop: Option<&str>) -> Option<String> {
+    match stop {
+        Some(stop) if !stop.is_empty() && stop != "0" => Some(stop.to_string()),
+        _ => None,
+    }
+}
+
+fn shape_id(path: &PathDetails, direction: &PathDirection) -> String {
+    format!(
+        "{}_{}",
+        path.route.to_string(),
+        direction.direction_number.trim()
+    )
+}
+
+/// Shape of the path for a trip's route variant and direction, if one was provided.
+fn trip_shape_id(trip: &RouteInfo, paths: &[PathDetails]) -> Option<String> {
+    paths
+        .iter()
+        .filter(|path| path.route == trip.route)
+        .flat_map(|path| {
+            vec![&path.direction_zero, &path.direction_one]
+                .into_iter()
+                .filter(|direction| {
+                    direction.direction_number.trim() == trip.direction_number.trim()
+                        && !direction.shape.is_empty()
+                })
+                .map(move |direction| shape_id(path, direction))
+        })
+        .next()
+}
+
+/// Formats a time as HH:MM:SS since midnight of the service date.
+fn gtfs_time(midnight: NaiveDateTime, time: DateTime<FixedOffset>) -> String {
+    let seconds = time
+        .naive_local()
+        .signed_duration_since(midnight)
+        .num_seconds()
+        .max(0);
+
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn to_csv<T>(records: &[T]) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    for record in records {
+        writer
+            .serialize(record)
+            .map_err(|err| Error::new(err.to_string()))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| Error::new(err.to_string()))?;
+
+    String::from_utf8(bytes).map_err(|err| Error::new(err.to_string()))
+}
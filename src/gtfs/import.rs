@@ -0,0 +1,568 @@
+//! Reading GTFS static feeds, and answering MetroBus and MetroRail questions from them without a network call.
+mod tests;
+
+use super::{Feed, GtfsRoute, GtfsStop, GtfsStopTime, GtfsTrip};
+use crate::{
+    bus::client::responses::{
+        Arrival, RouteInfo, RouteResponse, RouteSchedule, Routes, StopInfo, StopResponse,
+        StopRoutes, StopSchedule, Stops,
+    },
+    date,
+    error::Error,
+    rail::client::responses::{Address, StationResponse, Stations},
+    Date, Route, Station, Stop,
+};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Weekday,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{ErrorKind, Read, Seek},
+    path::Path,
+    str::FromStr,
+};
+use zip::{result::ZipError, ZipArchive};
+
+impl Feed {
+    /// Reads a feed from a zip archive, such as one of WMATA's published GTFS feeds.
+    pub fn read_zip<R>(reader: R) -> Result<Self, Error>
+    where
+        R: Read + Seek,
+    {
+        let mut archive = ZipArchive::new(reader).map_err(|err| Error::new(err.to_string()))?;
+
+        Feed::read(|name| {
+            let mut file = match archive.by_name(name) {
+                Ok(file) => file,
+                Err(ZipError::FileNotFound) => return Ok(None),
+                Err(err) => return Err(Error::new(err.to_string())),
+            };
+
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)
+                .map_err(|err| Error::new(err.to_string()))?;
+
+            Ok(Some(contents))
+        })
+    }
+
+    /// Reads a feed from a directory of GTFS files.
+    pub fn read_from_directory<P>(directory: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Feed::read(
+            |name| match fs::read_to_string(directory.as_ref().join(name)) {
+                Ok(contents) => Ok(Some(contents)),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(Error::new(err.to_string())),
+            },
+        )
+    }
+
+    fn read<F>(mut read_file: F) -> Result<Self, Error>
+    where
+        F: FnMut(&str) -> Result<Option<String>, Error>,
+    {
+        let mut required = |name: &str| -> Result<String, Error> {
+            read_file(name)?.ok_or_else(|| Error::new(format!("Feed is missing {}.", name)))
+        };
+
+        let agencies = from_csv(&required("agency.txt")?)?;
+        let stops = from_csv(&required("stops.txt")?)?;
+        let routes = from_csv(&required("routes.txt")?)?;
+        let trips = from_csv(&required("trips.txt")?)?;
+        let stop_times = from_csv(&required("stop_times.txt")?)?;
+
+        let mut optional =
+            |name: &str| -> Result<String, Error> { Ok(read_file(name)?.unwrap_or_default()) };
+
+        Ok(Feed {
+            agencies,
+            stops,
+            routes,
+            trips,
+            stop_times,
+            shapes: from_csv(&optional("shapes.txt")?)?,
+            calendars: from_csv(&optional("calendar.txt")?)?,
+            calendar_dates: from_csv(&optional("calendar_dates.txt")?)?,
+        })
+    }
+
+    /// Every MetroBus route in this feed, as returned by [`MetroBus::routes`](crate::MetroBus::routes).
+    /// Routes which aren't known to this crate are left out.
+    pub fn bus_routes(&self) -> Routes {
+        Routes {
+            routes: self
+                .routes
+                .iter()
+                .filter_map(|gtfs_route| {
+                    let route = route_of(gtfs_route)?;
+
+                    Some(RouteResponse {
+                        route,
+                        name: route_name(gtfs_route),
+                        line_description: gtfs_route.route_desc.clone(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Every MetroBus stop in this feed, as returned by [`MetroBus::stops`](crate::MetroBus::stops).
+    /// Stations, and stops belonging to MetroRail stations, are left out.
+    pub fn bus_stops(&self) -> Stops {
+        let routes_at = self.routes_at_stops();
+
+        Stops {
+            stops: self
+                .stops
+                .iter()
+                .filter(|stop| is_bus_stop(stop))
+                .filter_map(|stop| {
+                    let (latitude, longitude) = coordinates(stop)?;
+
+                    Some(StopResponse {
+                        stop: Some(stop_of(stop)),
+                        name: stop.stop_name.clone(),
+                        latitude,
+                        longitude,
+                        routes: routes_at
+                            .get(stop.stop_id.as_str())
+                            .cloned()
+                            .unwrap_or_default()
+                            .into_boxed_slice(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Every MetroRail station in this feed, as returned by [`MetroRail::stations_on`](crate::MetroRail::stations_on).
+    /// Lines and platforms come from [`Station::lines`] and [`Station::together`], since GTFS does not include them.
+    pub fn rail_stations(&self) -> Stations {
+        let mut stations: Vec<(Station, &GtfsStop, (f64, f64))> = vec![];
+
+        for stop in self.stops.iter() {
+            let (station, coordinates) =
+                match (Station::from_gtfs_id(&stop.stop_id), coordinates(stop)) {
+                    (Some(station), Some(coordinates)) => (station, coordinates),
+                    _ => continue,
+                };
+
+            match stations.iter().position(|(other, _, _)| *other == station) {
+                // Prefer the station over its platforms and entrances
+                Some(index) if stop.location_type == Some(1) => {
+                    stations[index] = (station, stop, coordinates)
+                }
+                Some(_) => {}
+                None => stations.push((station, stop, coordinates)),
+            }
+        }
+
+        Stations {
+            stations: stations
+                .into_iter()
+                .filter_map(|(station, stop, (latitude, longitude))| {
+                    let lines = station.lines();
+
+                    Some(StationResponse {
+                        address: Address {
+                            city: String::new(),
+                            state: String::new(),
+                            street: String::new(),
+                            zip: String::new(),
+                        },
+                        station,
+                        latitude,
+                        longitude,
+                        first_line: *lines.first()?,
+                        second_line: lines.get(1).copied(),
+                        third_line: lines.get(2).copied(),
+                        fourth_line: lines.get(3).copied(),
+                        name: stop.stop_name.clone(),
+                        first_station_together: station.together(),
+                        second_station_together: None,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Schedule of a route on a date, as returned by [`MetroBus::route_schedule`](crate::MetroBus::route_schedule).
+    ///
+    /// With `including_variations`, trips on every variant of the route (e.g.: 10Av1 for 10A) are included.
+    pub fn route_schedule(
+        &self,
+        route: Route,
+        date: Date,
+        including_variations: bool,
+    ) -> Result<RouteSchedule, Error> {
+        let midnight = midnight(date)?;
+        let services = self.services_on(midnight.date());
+        let stops = self.stops_by_id();
+        let stop_times = self.stop_times_by_trip();
+
+        let base = route.to_string();
+        let mut name = base.clone();
+        let mut directions: (Vec<RouteInfo>, Vec<RouteInfo>) = (vec![], vec![]);
+
+        for gtfs_route in self.routes.iter() {
+            let trip_route = match route_of(gtfs_route) {
+                Some(trip_route) => trip_route,
+                None => continue,
+            };
+
+            let trip_route_name = trip_route.to_string();
+            let matches = trip_route == route
+                || (including_variations && trip_route_name.starts_with(&format!("{}v", base)));
+
+            if !matches {
+                continue;
+            }
+
+            if trip_route == route {
+                name = route_name(gtfs_route);
+            }
+
+            for trip in self.trips.iter().filter(|trip| {
+                trip.route_id == gtfs_route.route_id && services.contains(trip.service_id.as_str())
+            }) {
+                let times = match stop_times.get(trip.trip_id.as_str()) {
+                    Some(times) => times,
+                    None => continue,
+                };
+
+                let stop_infos = times
+                    .iter()
+                    .filter_map(|stop_time| Some((*stop_time, departure(stop_time)?)))
+                    .map(|(stop_time, departure)| {
+                        Ok(StopInfo {
+                            stop: match stops.get(stop_time.stop_id.as_str()) {
+                                Some(stop) => stop_of(stop),
+                                None => Stop::new(&stop_time.stop_id),
+                            },
+                            stop_name: stops
+                                .get(stop_time.stop_id.as_str())
+                                .map(|stop| stop.stop_name.clone())
+                                .unwrap_or_default(),
+                            stop_sequence: stop_time.stop_sequence as i32,
+                            time: time_on(midnight, departure)?,
+                        })
+                    })
+                    .collect::<Result<Vec<StopInfo>, Error>>()?;
+
+                if stop_infos.is_empty() {
+                    continue;
+                }
+
+                let info = RouteInfo {
+                    route: trip_route,
+                    direction_number: trip.direction_id.unwrap_or(0).to_string(),
+                    trip_direction_text: String::new(),
+                    trip_headsign: trip.trip_headsign.clone(),
+                    start_time: stop_infos[0].time,
+                    end_time: stop_infos[stop_infos.len() - 1].time,
+                    stop_times: stop_infos.into_boxed_slice(),
+                    trip_id: trip.trip_id.clone(),
+                };
+
+                if trip.direction_id == Some(1) {
+                    directions.1.push(info);
+                } else {
+                    directions.0.push(info);
+                }
+            }
+        }
+
+        directions.0.sort_by_key(|trip| trip.start_time);
+        directions.1.sort_by_key(|trip| trip.start_time);
+
+        Ok(RouteSchedule {
+            name,
+            direction_zero: directions.0.into_boxed_slice(),
+            direction_one: directions.1.into_boxed_slice(),
+        })
+    }
+
+    /// Scheduled arrivals at a stop on a date, as returned by [`MetroBus::stop_schedule`](crate::MetroBus::stop_schedule).
+    /// Stops are matched by their code (the regional ID for MetroBus), or by their GTFS ID when they have no code.
+    pub fn stop_schedule(&self, stop: &Stop, date: Date) -> Result<StopSchedule, Error> {
+        let (gtfs_stop, (latitude, longitude)) = self
+            .stops
+            .iter()
+            .filter_map(|gtfs_stop| Some((gtfs_stop, coordinates(gtfs_stop)?)))
+            .find(|(gtfs_stop, _)| stop_of(gtfs_stop) == *stop)
+            .ok_or_else(|| Error::new(format!("Stop {} is not in this feed.", stop.0)))?;
+
+        let midnight = midnight(date)?;
+        let services = self.services_on(midnight.date());
+        let stop_times = self.stop_times_by_trip();
+        let routes: HashMap<&str, &GtfsRoute> = self
+            .routes
+            .iter()
+            .map(|route| (route.route_id.as_str(), route))
+            .collect();
+
+        let mut arrivals = vec![];
+        let mut stop_routes: Vec<Route> = vec![];
+
+        for trip in self.trips.iter() {
+            let route = match routes
+                .get(trip.route_id.as_str())
+                .and_then(|route| route_of(route))
+            {
+                Some(route) => route,
+                None => continue,
+            };
+
+            let times = match stop_times.get(trip.trip_id.as_str()) {
+                Some(times) if !times.is_empty() => times,
+                _ => continue,
+            };
+
+            let at_stop = match times
+                .iter()
+                .find(|stop_time| stop_time.stop_id == gtfs_stop.stop_id)
+            {
+                Some(at_stop) => at_stop,
+                None => continue,
+            };
+
+            if !stop_routes.contains(&route) {
+                stop_routes.push(route);
+            }
+
+            if !services.contains(trip.service_id.as_str()) {
+                continue;
+            }
+
+            let mut departures = times.iter().filter_map(|stop_time| departure(stop_time));
+
+            let (schedule_time, first, last) = match (
+                departure(at_stop),
+                departures.next(),
+                departures.next_back(),
+            ) {
+                (Some(schedule_time), Some(first), last) => {
+                    (schedule_time, first, last.unwrap_or(first))
+                }
+                _ => continue,
+            };
+
+            arrivals.push(Arrival {
+                schedule_time: time_on(midnight, schedule_time)?,
+                direction_number: trip.direction_id.unwrap_or(0).to_string(),
+                start_time: time_on(midnight, first)?,
+                end_time: time_on(midnight, last)?,
+                route,
+                trip_direction_text: String::new(),
+                trip_headsign: trip.trip_headsign.clone(),
+                trip_id: trip.trip_id.clone(),
+            });
+        }
+
+        arrivals.sort_by_key(|arrival| arrival.schedule_time);
+
+        Ok(StopSchedule {
+            arrivals: arrivals.into_boxed_slice(),
+            stop: StopRoutes {
+                stop: Some(stop_of(gtfs_stop)),
+                name: gtfs_stop.stop_name.clone(),
+                latitude,
+                longitude,
+                routes: stop_routes.into_boxed_slice(),
+            },
+        })
+    }
+
+    /// Service IDs running on a date, from both `calendar.txt` and `calendar_dates.txt`.
+    fn services_on(&self, date: NaiveDate) -> HashSet<&str> {
+        let day = date.format("%Y%m%d").to_string();
+        let weekday = date.weekday();
+
+        let mut services: HashSet<&str> = self
+            .calendars
+            .iter()
+            .filter(|calendar| {
+                let runs = match weekday {
+                    Weekday::Mon => calendar.monday,
+                    Weekday::Tue => calendar.tuesday,
+                    Weekday::Wed => calendar.wednesday,
+                    Weekday::Thu => calendar.thursday,
+                    Weekday::Fri => calendar.friday,
+                    Weekday::Sat => calendar.saturday,
+                    Weekday::Sun => calendar.sunday,
+                };
+
+                runs == 1 && calendar.start_date <= day && day <= calendar.end_date
+            })
+            .map(|calendar| calendar.service_id.as_str())
+            .collect();
+
+        for calendar_date in self.calendar_dates.iter().filter(|date| date.date == day) {
+            match calendar_date.exception_type {
+                1 => {
+                    services.insert(&calendar_date.service_id);
+                }
+                2 => {
+                    services.remove(calendar_date.service_id.as_str());
+                }
+                _ => {}
+            }
+        }
+
+        services
+    }
+
+    fn stops_by_id(&self) -> HashMap<&str, &GtfsStop> {
+        self.stops
+            .iter()
+            .map(|stop| (stop.stop_id.as_str(), stop))
+            .collect()
+    }
+
+    /// Stop times of each trip, in order.
+    fn stop_times_by_trip(&self) -> HashMap<&str, Vec<&GtfsStopTime>> {
+        let mut stop_times: HashMap<&str, Vec<&GtfsStopTime>> = HashMap::new();
+
+        for stop_time in self.stop_times.iter() {
+            stop_times
+                .entry(stop_time.trip_id.as_str())
+                .or_default()
+                .push(stop_time);
+        }
+
+        for times in stop_times.values_mut() {
+            times.sort_by_key(|stop_time| stop_time.stop_sequence);
+        }
+
+        stop_times
+    }
+
+    /// Routes serving each stop, by GTFS stop ID.
+    fn routes_at_stops(&self) -> HashMap<&str, Vec<Route>> {
+        let routes: HashMap<&str, Route> = self
+            .routes
+            .iter()
+            .filter_map(|route| Some((route.route_id.as_str(), route_of(route)?)))
+            .collect();
+        let trips: HashMap<&str, &GtfsTrip> = self
+            .trips
+            .iter()
+            .map(|trip| (trip.trip_id.as_str(), trip))
+            .collect();
+
+        let mut routes_at: HashMap<&str, Vec<Route>> = HashMap::new();
+
+        for stop_time in self.stop_times.iter() {
+            let route = match trips
+                .get(stop_time.trip_id.as_str())
+                .and_then(|trip| routes.get(trip.route_id.as_str()))
+            {
+                Some(route) => *route,
+                None => continue,
+            };
+
+            let stop_routes = routes_at.entry(stop_time.stop_id.as_str()).or_default();
+
+            if !stop_routes.contains(&route) {
+                stop_routes.push(route);
+            }
+        }
+
+        routes_at
+    }
+}
+
+fn from_csv<T>(contents: &str) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes())
+        .deserialize()
+        .collect::<Result<Vec<T>, csv::Error>>()
+        .map_err(|err| Error::new(err.to_string()))
+}
+
+/// [`Route`] of a GTFS route, by its short name or ID.
+fn route_of(route: &GtfsRoute) -> Option<Route> {
+    Route::from_str(&route.route_short_name)
+        .or_else(|_| Route::from_str(&route.route_id))
+        .ok()
+}
+
+fn route_name(route: &GtfsRoute) -> String {
+    if route.route_long_name.is_empty() {
+        route.route_short_name.clone()
+    } else {
+        route.route_long_name.clone()
+    }
+}
+
+/// [`Stop`] of a GTFS stop, by its code, or its ID if it has no code.
+fn stop_of(stop: &GtfsStop) -> Stop {
+    match &stop.stop_code {
+        Some(code) if !code.is_empty() => Stop::new(code),
+        _ => Stop::new(&stop.stop_id),
+    }
+}
+
+/// Latitude and longitude of a GTFS stop, unless it is a generic node or boarding area without them.
+fn coordinates(stop: &GtfsStop) -> Option<(f64, f64)> {
+    Some((stop.stop_lat?, stop.stop_lon?))
+}
+
+fn is_bus_stop(stop: &GtfsStop) -> bool {
+    stop.location_type.unwrap_or(0) == 0
+        && Station::from_gtfs_id(&stop.stop_id).is_none()
+        && match &stop.parent_station {
+            Some(parent_station) => Station::from_gtfs_id(parent_station).is_none(),
+            None => true,
+        }
+}
+
+fn midnight(date: Date) -> Result<NaiveDateTime, Error> {
+    NaiveDate::from_ymd_opt(
+        i32::from(date.year),
+        u32::from(date.month),
+        u32::from(date.day),
+    )
+    .and_then(|date| date.and_hms_opt(0, 0, 0))
+    .ok_or_else(|| Error::new(format!("{} is not a valid date.", date.to_string())))
+}
+
+/// Departure time of a stop time, falling back to its arrival time.
+/// `None` when both are blank, as GTFS allows for stops between timepoints.
+fn departure(stop_time: &GtfsStopTime) -> Option<&str> {
+    [&stop_time.departure_time, &stop_time.arrival_time]
+        .iter()
+        .map(|time| time.trim())
+        .find(|time| !time.is_empty())
+}
+
+/// A GTFS time (HH:MM:SS since midnight, possibly past 24:00:00) on a service date.
+fn time_on(midnight: NaiveDateTime, time: &str) -> Result<DateTime<FixedOffset>, Error> {
+    let invalid = || Error::new(format!("{} is not a valid GTFS time.", time));
+
+    let parts = time
+        .trim()
+        .split(':')
+        .map(|part| part.parse::<i64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<i64>, Error>>()?;
+
+    if parts.len() != 3 {
+        return Err(invalid());
+    }
+
+    let local = midnight + Duration::seconds(parts[0] * 3600 + parts[1] * 60 + parts[2]);
+
+    date::eastern()
+        .from_local_datetime(&local)
+        .single()
+        .ok_or_else(invalid)
+}
@@ -0,0 +1,277 @@
+//! Tests for GTFS import
+#[cfg(test)]
+use super::*;
+#[cfg(test)]
+use crate::Line;
+#[cfg(test)]
+use std::io::Cursor;
+
+#[cfg(test)]
+const FILES: &[(&str, &str)] = &[
+    (
+        "agency.txt",
+        "agency_name,agency_url,agency_timezone\nWMATA,https://www.wmata.com,America/New_York\n",
+    ),
+    (
+        "stops.txt",
+        "stop_id,stop_code,stop_name,stop_lat,stop_lon,location_type,parent_station
+1,1001,FIRST ST,38.9,-77.0,,
+2,1002,SECOND ST,38.8,-77.0,0,
+3,1003,THIRD ST,38.85,-77.0,0,
+NODE_A01_C_MEZZ,,METRO CENTER MEZZANINE,,,3,STN_A01_C01
+STN_A01_C01,,METRO CENTER,38.898,-77.028,1,
+BA_A01_C_1,,METRO CENTER BOARDING AREA,,,4,PF_A01_C
+PF_A01_C,,METRO CENTER PLATFORM,38.8983,-77.0281,0,STN_A01_C01
+",
+    ),
+    (
+        "routes.txt",
+        "route_id,route_short_name,route_long_name,route_desc,route_type
+A2,A2,A2 - ANACOSTIA,Anacostia Line,3
+A2v1,A2v1,,,3
+RED,RED,Red Line,,1
+",
+    ),
+    (
+        "trips.txt",
+        "route_id,service_id,trip_id,trip_headsign,direction_id
+A2,WEEKDAY,100,ANACOSTIA,0
+A2,WEEKDAY,200,FEDERAL TRIANGLE,1
+A2v1,WEEKDAY,300,ANACOSTIA,0
+A2,WEEKEND,400,ANACOSTIA,0
+",
+    ),
+    (
+        "stop_times.txt",
+        "trip_id,arrival_time,departure_time,stop_id,stop_sequence
+100,24:10:00,24:10:00,2,2
+100,23:50:00,23:50:00,1,1
+200,08:00:00,08:00:00,2,1
+200,08:30:00,,1,2
+300,07:00:00,07:00:00,1,1
+300,,,3,2
+300,07:15:00,07:15:00,2,3
+400,09:00:00,09:00:00,1,1
+",
+    ),
+    (
+        "calendar.txt",
+        "service_id,monday,tuesday,wednesday,thursday,friday,saturday,sunday,start_date,end_date
+WEEKDAY,1,1,1,1,1,0,0,20190101,20191231
+WEEKEND,0,0,0,0,0,1,1,20190101,20191231
+",
+    ),
+    (
+        "calendar_dates.txt",
+        "service_id,date,exception_type
+WEEKDAY,20191128,2
+WEEKEND,20191128,1
+",
+    ),
+];
+
+#[cfg(test)]
+fn feed() -> Feed {
+    Feed::read(|name| {
+        Ok(FILES
+            .iter()
+            .find(|(file, _)| *file == name)
+            .map(|(_, contents)| contents.to_string()))
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_read_zip() {
+    let mut buffer = Cursor::new(vec![]);
+    feed().write_zip(&mut buffer).unwrap();
+    buffer.set_position(0);
+
+    let feed = Feed::read_zip(buffer).unwrap();
+
+    assert_eq!(feed.stops.len(), 7);
+    assert_eq!(feed.calendars.len(), 2);
+    assert_eq!(feed.stops[0].stop_code, Some("1001".to_string()));
+}
+
+#[test]
+fn test_read_from_directory() {
+    let directory = std::env::temp_dir().join(format!("wmata-gtfs-import-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    for (name, contents) in FILES.iter().filter(|(name, _)| *name != "calendar.txt") {
+        fs::write(directory.join(name), contents).unwrap();
+    }
+
+    let feed = Feed::read_from_directory(&directory);
+    fs::remove_dir_all(&directory).unwrap();
+
+    let feed = feed.unwrap();
+    assert_eq!(feed.trips.len(), 4);
+    assert!(feed.calendars.is_empty());
+}
+
+#[test]
+fn test_missing_required_file() {
+    let feed = Feed::read(|name| {
+        Ok(FILES
+            .iter()
+            .filter(|(file, _)| *file != "stop_times.txt")
+            .find(|(file, _)| *file == name)
+            .map(|(_, contents)| contents.to_string()))
+    });
+
+    assert!(feed.is_err());
+}
+
+#[test]
+fn test_bus_routes() {
+    let routes = feed().bus_routes();
+
+    assert_eq!(routes.routes.len(), 2);
+    assert_eq!(routes.routes[0].route, Route::A2);
+    assert_eq!(routes.routes[0].name, "A2 - ANACOSTIA");
+    assert_eq!(routes.routes[0].line_description, "Anacostia Line");
+    assert_eq!(routes.routes[1].name, "A2v1");
+}
+
+#[test]
+fn test_bus_stops() {
+    let stops = feed().bus_stops();
+
+    assert_eq!(stops.stops.len(), 3);
+    assert_eq!(stops.stops[0].stop, Some(Stop::new("1001")));
+    assert_eq!(stops.stops[0].routes.to_vec(), vec![Route::A2, Route::A2v1]);
+}
+
+#[test]
+fn test_stops_without_coordinates() {
+    let feed = feed();
+    let node = feed
+        .stops
+        .iter()
+        .find(|stop| stop.stop_id == "NODE_A01_C_MEZZ")
+        .unwrap();
+
+    assert_eq!(node.location_type, Some(3));
+    assert_eq!(node.stop_lat, None);
+    assert_eq!(node.stop_lon, None);
+    assert!(feed
+        .bus_stops()
+        .stops
+        .iter()
+        .all(|stop| stop.name != "METRO CENTER MEZZANINE"));
+}
+
+#[test]
+fn test_rail_stations() {
+    let stations = feed().rail_stations();
+
+    assert_eq!(stations.stations.len(), 1);
+
+    let station = &stations.stations[0];
+    assert_eq!(station.station, Station::A01);
+    assert_eq!(station.name, "METRO CENTER");
+    assert_eq!(station.latitude, 38.898);
+    assert_eq!(station.first_line, Line::Blue);
+    assert_eq!(station.fourth_line, Some(Line::Red));
+    assert_eq!(station.first_station_together, Some(Station::C01));
+}
+
+#[test]
+fn test_route_schedule() {
+    let schedule = feed()
+        .route_schedule(Route::A2, Date::new(2019, 10, 1), false)
+        .unwrap();
+
+    assert_eq!(schedule.name, "A2 - ANACOSTIA");
+    assert_eq!(schedule.direction_zero.len(), 1);
+    assert_eq!(schedule.direction_one.len(), 1);
+
+    let trip = &schedule.direction_zero[0];
+    assert_eq!(trip.trip_id, "100");
+    assert_eq!(trip.stop_times[0].stop, Stop::new("1001"));
+    assert_eq!(trip.start_time.to_rfc3339(), "2019-10-01T23:50:00-05:00");
+    assert_eq!(trip.end_time.to_rfc3339(), "2019-10-02T00:10:00-05:00");
+    assert_eq!(
+        schedule.direction_one[0].end_time.to_rfc3339(),
+        "2019-10-01T08:30:00-05:00"
+    );
+}
+
+#[test]
+fn test_route_schedule_including_variations() {
+    let schedule = feed()
+        .route_schedule(Route::A2, Date::new(2019, 10, 1), true)
+        .unwrap();
+
+    assert_eq!(schedule.direction_zero.len(), 2);
+    assert_eq!(schedule.direction_zero[0].route, Route::A2v1);
+    assert_eq!(schedule.direction_zero[0].trip_id, "300");
+}
+
+#[test]
+fn test_route_schedule_calendar_dates() {
+    let feed = feed();
+
+    let saturday = feed
+        .route_schedule(Route::A2, Date::new(2019, 10, 5), false)
+        .unwrap();
+    assert_eq!(saturday.direction_zero[0].trip_id, "400");
+    assert!(saturday.direction_one.is_empty());
+
+    // Thanksgiving runs a weekend schedule
+    let thanksgiving = feed
+        .route_schedule(Route::A2, Date::new(2019, 11, 28), false)
+        .unwrap();
+    assert_eq!(thanksgiving.direction_zero.len(), 1);
+    assert_eq!(thanksgiving.direction_zero[0].trip_id, "400");
+}
+
+#[test]
+fn test_stop_schedule() {
+    let schedule = feed()
+        .stop_schedule(&Stop::new("1002"), Date::new(2019, 10, 1))
+        .unwrap();
+
+    assert_eq!(schedule.stop.name, "SECOND ST");
+    assert_eq!(schedule.stop.routes.to_vec(), vec![Route::A2, Route::A2v1]);
+    assert_eq!(
+        schedule
+            .arrivals
+            .iter()
+            .map(|arrival| arrival.trip_id.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["300", "200", "100"]
+    );
+    assert_eq!(
+        schedule.arrivals[2].schedule_time.to_rfc3339(),
+        "2019-10-02T00:10:00-05:00"
+    );
+}
+
+#[test]
+fn test_blank_times() {
+    let feed = feed();
+
+    // Stops without a time are between timepoints, and left out of schedules
+    let schedule = feed
+        .route_schedule(Route::A2v1, Date::new(2019, 10, 1), false)
+        .unwrap();
+    let trip = &schedule.direction_zero[0];
+    assert_eq!(trip.stop_times.len(), 2);
+    assert_eq!(trip.end_time.to_rfc3339(), "2019-10-01T07:15:00-05:00");
+
+    let schedule = feed
+        .stop_schedule(&Stop::new("1003"), Date::new(2019, 10, 1))
+        .unwrap();
+    assert_eq!(schedule.stop.routes.to_vec(), vec![Route::A2v1]);
+    assert!(schedule.arrivals.is_empty());
+}
+
+#[test]
+fn test_stop_schedule_unknown_stop() {
+    assert!(feed()
+        .stop_schedule(&Stop::new("9999"), Date::new(2019, 10, 1))
+        .is_err());
+}
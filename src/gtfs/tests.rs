@@ -0,0 +1,218 @@
+//! Tests for GTFS static feeds
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn feed() -> Feed {
+    let routes: Routes = serde_json::from_str(
+        r#"{"Routes": [{"RouteID": "A2", "Name": "A2 - ANACOSTIA", "LineDescription": "Anacostia Line"}]}"#,
+    )
+    .unwrap();
+    let stops: Stops = serde_json::from_str(
+        r#"{
+            "Stops": [
+                {"StopID": "1001", "Name": "FIRST ST", "Lat": 38.9, "Lon": -77.0, "Routes": ["A2"]},
+                {"StopID": "0", "Name": "NO ID", "Lat": 38.9, "Lon": -77.0, "Routes": ["A2"]}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let path: PathDetails = serde_json::from_str(
+        r#"{
+            "RouteID": "A2",
+            "Name": "A2 - ANACOSTIA",
+            "Direction0": {
+                "TripHeadsign": "ANACOSTIA",
+                "DirectionText": "SOUTH",
+                "DirectionNum": "0",
+                "Shape": [
+                    {"Lat": 38.8, "Lon": -77.0, "SeqNum": 2},
+                    {"Lat": 38.9, "Lon": -77.0, "SeqNum": 1}
+                ],
+                "Stops": [
+                    {"StopID": "1001", "Name": "FIRST ST", "Lat": 38.9, "Lon": -77.0, "Routes": ["A2"]},
+                    {"StopID": "1002", "Name": "SECOND ST", "Lat": 38.8, "Lon": -77.0, "Routes": ["A2"]}
+                ]
+            },
+            "Direction1": {
+                "TripHeadsign": "FEDERAL TRIANGLE",
+                "DirectionText": "NORTH",
+                "DirectionNum": "1",
+                "Shape": [],
+                "Stops": []
+            }
+        }"#,
+    )
+    .unwrap();
+    let schedule: RouteSchedule = serde_json::from_str(
+        r#"{
+            "Name": "A2",
+            "Direction0": [{
+                "RouteID": "A2",
+                "DirectionNum": "0",
+                "TripDirectionText": "SOUTH",
+                "TripHeadsign": "ANACOSTIA",
+                "StartTime": "2019-10-01T23:50:00",
+                "EndTime": "2019-10-02T00:10:00",
+                "StopTimes": [
+                    {"StopID": "1002", "StopName": "SECOND ST", "StopSeq": 2, "Time": "2019-10-02T00:10:00"},
+                    {"StopID": "1001", "StopName": "FIRST ST", "StopSeq": 1, "Time": "2019-10-01T23:50:00"}
+                ],
+                "TripID": "100"
+            }],
+            "Direction1": [{
+                "RouteID": "A2",
+                "DirectionNum": "1",
+                "TripDirectionText": "NORTH",
+                "TripHeadsign": "FEDERAL TRIANGLE",
+                "StartTime": "2019-10-01T08:00:00",
+                "EndTime": "2019-10-01T08:30:00",
+                "StopTimes": [],
+                "TripID": "200"
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    Feed::new(
+        Date::new(2019, 10, 1),
+        &routes,
+        &stops,
+        &[path],
+        &[schedule],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_feed() {
+    let feed = feed();
+
+    assert_eq!(feed.routes.len(), 1);
+    assert_eq!(feed.trips.len(), 2);
+    assert_eq!(feed.calendar_dates[0].date, "20191001");
+    assert!(feed.validate().is_empty());
+}
+
+#[test]
+fn test_stops_without_ids_are_skipped() {
+    let feed = feed();
+    let stop_ids: Vec<&str> = feed
+        .stops
+        .iter()
+        .map(|stop| stop.stop_id.as_str())
+        .collect();
+
+    assert_eq!(stop_ids, vec!["1001", "1002"]);
+}
+
+#[test]
+fn test_stop_times_past_midnight() {
+    let feed = feed();
+
+    assert_eq!(feed.stop_times[0].stop_id, "1001");
+    assert_eq!(feed.stop_times[0].arrival_time, "23:50:00");
+    assert_eq!(feed.stop_times[1].arrival_time, "24:10:00");
+}
+
+#[test]
+fn test_shapes() {
+    let feed = feed();
+
+    assert_eq!(feed.trips[0].shape_id, Some("A2_0".to_string()));
+    assert_eq!(feed.trips[0].direction_id, Some(0));
+    // The return direction has no shape
+    assert_eq!(feed.trips[1].shape_id, None);
+    assert_eq!(feed.shapes[0].shape_pt_lat, 38.9);
+    assert_eq!(feed.shapes[0].shape_pt_sequence, 0);
+}
+
+#[test]
+fn test_invalid_date() {
+    let routes: Routes = serde_json::from_str(r#"{"Routes": []}"#).unwrap();
+    let stops: Stops = serde_json::from_str(r#"{"Stops": []}"#).unwrap();
+
+    assert!(Feed::new(Date::new(2019, 2, 30), &routes, &stops, &[], &[]).is_err());
+}
+
+#[test]
+fn test_validate() {
+    let mut feed = feed();
+    feed.stops.retain(|stop| stop.stop_id != "1002");
+    feed.routes.clear();
+
+    let errors = feed.validate();
+
+    assert_eq!(errors.len(), 3);
+    assert!(errors.contains(&IntegrityError {
+        file: "stop_times.txt",
+        field: "stop_id",
+        id: "1002".to_string(),
+    }));
+    assert_eq!(
+        errors[0].to_string(),
+        "trips.txt references route_id A2, which does not exist."
+    );
+}
+
+#[test]
+fn test_files() {
+    let files = feed().files().unwrap();
+    let (name, trips) = &files[3];
+
+    assert_eq!(*name, "trips.txt");
+    assert_eq!(
+        trips.lines().next(),
+        Some("route_id,service_id,trip_id,trip_headsign,direction_id,shape_id")
+    );
+    assert_eq!(
+        trips.lines().nth(2),
+        Some("A2,20191001,200,FEDERAL TRIANGLE,1,")
+    );
+}
+
+#[test]
+fn test_files_without_shapes() {
+    let mut feed = feed();
+    feed.shapes.clear();
+
+    let files = feed.files().unwrap();
+
+    assert!(files.iter().all(|(name, _)| *name != "shapes.txt"));
+    assert!(files.iter().all(|(_, contents)| !contents.is_empty()));
+}
+
+#[test]
+fn test_write_to_directory() {
+    let directory = std::env::temp_dir().join(format!("wmata-gtfs-{}", std::process::id()));
+
+    feed().write_to_directory(&directory).unwrap();
+
+    assert!(directory.join("stop_times.txt").exists());
+    assert!(directory.join("calendar_dates.txt").exists());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_write_zip() {
+    let mut buffer = std::io::Cursor::new(vec![]);
+    feed().write_zip(&mut buffer).unwrap();
+
+    let archive = zip::ZipArchive::new(buffer).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort();
+
+    assert_eq!(
+        names,
+        vec![
+            "agency.txt",
+            "calendar_dates.txt",
+            "routes.txt",
+            "shapes.txt",
+            "stop_times.txt",
+            "stops.txt",
+            "trips.txt"
+        ]
+    );
+}
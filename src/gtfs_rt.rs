@@ -0,0 +1,432 @@
+//! Decoding and encoding of WMATA's GTFS Realtime trip updates, vehicle positions, and alerts, for both MetroBus and MetroRail.
+//!
+//! Only available with the `gtfs-rt` feature.
+mod encoder;
+mod tests;
+
+pub mod proto;
+
+pub use encoder::ToFeedMessage;
+
+use crate::{
+    date,
+    error::Error,
+    requests::{Fetch, Request},
+    Line, Route, Station,
+};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use prost::Message;
+use proto::{Cause, Effect, FeedMessage, StopTimeEvent, TripDescriptor, VehicleStopStatus};
+use std::{fmt, str::FromStr};
+
+/// WMATA's GTFS Realtime feeds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RealtimeFeed {
+    BusTripUpdates,
+    BusVehiclePositions,
+    BusAlerts,
+    RailTripUpdates,
+    RailVehiclePositions,
+    RailAlerts,
+}
+
+impl RealtimeFeed {
+    /// URL of this feed.
+    pub fn url(&self) -> &'static str {
+        match self {
+            RealtimeFeed::BusTripUpdates => "https://api.wmata.com/gtfs/bus-gtfsrt-tripupdates.pb",
+            RealtimeFeed::BusVehiclePositions => {
+                "https://api.wmata.com/gtfs/bus-gtfsrt-vehiclepositions.pb"
+            }
+            RealtimeFeed::BusAlerts => "https://api.wmata.com/gtfs/bus-gtfsrt-alerts.pb",
+            RealtimeFeed::RailTripUpdates => {
+                "https://api.wmata.com/gtfs/rail-gtfsrt-tripupdates.pb"
+            }
+            RealtimeFeed::RailVehiclePositions => {
+                "https://api.wmata.com/gtfs/rail-gtfsrt-vehiclepositions.pb"
+            }
+            RealtimeFeed::RailAlerts => "https://api.wmata.com/gtfs/rail-gtfsrt-alerts.pb",
+        }
+    }
+}
+
+impl fmt::Display for RealtimeFeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url())
+    }
+}
+
+/// Decodes a GTFS Realtime feed from protobuf bytes.
+pub fn decode(bytes: &[u8]) -> Result<FeedMessage, Error> {
+    FeedMessage::decode(bytes).map_err(|err| Error::new(err.to_string()))
+}
+
+/// Encodes a GTFS Realtime feed as protobuf bytes. See [`ToFeedMessage`].
+pub fn encode(feed: &FeedMessage) -> Vec<u8> {
+    feed.encode_to_vec()
+}
+
+/// Fetches and decodes a feed through a client, sharing its connection pool, keys, and retry and rate limit policies.
+/// Used by [`MetroBus::realtime`](crate::MetroBus::realtime) and [`MetroRail::realtime`](crate::MetroRail::realtime).
+pub(crate) async fn fetch<C>(
+    client: &C,
+    api_key: &str,
+    feed: RealtimeFeed,
+) -> Result<FeedMessage, Error>
+where
+    C: Fetch + Sync,
+{
+    decode(
+        &client
+            .fetch_bytes(Request::new(api_key, feed.url(), None))
+            .await?,
+    )
+}
+
+/// A bus from a vehicle positions feed, similar to [`BusPosition`](crate::bus::client::responses::BusPosition).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealtimeBusPosition {
+    /// Unique identifier for the bus.
+    pub vehicle_id: String,
+    /// Route of the bus's current trip, if it is known to this crate.
+    pub route: Option<Route>,
+    /// Current trip. This can be correlated with the data returned from the schedule-related methods.
+    pub trip_id: Option<String>,
+    /// Direction of the current trip.
+    pub direction_number: Option<u32>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Degrees clockwise from true north.
+    pub bearing: Option<f32>,
+    /// Meters per second.
+    pub speed: Option<f32>,
+    /// GTFS ID of the stop the bus is at or approaching.
+    pub stop_id: Option<String>,
+    /// Whether the bus is approaching, at, or past `stop_id`.
+    pub status: VehicleStopStatus,
+    /// Date and time (Eastern Standard Time) of this position.
+    pub date_time: Option<DateTime<FixedOffset>>,
+}
+
+/// A train from a vehicle positions feed, similar to [`TrainPosition`](crate::rail::client::responses::TrainPosition).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealtimeTrainPosition {
+    /// Uniquely identifiable internal train identifier.
+    pub train_id: String,
+    /// Non-unique train identifier shown to riders.
+    pub train_number: Option<String>,
+    /// [`Line`] of the train's current trip.
+    pub line: Option<Line>,
+    /// Current trip.
+    pub trip_id: Option<String>,
+    /// Direction of the current trip.
+    pub direction_number: Option<u32>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Degrees clockwise from true north.
+    pub bearing: Option<f32>,
+    /// [`Station`] the train is at or approaching.
+    pub station: Option<Station>,
+    /// Whether the train is approaching, at, or past `station`.
+    pub status: VehicleStopStatus,
+    /// Date and time (Eastern Standard Time) of this position.
+    pub date_time: Option<DateTime<FixedOffset>>,
+}
+
+/// Predictions for a trip from a trip updates feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripPrediction {
+    pub trip_id: Option<String>,
+    /// Route of a MetroBus trip.
+    pub route: Option<Route>,
+    /// Line of a MetroRail trip.
+    pub line: Option<Line>,
+    pub direction_number: Option<u32>,
+    /// Vehicle serving this trip.
+    pub vehicle_id: Option<String>,
+    /// Whether this trip was canceled.
+    pub canceled: bool,
+    /// Predictions for upcoming stops, in order.
+    pub stops: Vec<StopPrediction>,
+}
+
+/// Prediction for a stop along a trip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StopPrediction {
+    pub stop_sequence: Option<u32>,
+    /// GTFS ID of the stop.
+    pub stop_id: Option<String>,
+    /// [`Station`] of a MetroRail stop.
+    pub station: Option<Station>,
+    /// Predicted arrival (Eastern Standard Time). Missing when the feed only gives a delay.
+    pub arrival: Option<DateTime<FixedOffset>>,
+    /// Predicted departure (Eastern Standard Time). Missing when the feed only gives a delay.
+    pub departure: Option<DateTime<FixedOffset>>,
+    /// Deviation from schedule, in seconds. Positive values are late.
+    pub delay: Option<i32>,
+    /// Whether the trip will not stop here.
+    pub skipped: bool,
+}
+
+/// A service alert from an alerts feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealtimeAlert {
+    pub id: String,
+    /// Short summary of this alert, in English.
+    pub header: Option<String>,
+    /// Full description of this alert, in English.
+    pub description: Option<String>,
+    /// Link to more information.
+    pub url: Option<String>,
+    pub cause: Cause,
+    pub effect: Effect,
+    /// When this alert is in effect. In effect forever when empty.
+    pub active_periods: Vec<ActivePeriod>,
+    /// MetroBus routes affected by this alert.
+    pub routes: Vec<Route>,
+    /// MetroRail lines affected by this alert.
+    pub lines: Vec<Line>,
+    /// MetroRail stations affected by this alert.
+    pub stations: Vec<Station>,
+    /// GTFS IDs of stops affected by this alert which are not MetroRail stations.
+    pub stop_ids: Vec<String>,
+}
+
+/// A span of time (Eastern Standard Time) when an alert is in effect. A missing start or end is open ended.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ActivePeriod {
+    pub start: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+impl FeedMessage {
+    /// When this feed was created (Eastern Standard Time).
+    pub fn date_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.header
+            .timestamp
+            .and_then(|timestamp| date_time(timestamp as i64))
+    }
+
+    /// Every bus with a position in this feed.
+    pub fn bus_positions(&self) -> Vec<RealtimeBusPosition> {
+        self.vehicles()
+            .filter_map(|(id, vehicle)| {
+                let position = vehicle.position.as_ref()?;
+                let trip = vehicle.trip.as_ref();
+
+                Some(RealtimeBusPosition {
+                    vehicle_id: vehicle_id(id, vehicle.vehicle.as_ref()),
+                    route: trip.and_then(route_of),
+                    trip_id: trip.and_then(|trip| trip.trip_id.clone()),
+                    direction_number: trip.and_then(|trip| trip.direction_id),
+                    latitude: f64::from(position.latitude),
+                    longitude: f64::from(position.longitude),
+                    bearing: position.bearing,
+                    speed: position.speed,
+                    stop_id: vehicle.stop_id.clone(),
+                    status: vehicle.current_status(),
+                    date_time: vehicle
+                        .timestamp
+                        .and_then(|timestamp| date_time(timestamp as i64)),
+                })
+            })
+            .collect()
+    }
+
+    /// Every train with a position in this feed.
+    pub fn train_positions(&self) -> Vec<RealtimeTrainPosition> {
+        self.vehicles()
+            .filter_map(|(id, vehicle)| {
+                let position = vehicle.position.as_ref()?;
+                let trip = vehicle.trip.as_ref();
+
+                Some(RealtimeTrainPosition {
+                    train_id: vehicle_id(id, vehicle.vehicle.as_ref()),
+                    train_number: vehicle
+                        .vehicle
+                        .as_ref()
+                        .and_then(|descriptor| descriptor.label.clone()),
+                    line: trip.and_then(line_of),
+                    trip_id: trip.and_then(|trip| trip.trip_id.clone()),
+                    direction_number: trip.and_then(|trip| trip.direction_id),
+                    latitude: f64::from(position.latitude),
+                    longitude: f64::from(position.longitude),
+                    bearing: position.bearing,
+                    station: vehicle
+                        .stop_id
+                        .as_ref()
+                        .and_then(|stop_id| Station::from_gtfs_id(stop_id)),
+                    status: vehicle.current_status(),
+                    date_time: vehicle
+                        .timestamp
+                        .and_then(|timestamp| date_time(timestamp as i64)),
+                })
+            })
+            .collect()
+    }
+
+    /// Every trip update in this feed.
+    pub fn trip_predictions(&self) -> Vec<TripPrediction> {
+        self.entity
+            .iter()
+            .filter(|entity| !entity.is_deleted())
+            .filter_map(|entity| entity.trip_update.as_ref())
+            .map(|update| TripPrediction {
+                trip_id: update.trip.trip_id.clone(),
+                route: route_of(&update.trip),
+                line: line_of(&update.trip),
+                direction_number: update.trip.direction_id,
+                vehicle_id: update
+                    .vehicle
+                    .as_ref()
+                    .and_then(|vehicle| vehicle.id.clone()),
+                canceled: update.trip.schedule_relationship
+                    == Some(proto::TripScheduleRelationship::Canceled as i32),
+                stops: update
+                    .stop_time_update
+                    .iter()
+                    .map(|stop| StopPrediction {
+                        stop_sequence: stop.stop_sequence,
+                        stop_id: stop.stop_id.clone(),
+                        station: stop
+                            .stop_id
+                            .as_ref()
+                            .and_then(|stop_id| Station::from_gtfs_id(stop_id)),
+                        arrival: stop.arrival.as_ref().and_then(event_time),
+                        departure: stop.departure.as_ref().and_then(event_time),
+                        delay: stop
+                            .arrival
+                            .as_ref()
+                            .and_then(|arrival| arrival.delay)
+                            .or_else(|| {
+                                stop.departure
+                                    .as_ref()
+                                    .and_then(|departure| departure.delay)
+                            })
+                            .or(update.delay),
+                        skipped: stop.schedule_relationship()
+                            == proto::StopScheduleRelationship::Skipped,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Every alert in this feed.
+    pub fn alerts(&self) -> Vec<RealtimeAlert> {
+        self.entity
+            .iter()
+            .filter(|entity| !entity.is_deleted())
+            .filter_map(|entity| Some((&entity.id, entity.alert.as_ref()?)))
+            .map(|(id, alert)| {
+                let mut routes = vec![];
+                let mut lines = vec![];
+                let mut stations = vec![];
+                let mut stop_ids = vec![];
+
+                for informed in alert.informed_entity.iter() {
+                    if let Some(route_id) = &informed.route_id {
+                        if let Ok(route) = Route::from_str(route_id) {
+                            push_unique(&mut routes, route);
+                        } else if let Some(line) = line_from_route_id(route_id) {
+                            push_unique(&mut lines, line);
+                        }
+                    }
+
+                    if let Some(stop_id) = &informed.stop_id {
+                        match Station::from_gtfs_id(stop_id) {
+                            Some(station) => push_unique(&mut stations, station),
+                            None => push_unique(&mut stop_ids, stop_id.clone()),
+                        }
+                    }
+                }
+
+                RealtimeAlert {
+                    id: id.clone(),
+                    header: english(&alert.header_text),
+                    description: english(&alert.description_text),
+                    url: english(&alert.url),
+                    cause: alert.cause(),
+                    effect: alert.effect(),
+                    active_periods: alert
+                        .active_period
+                        .iter()
+                        .map(|period| ActivePeriod {
+                            start: period.start.and_then(|start| date_time(start as i64)),
+                            end: period.end.and_then(|end| date_time(end as i64)),
+                        })
+                        .collect(),
+                    routes,
+                    lines,
+                    stations,
+                    stop_ids,
+                }
+            })
+            .collect()
+    }
+
+    fn vehicles(&self) -> impl Iterator<Item = (&String, &proto::VehiclePosition)> {
+        self.entity
+            .iter()
+            .filter(|entity| !entity.is_deleted())
+            .filter_map(|entity| Some((&entity.id, entity.vehicle.as_ref()?)))
+    }
+}
+
+/// ID of a vehicle, falling back to the ID of its entity.
+fn vehicle_id(entity_id: &str, descriptor: Option<&proto::VehicleDescriptor>) -> String {
+    descriptor
+        .and_then(|descriptor| descriptor.id.clone())
+        .unwrap_or_else(|| entity_id.to_string())
+}
+
+fn route_of(trip: &TripDescriptor) -> Option<Route> {
+    trip.route_id
+        .as_ref()
+        .and_then(|route_id| Route::from_str(route_id).ok())
+}
+
+fn line_of(trip: &TripDescriptor) -> Option<Line> {
+    trip.route_id
+        .as_ref()
+        .and_then(|route_id| line_from_route_id(route_id))
+}
+
+/// [`Line`] of a MetroRail GTFS route ID, which is either a line code or the line's color.
+fn line_from_route_id(route_id: &str) -> Option<Line> {
+    Line::from_str(route_id)
+        .ok()
+        .or_else(|| match route_id.to_uppercase().as_str() {
+            "RED" => Some(Line::Red),
+            "BLUE" => Some(Line::Blue),
+            "YELLOW" => Some(Line::Yellow),
+            "ORANGE" => Some(Line::Orange),
+            "GREEN" => Some(Line::Green),
+            "SILVER" => Some(Line::Silver),
+            _ => None,
+        })
+}
+
+fn event_time(event: &StopTimeEvent) -> Option<DateTime<FixedOffset>> {
+    event.time.and_then(date_time)
+}
+
+/// Converts POSIX time to Eastern Standard Time.
+fn date_time(timestamp: i64) -> Option<DateTime<FixedOffset>> {
+    date::eastern().timestamp_opt(timestamp, 0).single()
+}
+
+fn english(text: &Option<proto::TranslatedString>) -> Option<String> {
+    text.as_ref()
+        .and_then(|text| text.english())
+        .map(|text| text.to_string())
+}
+
+fn push_unique<T>(values: &mut Vec<T>, value: T)
+where
+    T: PartialEq,
+{
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
@@ -0,0 +1,278 @@
+//! Encoding of the crate's live MetroBus and MetroRail data as GTFS Realtime feeds.
+mod tests;
+
+use super::proto::{
+    Alert, Cause, Effect, EntitySelector, FeedEntity, FeedHeader, FeedMessage, Incrementality,
+    Position, StopTimeEvent, StopTimeUpdate, TranslatedString, Translation, TripDescriptor,
+    TripUpdate, VehicleDescriptor, VehiclePosition,
+};
+use crate::{
+    bus::client::responses::{BusPositions, Incidents, Predictions},
+    rail::client::responses::RailIncidents,
+    Line, Stop,
+};
+use chrono::{DateTime, Duration, FixedOffset};
+use std::str::FromStr;
+
+/// Version of the GTFS Realtime specification produced.
+const GTFS_REALTIME_VERSION: &str = "2.0";
+
+/// Agency ID of WMATA, matching the GTFS static feeds built by this crate.
+const AGENCY_ID: &str = "WMATA";
+
+/// A type which can be converted into a GTFS Realtime [`FeedMessage`].
+pub trait ToFeedMessage {
+    /// Converts this response into a [`FeedMessage`].
+    fn to_feed_message(&self) -> FeedMessage;
+}
+
+impl ToFeedMessage for BusPositions {
+    /// A VehiclePositions feed, with a vehicle for each bus, timestamped with the latest position update.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::gtfs_rt::ToFeedMessage;
+    /// use wmata::bus::client::responses::BusPositions;
+    ///
+    /// let positions: BusPositions = serde_json::from_str(r#"{"BusPositions": []}"#).unwrap();
+    /// assert!(positions.to_feed_message().entity.is_empty());
+    /// ```
+    fn to_feed_message(&self) -> FeedMessage {
+        feed(
+            self.bus_positions
+                .iter()
+                .map(|position| position.date_time)
+                .max(),
+            self.bus_positions
+                .iter()
+                .map(|position| FeedEntity {
+                    vehicle: Some(VehiclePosition {
+                        trip: Some(TripDescriptor {
+                            trip_id: Some(position.trip_id.clone()),
+                            route_id: Some(position.route.to_string()),
+                            direction_id: direction_id(position.direction_number),
+                            start_time: Some(
+                                position.trip_start_time.format("%H:%M:%S").to_string(),
+                            ),
+                            start_date: Some(position.trip_start_time.format("%Y%m%d").to_string()),
+                            schedule_relationship: None,
+                        }),
+                        vehicle: Some(vehicle(&position.vehicle_id)),
+                        position: Some(Position {
+                            latitude: position.latitude as f32,
+                            longitude: position.longitude as f32,
+                            bearing: None,
+                            odometer: None,
+                            speed: None,
+                        }),
+                        current_stop_sequence: None,
+                        stop_id: None,
+                        current_status: None,
+                        timestamp: Some(position.date_time.timestamp() as u64),
+                        congestion_level: None,
+                        occupancy_status: None,
+                    }),
+                    ..entity(position.vehicle_id.clone())
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeedMessage for Incidents {
+    /// An Alerts feed, with an alert for each incident, informing the routes it affects.
+    fn to_feed_message(&self) -> FeedMessage {
+        feed(
+            self.incidents
+                .iter()
+                .map(|incident| incident.date_updated)
+                .max(),
+            self.incidents
+                .iter()
+                .map(|incident| FeedEntity {
+                    alert: Some(alert(
+                        &incident.incident_type,
+                        &incident.description,
+                        incident
+                            .routes_affected
+                            .iter()
+                            .map(|route| route.to_string())
+                            .collect(),
+                    )),
+                    ..entity(incident.incident_id.clone())
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToFeedMessage for RailIncidents {
+    /// An Alerts feed, with an alert for each incident, informing the lines it affects.
+    fn to_feed_message(&self) -> FeedMessage {
+        feed(
+            self.incidents
+                .iter()
+                .map(|incident| incident.date_updated)
+                .max(),
+            self.incidents
+                .iter()
+                .map(|incident| {
+                    let mut route_ids: Vec<String> = vec![];
+
+                    // Lines affected look like "BL; OR; RD;"
+                    for line in incident
+                        .lines_affected
+                        .split(';')
+                        .filter_map(|code| Line::from_str(code.trim()).ok())
+                    {
+                        let route_id = route_id(line).to_string();
+
+                        if !route_ids.contains(&route_id) {
+                            route_ids.push(route_id);
+                        }
+                    }
+
+                    FeedEntity {
+                        alert: Some(alert(
+                            &incident.incident_type,
+                            &incident.description,
+                            route_ids,
+                        )),
+                        ..entity(incident.incident_id.clone())
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Predictions {
+    /// A TripUpdates feed, with a trip for each predicted bus arriving at `stop`.
+    /// Predictions are in minutes, so arrival times are relative to `date_time`, when these predictions were fetched.
+    ///
+    /// Stops are identified by their WMATA stop ID, as used throughout this crate.
+    pub fn to_feed_message(&self, stop: &Stop, date_time: DateTime<FixedOffset>) -> FeedMessage {
+        feed(
+            Some(date_time),
+            self.predictions
+                .iter()
+                .map(|prediction| {
+                    let arrival = date_time + Duration::minutes(i64::from(prediction.minutes));
+
+                    FeedEntity {
+                        trip_update: Some(TripUpdate {
+                            trip: TripDescriptor {
+                                trip_id: Some(prediction.trip_id.clone()),
+                                route_id: Some(prediction.route.to_string()),
+                                direction_id: prediction.direction_number.parse().ok(),
+                                start_time: None,
+                                start_date: None,
+                                schedule_relationship: None,
+                            },
+                            vehicle: Some(vehicle(&prediction.vehicle_id)),
+                            stop_time_update: vec![StopTimeUpdate {
+                                stop_sequence: None,
+                                stop_id: Some(stop.0.clone()),
+                                arrival: Some(StopTimeEvent {
+                                    delay: None,
+                                    time: Some(arrival.timestamp()),
+                                    uncertainty: None,
+                                }),
+                                departure: None,
+                                schedule_relationship: None,
+                            }],
+                            timestamp: Some(date_time.timestamp() as u64),
+                            delay: None,
+                        }),
+                        ..entity(format!("{}_{}", prediction.trip_id, stop.0))
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+fn feed(date_time: Option<DateTime<FixedOffset>>, entity: Vec<FeedEntity>) -> FeedMessage {
+    FeedMessage {
+        header: FeedHeader {
+            gtfs_realtime_version: GTFS_REALTIME_VERSION.to_string(),
+            incrementality: Some(Incrementality::FullDataset as i32),
+            timestamp: date_time.map(|date_time| date_time.timestamp() as u64),
+        },
+        entity,
+    }
+}
+
+fn entity(id: String) -> FeedEntity {
+    FeedEntity {
+        id,
+        is_deleted: None,
+        trip_update: None,
+        vehicle: None,
+        alert: None,
+    }
+}
+
+fn vehicle(vehicle_id: &str) -> VehicleDescriptor {
+    VehicleDescriptor {
+        id: Some(vehicle_id.to_string()),
+        label: Some(vehicle_id.to_string()),
+        license_plate: None,
+    }
+}
+
+/// Directions are 0 or 1 in GTFS.
+fn direction_id(direction_number: i32) -> Option<u32> {
+    match direction_number {
+        0 | 1 => Some(direction_number as u32),
+        _ => None,
+    }
+}
+
+/// Route ID of a line in WMATA's MetroRail GTFS feeds.
+fn route_id(line: Line) -> &'static str {
+    match line {
+        Line::Red => "RED",
+        Line::Blue => "BLUE",
+        Line::Yellow | Line::YellowLineRushPlus => "YELLOW",
+        Line::Orange => "ORANGE",
+        Line::Green => "GREEN",
+        Line::Silver => "SILVER",
+    }
+}
+
+/// An alert from an incident, which is a delay or a general alert.
+fn alert(incident_type: &str, description: &str, route_ids: Vec<String>) -> Alert {
+    Alert {
+        active_period: vec![],
+        informed_entity: route_ids
+            .into_iter()
+            .map(|route_id| EntitySelector {
+                agency_id: Some(AGENCY_ID.to_string()),
+                route_id: Some(route_id),
+                route_type: None,
+                trip: None,
+                stop_id: None,
+                direction_id: None,
+            })
+            .collect(),
+        cause: Some(Cause::UnknownCause as i32),
+        effect: Some(if incident_type.eq_ignore_ascii_case("delay") {
+            Effect::SignificantDelays as i32
+        } else {
+            Effect::UnknownEffect as i32
+        }),
+        url: None,
+        header_text: Some(text(incident_type)),
+        description_text: Some(text(description)),
+    }
+}
+
+fn text(text: &str) -> TranslatedString {
+    TranslatedString {
+        translation: vec![Translation {
+            text: text.to_string(),
+            language: Some("en".to_string()),
+        }],
+    }
+}
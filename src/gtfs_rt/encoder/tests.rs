@@ -0,0 +1,197 @@
+//! Tests for GTFS Realtime encoding
+#[cfg(test)]
+use super::*;
+#[cfg(test)]
+use crate::{
+    gtfs_rt::{decode, encode},
+    Route,
+};
+
+#[cfg(test)]
+fn round_trip(feed: FeedMessage) -> FeedMessage {
+    decode(&encode(&feed)).unwrap()
+}
+
+#[test]
+fn test_bus_positions() {
+    let positions: BusPositions = serde_json::from_str(
+        r#"{
+            "BusPositions": [
+                {
+                    "DateTime": "2019-10-01T09:00:00",
+                    "Deviation": 2.0,
+                    "DirectionNum": 0,
+                    "DirectionText": "SOUTH",
+                    "Lat": 38.8626,
+                    "Lon": -76.995,
+                    "RouteID": "A2",
+                    "TripEndTime": "2019-10-01T09:40:00",
+                    "TripHeadsign": "ANACOSTIA",
+                    "TripID": "11225040",
+                    "TripStartTime": "2019-10-01T08:50:00",
+                    "VehicleID": "8032"
+                },
+                {
+                    "DateTime": "2019-10-01T09:00:30",
+                    "Deviation": 0.0,
+                    "DirectionNum": 1,
+                    "DirectionText": "NORTH",
+                    "Lat": 38.9,
+                    "Lon": -77.0,
+                    "RouteID": "A2",
+                    "TripEndTime": "2019-10-01T10:00:00",
+                    "TripHeadsign": "FEDERAL TRIANGLE",
+                    "TripID": "11225041",
+                    "TripStartTime": "2019-10-01T09:00:00",
+                    "VehicleID": "8033"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let feed = round_trip(positions.to_feed_message());
+    assert_eq!(
+        feed.date_time().unwrap().to_rfc3339(),
+        "2019-10-01T09:00:30-05:00"
+    );
+
+    let trip = feed.entity[0]
+        .vehicle
+        .as_ref()
+        .unwrap()
+        .trip
+        .as_ref()
+        .unwrap();
+    assert_eq!(trip.start_time, Some("08:50:00".to_string()));
+    assert_eq!(trip.start_date, Some("20191001".to_string()));
+
+    let buses = feed.bus_positions();
+    assert_eq!(buses.len(), 2);
+    assert_eq!(buses[0].vehicle_id, "8032");
+    assert_eq!(buses[0].route, Some(Route::A2));
+    assert_eq!(buses[0].trip_id, Some("11225040".to_string()));
+    assert_eq!(buses[0].direction_number, Some(0));
+    assert!((buses[0].latitude - 38.8626).abs() < 1e-5);
+    assert_eq!(
+        buses[0].date_time.unwrap().to_rfc3339(),
+        "2019-10-01T09:00:00-05:00"
+    );
+    assert_eq!(buses[1].direction_number, Some(1));
+}
+
+#[test]
+fn test_predictions() {
+    let predictions: Predictions = serde_json::from_str(
+        r#"{
+            "Predictions": [
+                {
+                    "DirectionNum": "0",
+                    "DirectionText": "South to Anacostia",
+                    "Minutes": 5,
+                    "RouteID": "A2",
+                    "TripID": "11225040",
+                    "VehicleID": "8032"
+                }
+            ],
+            "StopName": "FIRST ST"
+        }"#,
+    )
+    .unwrap();
+    let now = DateTime::parse_from_rfc3339("2019-10-01T09:00:00-05:00").unwrap();
+
+    let feed = round_trip(predictions.to_feed_message(&Stop::new("1001195"), now));
+    let trips = feed.trip_predictions();
+
+    assert_eq!(trips.len(), 1);
+    assert_eq!(trips[0].route, Some(Route::A2));
+    assert_eq!(trips[0].trip_id, Some("11225040".to_string()));
+    assert_eq!(trips[0].vehicle_id, Some("8032".to_string()));
+    assert_eq!(trips[0].direction_number, Some(0));
+    assert_eq!(trips[0].stops[0].stop_id, Some("1001195".to_string()));
+    assert_eq!(
+        trips[0].stops[0].arrival.unwrap().to_rfc3339(),
+        "2019-10-01T09:05:00-05:00"
+    );
+}
+
+#[test]
+fn test_bus_incidents() {
+    let incidents: Incidents = serde_json::from_str(
+        r#"{
+            "BusIncidents": [
+                {
+                    "DateUpdated": "2019-10-01T08:30:00",
+                    "Description": "Due to construction, A2 buses are detoured.",
+                    "IncidentID": "BUS1",
+                    "IncidentType": "Alert",
+                    "RoutesAffected": ["A2", "10A"]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let alerts = round_trip(incidents.to_feed_message()).alerts();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].id, "BUS1");
+    assert_eq!(alerts[0].header, Some("Alert".to_string()));
+    assert_eq!(
+        alerts[0].description,
+        Some("Due to construction, A2 buses are detoured.".to_string())
+    );
+    assert_eq!(alerts[0].routes, vec![Route::A2, Route::One0A]);
+    assert_eq!(alerts[0].effect, Effect::UnknownEffect);
+}
+
+#[test]
+fn test_rail_incidents() {
+    let incidents: RailIncidents = serde_json::from_str(
+        r#"{
+            "Incidents": [
+                {
+                    "IncidentID": "RAIL1",
+                    "Description": "Red Line: Delays in both directions.",
+                    "StartLocationFullName": null,
+                    "EndLocationFullName": null,
+                    "PassengerDelay": 0,
+                    "DelaySeverity": null,
+                    "IncidentType": "Delay",
+                    "EmergencyText": null,
+                    "LinesAffected": "RD; BL; YL; YLRP;",
+                    "DateUpdated": "2019-10-01T08:30:00"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let feed = round_trip(incidents.to_feed_message());
+    let alerts = feed.alerts();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].effect, Effect::SignificantDelays);
+    assert_eq!(alerts[0].lines, vec![Line::Red, Line::Blue, Line::Yellow]);
+    assert_eq!(
+        feed.entity[0].alert.as_ref().unwrap().informed_entity[0].agency_id,
+        Some(AGENCY_ID.to_string())
+    );
+}
+
+#[cfg(feature = "gtfs")]
+#[test]
+fn test_agency_id_matches_gtfs() {
+    // Realtime feeds refer to routes in the static feed by its agency
+    assert_eq!(AGENCY_ID, crate::gtfs::AGENCY_ID);
+}
+
+#[test]
+fn test_empty() {
+    let incidents: Incidents = serde_json::from_str(r#"{"BusIncidents": []}"#).unwrap();
+    let feed = round_trip(incidents.to_feed_message());
+
+    assert_eq!(feed.header.gtfs_realtime_version, GTFS_REALTIME_VERSION);
+    assert_eq!(feed.header.timestamp, None);
+    assert!(feed.entity.is_empty());
+}
@@ -0,0 +1,360 @@
+//! Messages of the [GTFS Realtime protocol](https://gtfs.org/realtime/reference/), as published by WMATA.
+//!
+//! Fields which WMATA does not publish, and experimental extensions, are left out and skipped when decoding.
+use prost::{Enumeration, Message};
+
+/// The contents of a feed.
+#[derive(Clone, PartialEq, Message)]
+pub struct FeedMessage {
+    /// Metadata about this feed.
+    #[prost(message, required, tag = "1")]
+    pub header: FeedHeader,
+    /// Contents of this feed.
+    #[prost(message, repeated, tag = "2")]
+    pub entity: Vec<FeedEntity>,
+}
+
+/// Metadata about a feed.
+#[derive(Clone, PartialEq, Message)]
+pub struct FeedHeader {
+    /// Version of the feed specification, e.g.: 2.0.
+    #[prost(string, required, tag = "1")]
+    pub gtfs_realtime_version: String,
+    /// Whether this feed is a full dataset or differential.
+    #[prost(
+        enumeration = "Incrementality",
+        optional,
+        tag = "2",
+        default = "FullDataset"
+    )]
+    pub incrementality: Option<i32>,
+    /// When this feed was created, in POSIX time.
+    #[prost(uint64, optional, tag = "3")]
+    pub timestamp: Option<u64>,
+}
+
+/// Whether a feed contains every entity, or only those which changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum Incrementality {
+    FullDataset = 0,
+    Differential = 1,
+}
+
+/// A single entity of a feed. Exactly one of `trip_update`, `vehicle` and `alert` is expected.
+#[derive(Clone, PartialEq, Message)]
+pub struct FeedEntity {
+    /// Unique identifier of this entity within the feed.
+    #[prost(string, required, tag = "1")]
+    pub id: String,
+    /// Whether this entity is to be deleted. Only used in differential feeds.
+    #[prost(bool, optional, tag = "2", default = "false")]
+    pub is_deleted: Option<bool>,
+    #[prost(message, optional, tag = "3")]
+    pub trip_update: Option<TripUpdate>,
+    #[prost(message, optional, tag = "4")]
+    pub vehicle: Option<VehiclePosition>,
+    #[prost(message, optional, tag = "5")]
+    pub alert: Option<Alert>,
+}
+
+/// Realtime progress of a trip.
+#[derive(Clone, PartialEq, Message)]
+pub struct TripUpdate {
+    #[prost(message, required, tag = "1")]
+    pub trip: TripDescriptor,
+    #[prost(message, optional, tag = "3")]
+    pub vehicle: Option<VehicleDescriptor>,
+    /// Predictions for stops along this trip, ordered by stop sequence.
+    #[prost(message, repeated, tag = "2")]
+    pub stop_time_update: Vec<StopTimeUpdate>,
+    /// When this prediction was last measured, in POSIX time.
+    #[prost(uint64, optional, tag = "4")]
+    pub timestamp: Option<u64>,
+    /// Current deviation from schedule, in seconds.
+    #[prost(int32, optional, tag = "5")]
+    pub delay: Option<i32>,
+}
+
+/// Timing of an arrival or departure. Either `delay` or `time` is expected.
+#[derive(Clone, PartialEq, Message)]
+pub struct StopTimeEvent {
+    /// Deviation from schedule, in seconds.
+    #[prost(int32, optional, tag = "1")]
+    pub delay: Option<i32>,
+    /// Predicted time, in POSIX time.
+    #[prost(int64, optional, tag = "2")]
+    pub time: Option<i64>,
+    /// Expected error of this prediction, in seconds.
+    #[prost(int32, optional, tag = "3")]
+    pub uncertainty: Option<i32>,
+}
+
+/// Prediction for a single stop of a trip.
+#[derive(Clone, PartialEq, Message)]
+pub struct StopTimeUpdate {
+    #[prost(uint32, optional, tag = "1")]
+    pub stop_sequence: Option<u32>,
+    #[prost(string, optional, tag = "4")]
+    pub stop_id: Option<String>,
+    #[prost(message, optional, tag = "2")]
+    pub arrival: Option<StopTimeEvent>,
+    #[prost(message, optional, tag = "3")]
+    pub departure: Option<StopTimeEvent>,
+    #[prost(
+        enumeration = "StopScheduleRelationship",
+        optional,
+        tag = "5",
+        default = "Scheduled"
+    )]
+    pub schedule_relationship: Option<i32>,
+}
+
+/// Relationship between a stop of a trip and its schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum StopScheduleRelationship {
+    Scheduled = 0,
+    Skipped = 1,
+    NoData = 2,
+    Unscheduled = 3,
+}
+
+/// Realtime position of a vehicle.
+#[derive(Clone, PartialEq, Message)]
+pub struct VehiclePosition {
+    #[prost(message, optional, tag = "1")]
+    pub trip: Option<TripDescriptor>,
+    #[prost(message, optional, tag = "8")]
+    pub vehicle: Option<VehicleDescriptor>,
+    #[prost(message, optional, tag = "2")]
+    pub position: Option<Position>,
+    #[prost(uint32, optional, tag = "3")]
+    pub current_stop_sequence: Option<u32>,
+    #[prost(string, optional, tag = "7")]
+    pub stop_id: Option<String>,
+    #[prost(
+        enumeration = "VehicleStopStatus",
+        optional,
+        tag = "4",
+        default = "InTransitTo"
+    )]
+    pub current_status: Option<i32>,
+    /// When this position was measured, in POSIX time.
+    #[prost(uint64, optional, tag = "5")]
+    pub timestamp: Option<u64>,
+    #[prost(enumeration = "CongestionLevel", optional, tag = "6")]
+    pub congestion_level: Option<i32>,
+    #[prost(enumeration = "OccupancyStatus", optional, tag = "9")]
+    pub occupancy_status: Option<i32>,
+}
+
+/// Where a vehicle is relative to its current stop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum VehicleStopStatus {
+    IncomingAt = 0,
+    StoppedAt = 1,
+    InTransitTo = 2,
+}
+
+/// Traffic congestion affecting a vehicle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum CongestionLevel {
+    UnknownCongestionLevel = 0,
+    RunningSmoothly = 1,
+    StopAndGo = 2,
+    Congestion = 3,
+    SevereCongestion = 4,
+}
+
+/// How full a vehicle is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum OccupancyStatus {
+    Empty = 0,
+    ManySeatsAvailable = 1,
+    FewSeatsAvailable = 2,
+    StandingRoomOnly = 3,
+    CrushedStandingRoomOnly = 4,
+    Full = 5,
+    NotAcceptingPassengers = 6,
+    NoDataAvailable = 7,
+    NotBoardable = 8,
+}
+
+/// A service alert.
+#[derive(Clone, PartialEq, Message)]
+pub struct Alert {
+    /// When this alert is shown. Shown forever when empty.
+    #[prost(message, repeated, tag = "1")]
+    pub active_period: Vec<TimeRange>,
+    /// Routes, stops and trips affected by this alert.
+    #[prost(message, repeated, tag = "5")]
+    pub informed_entity: Vec<EntitySelector>,
+    #[prost(enumeration = "Cause", optional, tag = "6", default = "UnknownCause")]
+    pub cause: Option<i32>,
+    #[prost(enumeration = "Effect", optional, tag = "7", default = "UnknownEffect")]
+    pub effect: Option<i32>,
+    #[prost(message, optional, tag = "8")]
+    pub url: Option<TranslatedString>,
+    #[prost(message, optional, tag = "10")]
+    pub header_text: Option<TranslatedString>,
+    #[prost(message, optional, tag = "11")]
+    pub description_text: Option<TranslatedString>,
+}
+
+/// Cause of an alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum Cause {
+    UnknownCause = 1,
+    OtherCause = 2,
+    TechnicalProblem = 3,
+    Strike = 4,
+    Demonstration = 5,
+    Accident = 6,
+    Holiday = 7,
+    Weather = 8,
+    Maintenance = 9,
+    Construction = 10,
+    PoliceActivity = 11,
+    MedicalEmergency = 12,
+}
+
+/// Effect of an alert on service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum Effect {
+    NoService = 1,
+    ReducedService = 2,
+    SignificantDelays = 3,
+    Detour = 4,
+    AdditionalService = 5,
+    ModifiedService = 6,
+    OtherEffect = 7,
+    UnknownEffect = 8,
+    StopMoved = 9,
+    NoEffect = 10,
+    AccessibilityIssue = 11,
+}
+
+/// A span of time, in POSIX time. A missing start or end is open ended.
+#[derive(Clone, PartialEq, Message)]
+pub struct TimeRange {
+    #[prost(uint64, optional, tag = "1")]
+    pub start: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub end: Option<u64>,
+}
+
+/// A geographic position of a vehicle.
+#[derive(Clone, PartialEq, Message)]
+pub struct Position {
+    #[prost(float, required, tag = "1")]
+    pub latitude: f32,
+    #[prost(float, required, tag = "2")]
+    pub longitude: f32,
+    /// Degrees clockwise from true north.
+    #[prost(float, optional, tag = "3")]
+    pub bearing: Option<f32>,
+    /// Meters.
+    #[prost(double, optional, tag = "4")]
+    pub odometer: Option<f64>,
+    /// Meters per second.
+    #[prost(float, optional, tag = "5")]
+    pub speed: Option<f32>,
+}
+
+/// Identifies a trip in the static schedule.
+#[derive(Clone, PartialEq, Message)]
+pub struct TripDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub trip_id: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub route_id: Option<String>,
+    #[prost(uint32, optional, tag = "6")]
+    pub direction_id: Option<u32>,
+    /// Scheduled start of this trip, e.g.: 25:15:00.
+    #[prost(string, optional, tag = "2")]
+    pub start_time: Option<String>,
+    /// Service date of this trip, e.g.: 20191001.
+    #[prost(string, optional, tag = "3")]
+    pub start_date: Option<String>,
+    #[prost(enumeration = "TripScheduleRelationship", optional, tag = "4")]
+    pub schedule_relationship: Option<i32>,
+}
+
+/// Relationship between a trip and the static schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Enumeration)]
+#[repr(i32)]
+pub enum TripScheduleRelationship {
+    Scheduled = 0,
+    Added = 1,
+    Unscheduled = 2,
+    Canceled = 3,
+    Duplicated = 6,
+    Deleted = 7,
+}
+
+/// Identifies a vehicle.
+#[derive(Clone, PartialEq, Message)]
+pub struct VehicleDescriptor {
+    /// Internal identifier of this vehicle.
+    #[prost(string, optional, tag = "1")]
+    pub id: Option<String>,
+    /// Identifier shown to riders, e.g.: a train number.
+    #[prost(string, optional, tag = "2")]
+    pub label: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub license_plate: Option<String>,
+}
+
+/// Selects the entities affected by an alert.
+#[derive(Clone, PartialEq, Message)]
+pub struct EntitySelector {
+    #[prost(string, optional, tag = "1")]
+    pub agency_id: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub route_id: Option<String>,
+    #[prost(int32, optional, tag = "3")]
+    pub route_type: Option<i32>,
+    #[prost(message, optional, tag = "4")]
+    pub trip: Option<TripDescriptor>,
+    #[prost(string, optional, tag = "5")]
+    pub stop_id: Option<String>,
+    #[prost(uint32, optional, tag = "6")]
+    pub direction_id: Option<u32>,
+}
+
+/// Text in one or more languages.
+#[derive(Clone, PartialEq, Message)]
+pub struct TranslatedString {
+    #[prost(message, repeated, tag = "1")]
+    pub translation: Vec<Translation>,
+}
+
+/// Text in a single language.
+#[derive(Clone, PartialEq, Message)]
+pub struct Translation {
+    #[prost(string, required, tag = "1")]
+    pub text: String,
+    /// BCP-47 language code. Missing when there is only one language.
+    #[prost(string, optional, tag = "2")]
+    pub language: Option<String>,
+}
+
+impl TranslatedString {
+    /// Text in English, or without a language, falling back to the first translation.
+    pub fn english(&self) -> Option<&str> {
+        self.translation
+            .iter()
+            .find(|translation| match &translation.language {
+                Some(language) => language.starts_with("en"),
+                None => true,
+            })
+            .or_else(|| self.translation.first())
+            .map(|translation| translation.text.as_str())
+    }
+}
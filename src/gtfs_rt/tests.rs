@@ -0,0 +1,167 @@
+//! Tests for GTFS Realtime feeds
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{retry::RetryPolicy, testing::FakeServer, MetroBus};
+#[cfg(test)]
+use std::time::Duration;
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+fn fixture(bytes: &[u8]) -> FeedMessage {
+    decode(bytes).unwrap()
+}
+
+#[test]
+fn test_decode_invalid() {
+    assert!(decode(&[0xff, 0xff, 0xff]).is_err());
+}
+
+#[test]
+fn test_header() {
+    let feed = fixture(include_bytes!("fixtures/bus-vehiclepositions.pb"));
+
+    assert_eq!(feed.header.gtfs_realtime_version, "2.0");
+    assert_eq!(
+        feed.date_time().unwrap().to_rfc3339(),
+        "2019-10-01T09:00:10-05:00"
+    );
+}
+
+#[test]
+fn test_bus_positions() {
+    let positions = fixture(include_bytes!("fixtures/bus-vehiclepositions.pb")).bus_positions();
+
+    // Vehicles without a position are left out
+    assert_eq!(positions.len(), 2);
+
+    let bus = &positions[0];
+    assert_eq!(bus.vehicle_id, "8032");
+    assert_eq!(bus.route, Some(Route::A2));
+    assert_eq!(bus.trip_id, Some("11225040".to_string()));
+    assert_eq!(bus.direction_number, Some(0));
+    assert_eq!(bus.stop_id, Some("13565".to_string()));
+    assert_eq!(bus.status, VehicleStopStatus::StoppedAt);
+    assert_eq!(bus.speed, Some(8.5));
+    assert!((bus.latitude - 38.8626).abs() < 1e-5);
+    assert_eq!(
+        bus.date_time.unwrap().to_rfc3339(),
+        "2019-10-01T09:00:00-05:00"
+    );
+
+    assert_eq!(positions[1].route, None);
+    assert_eq!(positions[1].status, VehicleStopStatus::InTransitTo);
+    assert_eq!(positions[1].date_time, None);
+}
+
+#[test]
+fn test_train_positions() {
+    let positions = fixture(include_bytes!("fixtures/rail-vehiclepositions.pb")).train_positions();
+
+    assert_eq!(positions.len(), 1);
+
+    let train = &positions[0];
+    assert_eq!(train.train_id, "201");
+    assert_eq!(train.train_number, Some("104".to_string()));
+    assert_eq!(train.line, Some(Line::Red));
+    assert_eq!(train.station, Some(Station::A01));
+    assert_eq!(train.status, VehicleStopStatus::IncomingAt);
+}
+
+#[test]
+fn test_trip_predictions() {
+    let predictions = fixture(include_bytes!("fixtures/rail-tripupdates.pb")).trip_predictions();
+
+    assert_eq!(predictions.len(), 2);
+
+    let trip = &predictions[0];
+    assert_eq!(trip.line, Some(Line::Red));
+    assert_eq!(trip.route, None);
+    assert_eq!(trip.vehicle_id, Some("201".to_string()));
+    assert!(!trip.canceled);
+    assert_eq!(trip.stops.len(), 2);
+    assert_eq!(trip.stops[0].station, Some(Station::A01));
+    assert_eq!(trip.stops[0].delay, Some(60));
+    assert_eq!(
+        trip.stops[0].arrival.unwrap().to_rfc3339(),
+        "2019-10-01T09:01:00-05:00"
+    );
+    assert_eq!(
+        trip.stops[0].departure.unwrap().to_rfc3339(),
+        "2019-10-01T09:01:30-05:00"
+    );
+    assert!(!trip.stops[0].skipped);
+
+    // Stops without their own delay fall back to the trip's
+    assert_eq!(trip.stops[1].station, Some(Station::A02));
+    assert_eq!(trip.stops[1].arrival, None);
+    assert_eq!(trip.stops[1].delay, Some(60));
+    assert!(trip.stops[1].skipped);
+
+    assert_eq!(predictions[1].line, Some(Line::Blue));
+    assert!(predictions[1].canceled);
+}
+
+#[test]
+fn test_alerts() {
+    let alerts = fixture(include_bytes!("fixtures/alerts.pb")).alerts();
+
+    // Deleted entities are left out
+    assert_eq!(alerts.len(), 1);
+
+    let alert = &alerts[0];
+    assert_eq!(alert.id, "A1");
+    assert_eq!(alert.header, Some("Red Line delays".to_string()));
+    assert_eq!(
+        alert.description,
+        Some("Red Line trains are single tracking at Metro Center.".to_string())
+    );
+    assert_eq!(alert.url, None);
+    assert_eq!(alert.cause, Cause::Maintenance);
+    assert_eq!(alert.effect, Effect::SignificantDelays);
+    assert_eq!(alert.lines, vec![Line::Red]);
+    assert_eq!(alert.routes, vec![Route::A2]);
+    assert_eq!(alert.stations, vec![Station::A01]);
+    assert_eq!(alert.stop_ids, vec!["13565".to_string()]);
+    assert_eq!(alert.active_periods.len(), 1);
+    assert_eq!(
+        alert.active_periods[0].start.unwrap().to_rfc3339(),
+        "2019-10-01T09:00:00-05:00"
+    );
+    assert_eq!(alert.active_periods[0].end, None);
+}
+
+#[test]
+fn test_line_from_route_id() {
+    assert_eq!(line_from_route_id("SV"), Some(Line::Silver));
+    assert_eq!(line_from_route_id("ORANGE"), Some(Line::Orange));
+    assert_eq!(line_from_route_id("A2"), None);
+}
+
+#[test]
+fn test_feed_urls() {
+    assert_eq!(
+        RealtimeFeed::RailAlerts.to_string(),
+        "https://api.wmata.com/gtfs/rail-gtfsrt-alerts.pb"
+    );
+}
+
+#[test]
+fn test_fetch_through_client() {
+    let server = FakeServer::start();
+    let client = MetroBus::builder(server.api_key())
+        .base_url(&server.url())
+        .retry(RetryPolicy::new(1, Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    // The fake server has no GTFS Realtime feeds
+    assert!(block_on(async { client.realtime(RealtimeFeed::BusAlerts).await }).is_err());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/gtfs/bus-gtfsrt-alerts.pb");
+    assert_eq!(requests[0].api_key.as_deref(), Some(server.api_key()));
+}
@@ -0,0 +1,435 @@
+//! iCalendar ([RFC 5545](https://tools.ietf.org/html/rfc5545)) export of bus stop schedules and MetroRail first and last trains.
+mod tests;
+
+use crate::{
+    bus::client::responses::StopSchedule,
+    error::Error,
+    rail::client::responses::{StationFirstLastTrains, StationTimings},
+    Date, Route, Station,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use std::fmt;
+
+/// Time zone of every event. WMATA's times are local to Washington, DC.
+pub const TIMEZONE: &str = "America/New_York";
+
+/// Maximum length of a content line, in octets, before it is folded.
+const MAXIMUM_LINE_LENGTH: usize = 75;
+
+/// Definition of [`TIMEZONE`], with the daylight saving rules in effect since 2007.
+const VTIMEZONE: &[&str] = &[
+    "BEGIN:VTIMEZONE",
+    "TZID:America/New_York",
+    "BEGIN:DAYLIGHT",
+    "TZOFFSETFROM:-0500",
+    "TZOFFSETTO:-0400",
+    "TZNAME:EDT",
+    "DTSTART:20070311T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU",
+    "END:DAYLIGHT",
+    "BEGIN:STANDARD",
+    "TZOFFSETFROM:-0400",
+    "TZOFFSETTO:-0500",
+    "TZNAME:EST",
+    "DTSTART:20071104T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+/// An iCalendar of events, written out as an `.ics` file with [`fmt::Display`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    /// Name shown by calendar apps.
+    pub name: String,
+    /// When this calendar was created.
+    pub created: DateTime<Utc>,
+    /// See [`Event`].
+    pub events: Vec<Event>,
+}
+
+/// A single event, or a series of events with a [`Recurrence`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Globally unique identifier of this event.
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// Local time in [`TIMEZONE`].
+    pub start: NaiveDateTime,
+    pub duration: Duration,
+    pub recurrence: Option<Recurrence>,
+}
+
+/// Weekly recurrence of an event on some days of the week, optionally until a date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub days: Vec<Weekday>,
+    /// Last date (inclusive) the event recurs on.
+    pub until: Option<NaiveDate>,
+}
+
+impl Calendar {
+    /// Constructor for an empty calendar.
+    pub fn new(name: &str) -> Self {
+        Calendar {
+            name: name.to_string(),
+            created: Utc::now(),
+            events: vec![],
+        }
+    }
+}
+
+impl Recurrence {
+    /// Constructor for a recurrence on the given days of the week.
+    pub fn new(days: Vec<Weekday>) -> Self {
+        Recurrence { days, until: None }
+    }
+
+    /// Monday through Friday.
+    pub fn weekdays() -> Self {
+        Recurrence::new(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ])
+    }
+
+    /// Saturday and Sunday.
+    pub fn weekends() -> Self {
+        Recurrence::new(vec![Weekday::Sat, Weekday::Sun])
+    }
+
+    /// Stops this recurrence after a date.
+    pub fn until(mut self, date: NaiveDate) -> Self {
+        self.until = Some(date);
+        self
+    }
+}
+
+impl fmt::Display for Recurrence {
+    /// An RRULE value, e.g.: `FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days: Vec<&str> = self.days.iter().map(|day| day_code(*day)).collect();
+
+        write!(f, "FREQ=WEEKLY;BYDAY={}", days.join(","))?;
+
+        // UNTIL must be in UTC when DTSTART has a TZID
+        if let Some(until) = self.until {
+            let end_of_day = until.and_hms_opt(23, 59, 59).ok_or(fmt::Error)?;
+
+            write!(
+                f,
+                ";UNTIL={}",
+                (end_of_day - utc_offset(until)).format("%Y%m%dT%H%M%SZ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Calendar {
+    /// Writes this calendar in iCalendar format, with CRLF line endings and folded lines.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:-//wmata//wmata {}//EN", env!("CARGO_PKG_VERSION")),
+            "CALSCALE:GREGORIAN".to_string(),
+            format!("X-WR-CALNAME:{}", escape(&self.name)),
+            format!("X-WR-TIMEZONE:{}", TIMEZONE),
+        ];
+
+        lines.extend(VTIMEZONE.iter().map(|line| line.to_string()));
+
+        let stamp = self.created.format("%Y%m%dT%H%M%SZ").to_string();
+
+        for event in self.events.iter() {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", event.uid));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            lines.push(format!(
+                "DTSTART;TZID={}:{}",
+                TIMEZONE,
+                event.start.format("%Y%m%dT%H%M%S")
+            ));
+            lines.push(format!("DURATION:{}", duration(event.duration)));
+
+            if let Some(recurrence) = &event.recurrence {
+                lines.push(format!("RRULE:{}", recurrence));
+            }
+
+            lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+
+            if let Some(description) = &event.description {
+                lines.push(format!("DESCRIPTION:{}", escape(description)));
+            }
+
+            if let Some(location) = &event.location {
+                lines.push(format!("LOCATION:{}", escape(location)));
+            }
+
+            lines.push("END:VEVENT".to_string());
+        }
+
+        lines.push("END:VCALENDAR".to_string());
+
+        for line in lines {
+            write!(f, "{}\r\n", fold(&line))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StopSchedule {
+    /// An event for each scheduled arrival at this stop, optionally only for a route and direction number.
+    /// Filtering by a route includes its variants, e.g.: 10Av1 for 10A.
+    ///
+    /// Each event repeats with `recurrence`, e.g.: [`Recurrence::weekdays`] for a weekday schedule.
+    pub fn to_calendar(
+        &self,
+        route: Option<Route>,
+        direction_number: Option<&str>,
+        recurrence: Option<&Recurrence>,
+    ) -> Calendar {
+        let mut calendar = Calendar::new(&self.stop.name);
+        let stop = match &self.stop.stop {
+            Some(stop) => stop.0.clone(),
+            None => self.stop.name.clone(),
+        };
+
+        calendar.events = self
+            .arrivals
+            .iter()
+            .filter(|arrival| match route {
+                Some(route) => {
+                    arrival.route == route
+                        || arrival
+                            .route
+                            .to_string()
+                            .starts_with(&format!("{}v", route.to_string()))
+                }
+                None => true,
+            })
+            .filter(|arrival| match direction_number {
+                Some(direction_number) => arrival.direction_number == direction_number,
+                None => true,
+            })
+            .map(|arrival| {
+                let route = arrival.route.to_string();
+                let start = arrival.schedule_time.naive_local();
+
+                Event {
+                    uid: format!(
+                        "{}-{}-{}@wmata",
+                        arrival.trip_id,
+                        stop,
+                        start.format("%Y%m%d")
+                    ),
+                    summary: format!(
+                        "{} to {}",
+                        route.split('v').next().unwrap_or(&route),
+                        arrival.trip_headsign
+                    ),
+                    description: Some(format!(
+                        "Route {}, {}. Trip {}.",
+                        route, arrival.trip_direction_text, arrival.trip_id
+                    )),
+                    location: Some(self.stop.name.clone()),
+                    start,
+                    duration: Duration::minutes(1),
+                    recurrence: recurrence.cloned(),
+                }
+            })
+            .collect();
+
+        calendar
+    }
+}
+
+impl StationTimings {
+    /// An event for each first and last train at these stations, starting on a date.
+    /// Trains leaving at the same time on several days of the week are a single recurring event.
+    pub fn to_calendar(&self, starting: Date) -> Result<Calendar, Error> {
+        let starting = NaiveDate::from_ymd_opt(
+            i32::from(starting.year),
+            u32::from(starting.month),
+            u32::from(starting.day),
+        )
+        .ok_or_else(|| Error::new(format!("{} is not a valid date.", starting.to_string())))?;
+
+        let mut calendar = Calendar::new("MetroRail first and last trains");
+
+        for station_time in self.station_times.iter() {
+            let days = [
+                (Weekday::Mon, &station_time.monday),
+                (Weekday::Tue, &station_time.tuesday),
+                (Weekday::Wed, &station_time.wednesday),
+                (Weekday::Thu, &station_time.thursday),
+                (Weekday::Fri, &station_time.friday),
+                (Weekday::Sat, &station_time.saturday),
+                (Weekday::Sun, &station_time.sunday),
+            ];
+
+            let mut trains: Vec<(Train, Vec<Weekday>)> = vec![];
+
+            for (day, timings) in days.iter() {
+                for train in Train::all(*day, timings)? {
+                    match trains.iter_mut().find(|(other, _)| *other == train.0) {
+                        Some((_, days)) => days.push(train.1),
+                        None => trains.push((train.0, vec![train.1])),
+                    }
+                }
+            }
+
+            for (train, days) in trains {
+                let mut date = starting;
+
+                while !days.contains(&date.weekday()) {
+                    date = date.succ_opt().ok_or_else(|| {
+                        Error::new(format!("No date after {} is available.", date))
+                    })?;
+                }
+
+                let kind = if train.first { "First" } else { "Last" };
+                let day_codes: Vec<&str> = days.iter().map(|day| day_code(*day)).collect();
+
+                calendar.events.push(Event {
+                    uid: format!(
+                        "{}-{}-{}-{}-{}@wmata",
+                        station_time.station.to_string(),
+                        kind.to_lowercase(),
+                        train.destination.to_string(),
+                        train.time.format("%H%M"),
+                        day_codes.join("")
+                    ),
+                    summary: format!("{} train to {}", kind, train.destination.name()),
+                    description: Some(format!(
+                        "{} train from {} to {}.",
+                        kind,
+                        station_time.station_name,
+                        train.destination.name()
+                    )),
+                    location: Some(station_time.station_name.clone()),
+                    start: date.and_time(train.time),
+                    duration: Duration::minutes(1),
+                    recurrence: Some(Recurrence::new(days)),
+                });
+            }
+        }
+
+        Ok(calendar)
+    }
+}
+
+/// A first or last train, on the day of the week it actually leaves.
+#[derive(Debug, PartialEq)]
+struct Train {
+    first: bool,
+    destination: Station,
+    time: NaiveTime,
+}
+
+impl Train {
+    /// Every first and last train of a day. Trains leaving before the station opens leave after midnight, on the next day.
+    fn all(day: Weekday, timings: &StationFirstLastTrains) -> Result<Vec<(Train, Weekday)>, Error> {
+        let opening_time = parse_time(&timings.opening_time)?;
+
+        let first_trains = timings.first_trains.iter().map(|train| (true, train));
+        let last_trains = timings.last_trains.iter().map(|train| (false, train));
+
+        first_trains
+            .chain(last_trains)
+            .map(|(first, train)| {
+                let time = parse_time(&train.time)?;
+                let day = if !first && time < opening_time {
+                    day.succ()
+                } else {
+                    day
+                };
+
+                Ok((
+                    Train {
+                        first,
+                        destination: train.destination,
+                        time,
+                    },
+                    day,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Parses a time in WMATA's HH:mm format.
+fn parse_time(time: &str) -> Result<NaiveTime, Error> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| Error::new(format!("{} is not a valid time.", time)))
+}
+
+/// Offset from UTC of [`TIMEZONE`] at the end of a date, following the rules in [`VTIMEZONE`].
+fn utc_offset(date: NaiveDate) -> Duration {
+    let daylight_start = NaiveDate::from_weekday_of_month_opt(date.year(), 3, Weekday::Sun, 2);
+    let daylight_end = NaiveDate::from_weekday_of_month_opt(date.year(), 11, Weekday::Sun, 1);
+
+    match (daylight_start, daylight_end) {
+        (Some(start), Some(end)) if start <= date && date < end => Duration::hours(-4),
+        _ => Duration::hours(-5),
+    }
+}
+
+fn day_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// A DURATION value, e.g.: `PT1M`.
+fn duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+
+    if seconds % 60 == 0 {
+        format!("PT{}M", seconds / 60)
+    } else {
+        format!("PT{}S", seconds)
+    }
+}
+
+/// Escapes a TEXT value.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line longer than [`MAXIMUM_LINE_LENGTH`] octets, without splitting characters.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+
+    for character in line.chars() {
+        if length + character.len_utf8() > MAXIMUM_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the length of the line
+            length = 1;
+        }
+
+        folded.push(character);
+        length += character.len_utf8();
+    }
+
+    folded
+}
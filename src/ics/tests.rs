@@ -0,0 +1,253 @@
+//! Tests for iCalendar export
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn stop_schedule() -> StopSchedule {
+    serde_json::from_str(
+        r#"{
+            "ScheduleArrivals": [
+                {
+                    "ScheduleTime": "2019-10-01T09:05:00",
+                    "DirectionNum": "0",
+                    "StartTime": "2019-10-01T08:50:00",
+                    "EndTime": "2019-10-01T09:40:00",
+                    "RouteID": "A2",
+                    "TripDirectionText": "SOUTH",
+                    "TripHeadsign": "ANACOSTIA",
+                    "TripID": "100"
+                },
+                {
+                    "ScheduleTime": "2019-10-01T09:15:00",
+                    "DirectionNum": "0",
+                    "StartTime": "2019-10-01T09:00:00",
+                    "EndTime": "2019-10-01T09:50:00",
+                    "RouteID": "A2v1",
+                    "TripDirectionText": "SOUTH",
+                    "TripHeadsign": "ANACOSTIA, VIA MLK",
+                    "TripID": "101"
+                },
+                {
+                    "ScheduleTime": "2019-10-01T09:20:00",
+                    "DirectionNum": "1",
+                    "StartTime": "2019-10-01T09:10:00",
+                    "EndTime": "2019-10-01T10:00:00",
+                    "RouteID": "A2",
+                    "TripDirectionText": "NORTH",
+                    "TripHeadsign": "FEDERAL TRIANGLE",
+                    "TripID": "200"
+                },
+                {
+                    "ScheduleTime": "2019-10-01T09:30:00",
+                    "DirectionNum": "0",
+                    "StartTime": "2019-10-01T09:10:00",
+                    "EndTime": "2019-10-01T10:00:00",
+                    "RouteID": "A6",
+                    "TripDirectionText": "SOUTH",
+                    "TripHeadsign": "SOUTHERN AVE",
+                    "TripID": "300"
+                }
+            ],
+            "Stop": {
+                "StopID": "1001195",
+                "Name": "MLK JR AVE SE + GOOD HOPE RD SE",
+                "Lat": 38.86,
+                "Lon": -76.99,
+                "Routes": ["A2", "A6"]
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+fn station_timings() -> StationTimings {
+    let weekday = r#"{
+        "OpeningTime": "05:00",
+        "FirstTrains": [{"Time": "05:14", "DestinationStation": "A15"}],
+        "LastTrains": [{"Time": "23:48", "DestinationStation": "A15"}]
+    }"#;
+    let late_night = r#"{
+        "OpeningTime": "07:00",
+        "FirstTrains": [{"Time": "07:14", "DestinationStation": "A15"}],
+        "LastTrains": [{"Time": "00:48", "DestinationStation": "A15"}]
+    }"#;
+
+    serde_json::from_str(&format!(
+        r#"{{
+            "StationTimes": [{{
+                "Code": "A01",
+                "StationName": "Metro Center",
+                "Monday": {weekday},
+                "Tuesday": {weekday},
+                "Wednesday": {weekday},
+                "Thursday": {weekday},
+                "Friday": {late_night},
+                "Saturday": {late_night},
+                "Sunday": {weekday}
+            }}]
+        }}"#,
+        weekday = weekday,
+        late_night = late_night
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_stop_schedule() {
+    let calendar = stop_schedule().to_calendar(None, None, None);
+
+    assert_eq!(calendar.name, "MLK JR AVE SE + GOOD HOPE RD SE");
+    assert_eq!(calendar.events.len(), 4);
+
+    let event = &calendar.events[0];
+    assert_eq!(event.summary, "A2 to ANACOSTIA");
+    assert_eq!(event.uid, "100-1001195-20191001@wmata");
+    assert_eq!(
+        event.start,
+        NaiveDate::from_ymd_opt(2019, 10, 1)
+            .unwrap()
+            .and_hms_opt(9, 5, 0)
+            .unwrap()
+    );
+    assert_eq!(event.recurrence, None);
+
+    // Variants are shown as their base route
+    assert_eq!(calendar.events[1].summary, "A2 to ANACOSTIA, VIA MLK");
+}
+
+#[test]
+fn test_stop_schedule_filtered() {
+    let schedule = stop_schedule();
+
+    let route = schedule.to_calendar(Some(Route::A2), None, None);
+    assert_eq!(
+        route
+            .events
+            .iter()
+            .map(|event| event.uid.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "100-1001195-20191001@wmata",
+            "101-1001195-20191001@wmata",
+            "200-1001195-20191001@wmata"
+        ]
+    );
+
+    let recurrence = Recurrence::weekdays();
+    let direction = schedule.to_calendar(Some(Route::A2), Some("1"), Some(&recurrence));
+    assert_eq!(direction.events.len(), 1);
+    assert_eq!(direction.events[0].summary, "A2 to FEDERAL TRIANGLE");
+    assert_eq!(direction.events[0].recurrence, Some(recurrence));
+}
+
+#[test]
+fn test_station_timings() {
+    let calendar = station_timings()
+        .to_calendar(Date::new(2019, 10, 2))
+        .unwrap();
+
+    let summary: Vec<(&str, String, Option<String>)> = calendar
+        .events
+        .iter()
+        .map(|event| {
+            (
+                event.summary.as_str(),
+                event.start.format("%a %Y-%m-%d %H:%M").to_string(),
+                event
+                    .recurrence
+                    .as_ref()
+                    .map(|recurrence| recurrence.to_string()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "First train to Shady Grove",
+                "Wed 2019-10-02 05:14".to_string(),
+                Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,SU".to_string())
+            ),
+            (
+                "Last train to Shady Grove",
+                "Wed 2019-10-02 23:48".to_string(),
+                Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,SU".to_string())
+            ),
+            (
+                "First train to Shady Grove",
+                "Fri 2019-10-04 07:14".to_string(),
+                Some("FREQ=WEEKLY;BYDAY=FR,SA".to_string())
+            ),
+            // Friday and Saturday last trains leave after midnight
+            (
+                "Last train to Shady Grove",
+                "Sat 2019-10-05 00:48".to_string(),
+                Some("FREQ=WEEKLY;BYDAY=SA,SU".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_station_timings_invalid_date() {
+    assert!(station_timings()
+        .to_calendar(Date::new(2019, 2, 30))
+        .is_err());
+}
+
+#[test]
+fn test_recurrence() {
+    let recurrence = Recurrence::weekends().until(NaiveDate::from_ymd_opt(2019, 12, 31).unwrap());
+
+    assert_eq!(
+        recurrence.to_string(),
+        "FREQ=WEEKLY;BYDAY=SA,SU;UNTIL=20200101T045959Z"
+    );
+
+    // The end of a day in Eastern Daylight Time
+    let recurrence = Recurrence::weekends().until(NaiveDate::from_ymd_opt(2019, 7, 4).unwrap());
+    assert!(recurrence.to_string().ends_with(";UNTIL=20190705T035959Z"));
+
+    // Daylight saving time ends at 2 AM on the first Sunday of November
+    let recurrence = Recurrence::weekends().until(NaiveDate::from_ymd_opt(2019, 11, 3).unwrap());
+    assert!(recurrence.to_string().ends_with(";UNTIL=20191104T045959Z"));
+}
+
+#[test]
+fn test_calendar() {
+    let mut calendar =
+        stop_schedule().to_calendar(Some(Route::A2), Some("0"), Some(&Recurrence::weekdays()));
+    calendar.created = DateTime::parse_from_rfc3339("2019-10-01T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+
+    let ics = calendar.to_string();
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains("TZID:America/New_York\r\n"));
+    assert!(ics.contains("DTSTAMP:20191001T120000Z\r\n"));
+    assert!(ics.contains("DTSTART;TZID=America/New_York:20191001T090500\r\n"));
+    assert!(ics.contains("DURATION:PT1M\r\n"));
+    assert!(ics.contains("RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r\n"));
+    assert!(ics.contains("SUMMARY:A2 to ANACOSTIA\\, VIA MLK\r\n"));
+    assert!(ics.contains("LOCATION:MLK JR AVE SE + GOOD HOPE RD SE\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+    assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+}
+
+#[test]
+fn test_fold() {
+    let line = format!("DESCRIPTION:{}", "é".repeat(40));
+    let folded = fold(&line);
+
+    assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+    assert_eq!(folded.replace("\r\n ", ""), line);
+}
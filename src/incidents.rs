@@ -0,0 +1,249 @@
+//! Watches the incident endpoints and reports what changed between polls.
+//!
+//! WMATA's incident endpoints return full snapshots. An [`IncidentWatcher`]
+//! remembers the previous snapshot and turns each new one into a list of
+//! [`IncidentEvent`]s, so only changes need to be acted on.
+mod tests;
+
+use crate::{
+    bus::client::responses as bus_responses,
+    error::Error,
+    rail::{client::responses as rail_responses, unit::UnitType},
+    MetroBus, MetroRail, Route, Station,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// An incident which can be tracked across snapshots.
+pub trait Incident: Clone + PartialEq {
+    /// Identifies the same incident across snapshots.
+    type Key: Eq + Hash;
+
+    /// The key of this incident.
+    fn key(&self) -> Self::Key;
+}
+
+impl Incident for rail_responses::RailIncident {
+    type Key = String;
+
+    /// Rail incidents are keyed by their `incident_id`.
+    fn key(&self) -> Self::Key {
+        self.incident_id.clone()
+    }
+}
+
+impl Incident for rail_responses::ElevatorAndEscalatorIncident {
+    type Key = (String, UnitType, Station);
+
+    /// Elevator and escalator incidents are keyed by unit name, unit type, and station,
+    /// as an elevator and an escalator may share a unit name.
+    fn key(&self) -> Self::Key {
+        (self.unit_name.clone(), self.unit_type.clone(), self.station)
+    }
+}
+
+impl Incident for bus_responses::Incident {
+    type Key = String;
+
+    /// Bus incidents are keyed by their `incident_id`.
+    fn key(&self) -> Self::Key {
+        self.incident_id.clone()
+    }
+}
+
+/// A change to an incident between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncidentEvent<T> {
+    /// The incident was not present in the previous snapshot.
+    Opened(T),
+    /// The incident was present in both snapshots, but changed.
+    Updated { before: T, after: T },
+    /// The incident was present in the previous snapshot, but not this one.
+    Resolved(T),
+}
+
+impl<T> IncidentEvent<T> {
+    /// The most recent value of the incident this event is about.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::incidents::IncidentEvent;
+    ///
+    /// let event = IncidentEvent::Updated { before: 1, after: 2 };
+    /// assert_eq!(event.incident(), &2);
+    /// ```
+    pub fn incident(&self) -> &T {
+        match self {
+            IncidentEvent::Opened(incident) => incident,
+            IncidentEvent::Updated { after, .. } => after,
+            IncidentEvent::Resolved(incident) => incident,
+        }
+    }
+}
+
+/// Tracks incidents across snapshots and reports changes as [`IncidentEvent`]s.
+///
+/// Every incident in the first snapshot is reported as [`IncidentEvent::Opened`].
+pub struct IncidentWatcher<T> {
+    incidents: Vec<T>,
+}
+
+impl<T> IncidentWatcher<T>
+where
+    T: Incident,
+{
+    /// Constructor for a watcher that has not seen any incidents.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::incidents::IncidentWatcher;
+    /// use wmata::rail::client::responses::RailIncident;
+    ///
+    /// let watcher = IncidentWatcher::<RailIncident>::new();
+    /// assert!(watcher.incidents().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        IncidentWatcher { incidents: vec![] }
+    }
+
+    /// Incidents in the most recent snapshot.
+    pub fn incidents(&self) -> &[T] {
+        &self.incidents
+    }
+
+    /// Compares a snapshot to the previous one and returns what changed.
+    ///
+    /// Opened and updated incidents are returned in snapshot order, followed by
+    /// resolved incidents in the order they appeared in the previous snapshot.
+    /// Only the first of several incidents with the same key in a snapshot is kept.
+    pub fn update<I>(&mut self, snapshot: I) -> Vec<IncidentEvent<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut keys = HashSet::new();
+        let snapshot: Vec<T> = snapshot
+            .into_iter()
+            .filter(|incident| keys.insert(incident.key()))
+            .collect();
+        let mut previous: HashMap<T::Key, &T> = self
+            .incidents
+            .iter()
+            .map(|incident| (incident.key(), incident))
+            .collect();
+
+        let mut events = vec![];
+
+        for incident in &snapshot {
+            match previous.remove(&incident.key()) {
+                None => events.push(IncidentEvent::Opened(incident.clone())),
+                Some(before) if before != incident => events.push(IncidentEvent::Updated {
+                    before: before.clone(),
+                    after: incident.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for incident in &self.incidents {
+            if previous.contains_key(&incident.key()) {
+                events.push(IncidentEvent::Resolved(incident.clone()));
+            }
+        }
+
+        self.incidents = snapshot;
+
+        events
+    }
+}
+
+impl<T> Default for IncidentWatcher<T>
+where
+    T: Incident,
+{
+    fn default() -> Self {
+        IncidentWatcher::new()
+    }
+}
+
+impl IncidentWatcher<rail_responses::RailIncident> {
+    /// Fetches rail incidents at an optional station and returns what changed since the last poll.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{MetroRail, incidents::IncidentWatcher};
+    /// use wmata::rail::client::responses::RailIncident;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let mut watcher = IncidentWatcher::<RailIncident>::new();
+    /// let events = block_on(async { watcher.poll(&client, None).await });
+    /// assert!(events.is_ok());
+    /// ```
+    pub async fn poll(
+        &mut self,
+        client: &MetroRail,
+        station: Option<Station>,
+    ) -> Result<Vec<IncidentEvent<rail_responses::RailIncident>>, Error> {
+        let snapshot = client.incidents_at(station).await?;
+
+        Ok(self.update(snapshot.incidents.into_vec()))
+    }
+}
+
+impl IncidentWatcher<rail_responses::ElevatorAndEscalatorIncident> {
+    /// Fetches elevator and escalator outages at an optional station and returns what changed since the last poll.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{MetroRail, incidents::IncidentWatcher};
+    /// use wmata::rail::client::responses::ElevatorAndEscalatorIncident;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let mut watcher = IncidentWatcher::<ElevatorAndEscalatorIncident>::new();
+    /// let events = block_on(async { watcher.poll(&client, None).await });
+    /// assert!(events.is_ok());
+    /// ```
+    pub async fn poll(
+        &mut self,
+        client: &MetroRail,
+        station: Option<Station>,
+    ) -> Result<Vec<IncidentEvent<rail_responses::ElevatorAndEscalatorIncident>>, Error> {
+        let snapshot = client.elevator_and_escalator_incidents_at(station).await?;
+
+        Ok(self.update(snapshot.incidents.into_vec()))
+    }
+}
+
+impl IncidentWatcher<bus_responses::Incident> {
+    /// Fetches bus incidents along an optional route and returns what changed since the last poll.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{MetroBus, incidents::IncidentWatcher};
+    /// use wmata::bus::client::responses::Incident;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroBus::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.bus();
+    /// let mut watcher = IncidentWatcher::<Incident>::new();
+    /// let events = block_on(async { watcher.poll(&client, None).await });
+    /// assert!(events.is_ok());
+    /// ```
+    pub async fn poll(
+        &mut self,
+        client: &MetroBus,
+        route: Option<Route>,
+    ) -> Result<Vec<IncidentEvent<bus_responses::Incident>>, Error> {
+        let snapshot = client.incidents_along(route).await?;
+
+        Ok(self.update(snapshot.incidents.into_vec()))
+    }
+}
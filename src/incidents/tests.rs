@@ -0,0 +1,150 @@
+//! Tests for incident watchers
+#[cfg(test)]
+use super::*;
+#[cfg(test)]
+use crate::rail::fixtures::incident;
+
+#[cfg(test)]
+fn rail_incident(id: &str, description: &str) -> rail_responses::RailIncident {
+    serde_json::from_str(&format!(
+        r#"{{
+            "IncidentID": "{}",
+            "Description": "{}",
+            "StartLocationFullName": null,
+            "EndLocationFullName": null,
+            "PassengerDelay": 0,
+            "DelaySeverity": null,
+            "IncidentType": "Delay",
+            "EmergencyText": null,
+            "LinesAffected": "RD;",
+            "DateUpdated": "2019-10-01T08:45:00"
+        }}"#,
+        id, description
+    ))
+    .unwrap()
+}
+
+#[cfg(test)]
+fn bus_incident(id: &str) -> bus_responses::Incident {
+    serde_json::from_str(&format!(
+        r#"{{
+            "DateUpdated": "2019-10-01T08:45:00",
+            "Description": "Buses are detoured.",
+            "IncidentID": "{}",
+            "IncidentType": "Alert",
+            "RoutesAffected": ["10A"]
+        }}"#,
+        id
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_first_snapshot_is_opened() {
+    let mut watcher = IncidentWatcher::new();
+    let events = watcher.update(vec![
+        rail_incident("1", "Delay"),
+        rail_incident("2", "Delay"),
+    ]);
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0],
+        IncidentEvent::Opened(rail_incident("1", "Delay"))
+    );
+    assert_eq!(watcher.incidents().len(), 2);
+}
+
+#[test]
+fn test_unchanged_snapshot_has_no_events() {
+    let mut watcher = IncidentWatcher::new();
+    watcher.update(vec![rail_incident("1", "Delay")]);
+
+    assert!(watcher.update(vec![rail_incident("1", "Delay")]).is_empty());
+}
+
+#[test]
+fn test_updated_and_resolved() {
+    let mut watcher = IncidentWatcher::new();
+    watcher.update(vec![
+        rail_incident("1", "Delay"),
+        rail_incident("2", "Delay"),
+    ]);
+
+    let events = watcher.update(vec![
+        rail_incident("1", "Longer delay"),
+        rail_incident("3", "Delay"),
+    ]);
+
+    assert_eq!(
+        events,
+        vec![
+            IncidentEvent::Updated {
+                before: rail_incident("1", "Delay"),
+                after: rail_incident("1", "Longer delay"),
+            },
+            IncidentEvent::Opened(rail_incident("3", "Delay")),
+            IncidentEvent::Resolved(rail_incident("2", "Delay")),
+        ]
+    );
+}
+
+#[test]
+fn test_elevator_incidents_keyed_by_unit_and_station() {
+    let mut watcher = IncidentWatcher::new();
+    watcher.update(vec![incident().unit_name("A01W01").station("A01").build()]);
+
+    let events = watcher.update(vec![
+        incident().unit_name("A01W01").station("A01").build(),
+        incident().unit_name("A01W01").station("C01").build(),
+    ]);
+
+    assert_eq!(
+        events,
+        vec![IncidentEvent::Opened(
+            incident().unit_name("A01W01").station("C01").build()
+        )]
+    );
+}
+
+#[test]
+fn test_elevator_and_escalator_sharing_unit_name() {
+    let snapshot = vec![
+        incident().unit_name("A01X01").build(),
+        incident()
+            .unit_name("A01X01")
+            .unit_type("ESCALATOR")
+            .build(),
+    ];
+    let mut watcher = IncidentWatcher::new();
+
+    assert_eq!(watcher.update(snapshot.clone()).len(), 2);
+    assert!(watcher.update(snapshot).is_empty());
+}
+
+#[test]
+fn test_duplicate_keys_in_snapshot() {
+    let snapshot = vec![
+        rail_incident("1", "Delay"),
+        rail_incident("1", "Longer delay"),
+    ];
+    let mut watcher = IncidentWatcher::new();
+
+    assert_eq!(
+        watcher.update(snapshot.clone()),
+        vec![IncidentEvent::Opened(rail_incident("1", "Delay"))]
+    );
+    assert_eq!(watcher.incidents().len(), 1);
+    assert!(watcher.update(snapshot).is_empty());
+}
+
+#[test]
+fn test_bus_incidents() {
+    let mut watcher = IncidentWatcher::new();
+    watcher.update(vec![bus_incident("1")]);
+
+    let events = watcher.update(vec![]);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].incident().incident_id, "1");
+}
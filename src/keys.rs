@@ -0,0 +1,294 @@
+//! Pools of WMATA API keys, for spreading requests across several subscriptions.
+//!
+//! A [`KeyPool`] given to a client's builder picks a key for every request, round-robin or least-used.
+//! A key rejected with 401 Unauthorized, 403 Forbidden, or a 429 for an exhausted quota is taken out of
+//! rotation for a while, and the request is retried with another key.
+//!
+//! Keys are loaded from an environment variable or a file, so they needn't be in code,
+//! and are never printed by `Debug` or `Display`.
+//!
+//! # Example
+//! ```no_run
+//! use wmata::{builder::Builder, keys::KeyPool, Wmata};
+//!
+//! // WMATA_API_KEYS=first-key,second-key
+//! let pool = KeyPool::from_env("WMATA_API_KEYS").unwrap();
+//! let client = Builder::<Wmata>::from_key_pool(pool).build().unwrap();
+//! ```
+mod tests;
+
+use crate::error::Error;
+use serde::Serialize;
+use std::{
+    env, fmt, fs,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Shown in place of API keys.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// How long a rejected key is out of rotation, unless set with [`KeyPool::with_cooldown`].
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How a [`KeyPool`] picks the key for each request.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rotation {
+    /// Each key in turn.
+    RoundRobin,
+    /// The key used for the fewest requests so far.
+    LeastUsed,
+}
+
+/// Requests made with one key of a [`KeyPool`]. Keys are identified by their position in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyUsage {
+    /// Position of the key in the pool, from 0.
+    pub index: usize,
+    /// Requests sent with the key.
+    pub requests: u64,
+    /// Requests rejected for the key, with 401, 403, or a quota 429.
+    pub rejections: u64,
+    /// Whether the key is in rotation, rather than resting after a rejection.
+    pub in_rotation: bool,
+}
+
+#[derive(Default)]
+struct KeyState {
+    requests: u64,
+    rejections: u64,
+    /// When a rejected key returns to rotation.
+    resting_until: Option<Instant>,
+}
+
+impl KeyState {
+    fn in_rotation(&self, now: Instant) -> bool {
+        match self.resting_until {
+            Some(until) => until <= now,
+            None => true,
+        }
+    }
+}
+
+struct State {
+    keys: Vec<KeyState>,
+    /// Position of the next key, for round-robin rotation.
+    next: usize,
+}
+
+/// WMATA API keys shared by clients. Clones share the same keys and usage.
+#[derive(Clone)]
+pub struct KeyPool {
+    keys: Arc<[String]>,
+    rotation: Rotation,
+    cooldown: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+impl KeyPool {
+    /// A pool of `keys`, rotated round-robin. Fails if there are no keys.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::keys::KeyPool;
+    ///
+    /// let pool = KeyPool::new(vec!["first-key".to_string(), "second-key".to_string()]).unwrap();
+    /// assert_eq!(pool.len(), 2);
+    /// ```
+    pub fn new(keys: Vec<String>) -> Result<Self, Error> {
+        let keys: Vec<String> = keys
+            .into_iter()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect();
+
+        if keys.is_empty() {
+            return Err(Error::new("A key pool needs at least one key.".to_string()));
+        }
+
+        Ok(KeyPool {
+            state: Arc::new(Mutex::new(State {
+                keys: keys.iter().map(|_| KeyState::default()).collect(),
+                next: 0,
+            })),
+            keys: keys.into(),
+            rotation: Rotation::RoundRobin,
+            cooldown: DEFAULT_COOLDOWN,
+        })
+    }
+
+    /// A pool of the keys in environment variable `variable`, separated by commas or whitespace.
+    pub fn from_env(variable: &str) -> Result<Self, Error> {
+        let keys = env::var(variable).map_err(|err| {
+            Error::new(format!("Unable to read keys from ${}: {}", variable, err))
+        })?;
+
+        KeyPool::new(split(&keys))
+            .map_err(|err| Error::new(format!("No keys in ${}. {}", variable, err.message)))
+    }
+
+    /// A pool of the keys in the file at `path`, separated by commas or whitespace.
+    /// Lines starting with `#` are skipped.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|err| {
+            Error::new(format!(
+                "Unable to read keys from {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+        let keys = contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(split)
+            .collect();
+
+        KeyPool::new(keys)
+            .map_err(|err| Error::new(format!("No keys in {}. {}", path.display(), err.message)))
+    }
+
+    /// This pool, picking keys by `rotation`.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// This pool, taking rejected keys out of rotation for `cooldown`. See [`DEFAULT_COOLDOWN`].
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Number of keys in this pool.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether this pool has no keys. Never true, since pools are made with at least one.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of keys in rotation, rather than resting after a rejection.
+    pub fn in_rotation(&self) -> usize {
+        let now = Instant::now();
+
+        self.state()
+            .keys
+            .iter()
+            .filter(|key| key.in_rotation(now))
+            .count()
+    }
+
+    /// Requests made with each key so far.
+    pub fn usage(&self) -> Vec<KeyUsage> {
+        let now = Instant::now();
+
+        self.state()
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| KeyUsage {
+                index,
+                requests: key.requests,
+                rejections: key.rejections,
+                in_rotation: key.in_rotation(now),
+            })
+            .collect()
+    }
+
+    /// Picks the key for a request, returning its position.
+    /// If every key is resting, picks the one returning to rotation soonest.
+    pub(crate) fn acquire(&self) -> usize {
+        let now = Instant::now();
+        let mut state = self.state();
+        let count = state.keys.len();
+
+        let available: Vec<usize> = match self.rotation {
+            Rotation::RoundRobin => (0..count)
+                .map(|offset| (state.next + offset) % count)
+                .collect(),
+            Rotation::LeastUsed => {
+                let mut indices: Vec<usize> = (0..count).collect();
+                indices.sort_by_key(|index| state.keys[*index].requests);
+                indices
+            }
+        };
+
+        let index = available
+            .iter()
+            .copied()
+            .find(|index| state.keys[*index].in_rotation(now))
+            .unwrap_or_else(|| {
+                (0..count)
+                    .min_by_key(|index| state.keys[*index].resting_until)
+                    .unwrap_or(0)
+            });
+
+        state.next = (index + 1) % count;
+        state.keys[index].requests += 1;
+
+        index
+    }
+
+    /// The key at `index`.
+    pub(crate) fn key(&self, index: usize) -> &str {
+        &self.keys[index]
+    }
+
+    /// Every key in this pool.
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    /// Records a response to a request made with the key at `index`, taking the key out of rotation
+    /// if it was rejected. Returns whether it was.
+    pub(crate) fn record(&self, index: usize, status: u16, body: &str) -> bool {
+        let rejected = match status {
+            401 | 403 => true,
+            // WMATA's rate limit is per second, and its quota per day. Only the quota rests a key
+            429 => body.to_lowercase().contains("quota"),
+            _ => false,
+        };
+
+        if rejected {
+            let mut state = self.state();
+            state.keys[index].rejections += 1;
+            state.keys[index].resting_until = Some(Instant::now() + self.cooldown);
+        }
+
+        rejected
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Debug for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPool")
+            .field("keys", &self.len())
+            .field("in_rotation", &self.in_rotation())
+            .field("rotation", &self.rotation)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl fmt::Display for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} keys, {} in rotation", self.len(), self.in_rotation())
+    }
+}
+
+/// Keys separated by commas or whitespace.
+fn split(keys: &str) -> Vec<String> {
+    keys.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,154 @@
+//! Tests for API key pools
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{builder::Builder, testing::FakeServer, MetroRail};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+fn pool(keys: &[&str]) -> KeyPool {
+    KeyPool::new(keys.iter().map(|key| key.to_string()).collect()).unwrap()
+}
+
+#[test]
+fn test_new() {
+    assert_eq!(pool(&["first", " second "]).len(), 2);
+    assert!(KeyPool::new(vec![]).is_err());
+    assert!(KeyPool::new(vec![" ".to_string()]).is_err());
+}
+
+#[test]
+fn test_from_env() {
+    env::set_var("WMATA_TEST_KEYS", "first, second\nthird");
+    let pool = KeyPool::from_env("WMATA_TEST_KEYS").unwrap();
+
+    assert_eq!(
+        pool.keys().collect::<Vec<_>>(),
+        ["first", "second", "third"]
+    );
+    assert!(KeyPool::from_env("WMATA_TEST_KEYS_UNSET").is_err());
+}
+
+#[test]
+fn test_from_file() {
+    let path = env::temp_dir().join(format!("wmata-keys-{}", std::process::id()));
+    fs::write(&path, "# Production\nfirst\n\nsecond,third\n").unwrap();
+    let pool = KeyPool::from_file(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        pool.unwrap().keys().collect::<Vec<_>>(),
+        ["first", "second", "third"]
+    );
+    assert!(KeyPool::from_file(&path).is_err());
+}
+
+#[test]
+fn test_round_robin() {
+    let pool = pool(&["first", "second", "third"]);
+
+    let indices: Vec<usize> = (0..4).map(|_| pool.acquire()).collect();
+
+    assert_eq!(indices, [0, 1, 2, 0]);
+}
+
+#[test]
+fn test_least_used() {
+    let pool = pool(&["first", "second"]).with_rotation(Rotation::LeastUsed);
+    pool.acquire();
+    pool.acquire();
+    pool.acquire();
+
+    assert_eq!(
+        pool.usage()
+            .iter()
+            .map(|usage| usage.requests)
+            .collect::<Vec<_>>(),
+        [2, 1]
+    );
+    assert_eq!(pool.acquire(), 1);
+}
+
+#[test]
+fn test_record() {
+    let pool = pool(&["first", "second"]);
+
+    assert!(!pool.record(0, 200, "{}"));
+    assert!(!pool.record(0, 429, "Rate limit is exceeded."));
+    assert_eq!(pool.in_rotation(), 2);
+
+    assert!(pool.record(0, 401, "Access denied due to invalid subscription key."));
+    assert!(pool.record(1, 429, "Out of call volume quota."));
+    assert_eq!(pool.in_rotation(), 0);
+    assert_eq!(pool.usage()[0].rejections, 1);
+
+    // Every key is resting, so the one returning soonest is used
+    assert_eq!(pool.acquire(), 0);
+}
+
+#[test]
+fn test_cooldown() {
+    let pool = pool(&["first", "second"]).with_cooldown(Duration::from_secs(0));
+
+    pool.record(0, 403, "Forbidden");
+
+    assert_eq!(pool.in_rotation(), 2);
+}
+
+#[test]
+fn test_keys_not_printed() {
+    let pool = pool(&["9e38c3eab34c4e6c990828002828f5ed"]);
+    let client = MetroRail::builder("9e38c3eab34c4e6c990828002828f5ed")
+        .key_pool(pool.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(pool.to_string(), "1 keys, 1 in rotation");
+    assert!(!format!("{:?}", pool).contains("9e38c3eab34c4e6c990828002828f5ed"));
+    assert!(!format!("{:?}", client).contains("9e38c3eab34c4e6c990828002828f5ed"));
+}
+
+#[test]
+fn test_key_or_pool_required() {
+    assert!(MetroRail::builder("").build().is_err());
+    assert!(MetroRail::builder(" ").build().is_err());
+
+    let client = Builder::<MetroRail>::from_key_pool(pool(&["first"]))
+        .build()
+        .unwrap();
+    assert_eq!(client.key, "");
+}
+
+#[test]
+fn test_rejected_key_retried() {
+    let server = FakeServer::with_api_key("right");
+    let pool = pool(&["wrong", "right"]);
+    let client = Builder::<MetroRail>::from_key_pool(pool.clone())
+        .base_url(&server.url())
+        .build()
+        .unwrap();
+
+    block_on(async { client.lines().await }).unwrap();
+    block_on(async { client.lines().await }).unwrap();
+
+    let usage = pool.usage();
+    assert_eq!(usage[0].requests, 1);
+    assert_eq!(usage[0].rejections, 1);
+    assert!(!usage[0].in_rotation);
+    assert_eq!(usage[1].requests, 2);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_every_key_rejected() {
+    let server = FakeServer::with_api_key("right");
+    let client = Builder::<MetroRail>::from_key_pool(pool(&["wrong", "also wrong"]))
+        .base_url(&server.url())
+        .build()
+        .unwrap();
+
+    assert!(block_on(async { client.lines().await }).is_err());
+    assert_eq!(server.requests().len(), 2);
+}
@@ -9,16 +9,40 @@
 //! ## [`MetroRail`]
 //! The interface to WMATA's MetroRail related methods
 //!
+//! ## [`Wmata`]
+//! Both interfaces, sharing one API key, connection pool, and rate limit
+//!
 
 pub mod bus;
+pub mod client;
 pub mod rail;
 
+pub mod builder;
+pub mod cache;
 pub mod date;
+pub mod drift;
 pub mod error;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "gtfs")]
+pub mod gtfs;
+#[cfg(feature = "gtfs-rt")]
+pub mod gtfs_rt;
+pub mod ics;
+pub mod incidents;
+pub mod keys;
+pub mod limit;
 pub mod location;
 mod requests;
+pub mod retry;
+pub mod spatial;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tracing")]
+mod trace;
 
 pub use bus::client::Client as MetroBus;
+pub use client::Client as Wmata;
 pub use rail::client::Client as MetroRail;
 
 pub use date::Date;
@@ -0,0 +1,116 @@
+//! Rate limiting of calls to the WMATA API.
+//!
+//! A token bucket, refilled at a steady rate. Calls over the limit queue for their turn,
+//! unless their turn is further away than the maximum wait, in which case they're refused.
+mod tests;
+
+use crate::error::Error;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A rate limit, shared by every client holding it. See [`crate::Wmata`].
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use wmata::limit::RateLimit;
+///
+/// // WMATA's default tier: 10 calls per second
+/// let rate_limit = RateLimit::new(10.0, 10.0, Duration::from_secs(60)).unwrap();
+///
+/// assert!(RateLimit::new(0.0, 10.0, Duration::from_secs(60)).is_err());
+/// ```
+#[derive(Debug)]
+pub struct RateLimit {
+    /// Calls allowed per second.
+    rate: f64,
+    /// Calls allowed in a burst, after a quiet period.
+    burst: f64,
+    /// Longest a call will queue before being refused.
+    maximum_wait: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Calls available. Negative when calls are queued.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimit {
+    /// A rate limit of `rate` calls per second, allowing bursts of `burst` calls,
+    /// refusing calls which would wait longer than `maximum_wait`.
+    ///
+    /// Fails unless `rate` is positive and `burst` is at least 1, as calls would otherwise never be allowed.
+    pub fn new(rate: f64, burst: f64, maximum_wait: Duration) -> Result<Self, Error> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(Error::new(format!(
+                "Rate {} is not a positive number of calls per second.",
+                rate
+            )));
+        }
+
+        if !burst.is_finite() || burst < 1.0 {
+            return Err(Error::new(format!(
+                "Burst {} is not at least 1 call.",
+                burst
+            )));
+        }
+
+        Ok(RateLimit::new_unchecked(rate, burst, maximum_wait))
+    }
+
+    /// [`RateLimit::new`], for a rate and burst known to be valid.
+    pub(crate) fn new_unchecked(rate: f64, burst: f64, maximum_wait: Duration) -> Self {
+        RateLimit {
+            rate,
+            burst,
+            maximum_wait,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits for this call's turn. Fails with the time until a call would be allowed,
+    /// if longer than the maximum wait.
+    pub async fn acquire(&self) -> Result<(), Duration> {
+        let wait = self.reserve(Instant::now())?;
+
+        if wait > Duration::from_secs(0) {
+            #[cfg(feature = "tracing")]
+            tracing::info!(wait_ms = wait.as_millis() as u64, "waiting for rate limit");
+
+            tokio::time::delay_for(wait).await;
+        }
+
+        Ok(())
+    }
+
+    /// Reserves a call at `now`, returning how long until it may be made.
+    fn reserve(&self, now: Instant) -> Result<Duration, Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|err| err.into_inner());
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+
+        let wait = if bucket.tokens >= 1.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+        };
+
+        if wait > self.maximum_wait {
+            return Err(wait);
+        }
+
+        bucket.tokens -= 1.0;
+
+        Ok(wait)
+    }
+}
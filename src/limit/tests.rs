@@ -0,0 +1,63 @@
+//! Tests for rate limiting
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_burst() {
+    let limit = RateLimit::new(10.0, 2.0, Duration::from_secs(5)).unwrap();
+    let now = Instant::now();
+
+    assert_eq!(limit.reserve(now), Ok(Duration::from_secs(0)));
+    assert_eq!(limit.reserve(now), Ok(Duration::from_secs(0)));
+    // Third call waits for a token, refilled every 100ms
+    assert_eq!(limit.reserve(now), Ok(Duration::from_millis(100)));
+    assert_eq!(limit.reserve(now), Ok(Duration::from_millis(200)));
+}
+
+#[test]
+fn test_refill() {
+    let limit = RateLimit::new(10.0, 1.0, Duration::from_secs(5)).unwrap();
+    let now = Instant::now();
+
+    assert_eq!(limit.reserve(now), Ok(Duration::from_secs(0)));
+    assert_eq!(
+        limit.reserve(now + Duration::from_millis(100)),
+        Ok(Duration::from_secs(0))
+    );
+    // Quiet periods don't refill past the burst
+    assert_eq!(
+        limit.reserve(now + Duration::from_secs(60)),
+        Ok(Duration::from_secs(0))
+    );
+    assert_eq!(
+        limit.reserve(now + Duration::from_secs(60)),
+        Ok(Duration::from_millis(100))
+    );
+}
+
+#[test]
+fn test_maximum_wait() {
+    let limit = RateLimit::new(1.0, 1.0, Duration::from_secs(1)).unwrap();
+    let now = Instant::now();
+
+    assert_eq!(limit.reserve(now), Ok(Duration::from_secs(0)));
+    assert_eq!(limit.reserve(now), Ok(Duration::from_secs(1)));
+    assert_eq!(limit.reserve(now), Err(Duration::from_secs(2)));
+    // Refused calls don't take a turn
+    assert_eq!(
+        limit.reserve(now + Duration::from_secs(1)),
+        Ok(Duration::from_secs(1))
+    );
+}
+
+#[test]
+fn test_invalid() {
+    let wait = Duration::from_secs(1);
+
+    assert!(RateLimit::new(0.0, 1.0, wait).is_err());
+    assert!(RateLimit::new(-1.0, 1.0, wait).is_err());
+    assert!(RateLimit::new(f64::NAN, 1.0, wait).is_err());
+    assert!(RateLimit::new(f64::INFINITY, 1.0, wait).is_err());
+    assert!(RateLimit::new(1.0, 0.5, wait).is_err());
+    assert!(RateLimit::new(1.0, f64::NAN, wait).is_err());
+}
@@ -1,6 +1,23 @@
 //! Location related types used throughout the crate.
+mod tests;
+
+use crate::{
+    bus::client::responses as bus_responses, error::Error,
+    rail::client::responses as rail_responses,
+};
+use std::f64::consts::PI;
+
+/// Mean radius of the Earth, in meters.
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Meters per degree of latitude.
+pub(crate) const METERS_PER_DEGREE: f64 = EARTH_RADIUS * PI / 180.0;
+
+/// Highest latitude considered when bounding longitudes, to avoid dividing by zero near the poles.
+pub(crate) const MAXIMUM_BOUNDED_LATITUDE: f64 = 85.0;
 
 /// A radius (in meters) around a given latitude and longitude
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct RadiusAtLatLong {
     /// Radius (in meters) around this point.
     pub radius: u32,
@@ -27,20 +44,291 @@ impl RadiusAtLatLong {
         }
     }
 
-    /// Convert this struct to a query string
+    /// Checks that this point is a valid latitude and longitude. Any radius is accepted, as WMATA documents no limit.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::RadiusAtLatLong;
+    ///
+    /// assert!(RadiusAtLatLong::new(1000, 38.8817596, -77.0166426).validate().is_ok());
+    /// assert!(RadiusAtLatLong::new(1000, 138.8817596, -77.0166426).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.latitude.is_finite() || self.latitude < -90.0 || self.latitude > 90.0 {
+            return Err(Error::new(format!(
+                "Latitude {} is not between -90 and 90.",
+                self.latitude
+            )));
+        }
+
+        if !self.longitude.is_finite() || self.longitude < -180.0 || self.longitude > 180.0 {
+            return Err(Error::new(format!(
+                "Longitude {} is not between -180 and 180.",
+                self.longitude
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Convert this struct to a query string, if it is valid. See [`RadiusAtLatLong::validate`].
     ///
     /// # Examples
     /// ```
     /// use wmata::RadiusAtLatLong;
     ///
     /// let point_with_radius = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426);
-    /// assert_eq!(point_with_radius.radius, 1000);
+    /// assert_eq!(point_with_radius.to_query().unwrap().len(), 3);
     /// ```
-    pub fn to_query(&self) -> Vec<(String, String)> {
-        vec![
+    pub fn to_query(&self) -> Result<Vec<(String, String)>, Error> {
+        self.validate()?;
+
+        Ok(vec![
             ("Radius".to_string(), self.radius.to_string()),
             ("Lat".to_string(), self.latitude.to_string()),
             ("Lon".to_string(), self.longitude.to_string()),
-        ]
+        ])
+    }
+
+    /// Whether a point lies within this radius.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::RadiusAtLatLong;
+    ///
+    /// let point_with_radius = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426);
+    /// assert!(point_with_radius.contains(&(38.8820, -77.0170)));
+    /// assert!(!point_with_radius.contains(&(38.95, -77.0170)));
+    /// ```
+    pub fn contains<T>(&self, point: &T) -> bool
+    where
+        T: LatLong + ?Sized,
+    {
+        self.distance_to(point) <= f64::from(self.radius)
+    }
+
+    /// Smallest [`BoundingBox`] containing this radius.
+    pub fn bounding_box(&self) -> BoundingBox {
+        let latitude_delta = f64::from(self.radius) / METERS_PER_DEGREE;
+        let longitude_delta = latitude_delta
+            / (self.latitude.abs() + latitude_delta)
+                .min(MAXIMUM_BOUNDED_LATITUDE)
+                .to_radians()
+                .cos();
+
+        BoundingBox {
+            minimum_latitude: (self.latitude - latitude_delta).max(-90.0),
+            minimum_longitude: self.longitude - longitude_delta,
+            maximum_latitude: (self.latitude + latitude_delta).min(90.0),
+            maximum_longitude: self.longitude + longitude_delta,
+        }
+    }
+}
+
+/// A box between two latitudes and two longitudes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox {
+    /// Southern edge of this box.
+    pub minimum_latitude: f64,
+    /// Western edge of this box.
+    pub minimum_longitude: f64,
+    /// Northern edge of this box.
+    pub maximum_latitude: f64,
+    /// Eastern edge of this box.
+    pub maximum_longitude: f64,
+}
+
+impl BoundingBox {
+    /// Smallest box containing every given point. Returns None if there are no points.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::location::BoundingBox;
+    ///
+    /// let bounding_box = BoundingBox::around(&[(38.89, -77.03), (38.91, -77.04)]).unwrap();
+    /// assert_eq!(bounding_box.maximum_latitude, 38.91);
+    /// assert_eq!(bounding_box.minimum_longitude, -77.04);
+    /// ```
+    pub fn around<T>(points: &[T]) -> Option<Self>
+    where
+        T: LatLong,
+    {
+        let (first, rest) = points.split_first()?;
+
+        let mut bounding_box = BoundingBox {
+            minimum_latitude: first.latitude(),
+            minimum_longitude: first.longitude(),
+            maximum_latitude: first.latitude(),
+            maximum_longitude: first.longitude(),
+        };
+
+        for point in rest {
+            bounding_box.minimum_latitude = bounding_box.minimum_latitude.min(point.latitude());
+            bounding_box.minimum_longitude = bounding_box.minimum_longitude.min(point.longitude());
+            bounding_box.maximum_latitude = bounding_box.maximum_latitude.max(point.latitude());
+            bounding_box.maximum_longitude = bounding_box.maximum_longitude.max(point.longitude());
+        }
+
+        Some(bounding_box)
+    }
+
+    /// Whether a point lies within this box.
+    pub fn contains<T>(&self, point: &T) -> bool
+    where
+        T: LatLong + ?Sized,
+    {
+        point.latitude() >= self.minimum_latitude
+            && point.latitude() <= self.maximum_latitude
+            && point.longitude() >= self.minimum_longitude
+            && point.longitude() <= self.maximum_longitude
+    }
+}
+
+/// A type with a latitude and longitude.
+pub trait LatLong {
+    /// Latitude of this point.
+    fn latitude(&self) -> f64;
+
+    /// Longitude of this point.
+    fn longitude(&self) -> f64;
+
+    /// Great-circle distance (in meters) to another point, using the haversine formula.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{RadiusAtLatLong, location::LatLong};
+    ///
+    /// let here = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426);
+    /// assert_eq!(here.distance_to(&here), 0.0);
+    /// ```
+    fn distance_to<T>(&self, other: &T) -> f64
+    where
+        T: LatLong + ?Sized,
+    {
+        let (latitude, other_latitude) =
+            (self.latitude().to_radians(), other.latitude().to_radians());
+        let latitude_delta = other_latitude - latitude;
+        let longitude_delta = (other.longitude() - self.longitude()).to_radians();
+
+        let a = (latitude_delta / 2.0).sin().powi(2)
+            + latitude.cos() * other_latitude.cos() * (longitude_delta / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS * a.sqrt().atan2((1.0 - a).sqrt())
+    }
+
+    /// Initial bearing (in degrees clockwise from north, from 0 up to 360) along the great circle to another point.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::location::LatLong;
+    ///
+    /// let bearing = (38.89, -77.03).bearing_to(&(38.90, -77.03));
+    /// assert!(bearing.abs() < 1e-9);
+    /// ```
+    fn bearing_to<T>(&self, other: &T) -> f64
+    where
+        T: LatLong + ?Sized,
+    {
+        let (latitude, other_latitude) =
+            (self.latitude().to_radians(), other.latitude().to_radians());
+        let longitude_delta = (other.longitude() - self.longitude()).to_radians();
+
+        let y = longitude_delta.sin() * other_latitude.cos();
+        let x = latitude.cos() * other_latitude.sin()
+            - latitude.sin() * other_latitude.cos() * longitude_delta.cos();
+
+        (y.atan2(x).to_degrees() + 360.0) % 360.0
+    }
+}
+
+impl LatLong for (f64, f64) {
+    /// Latitude of a (latitude, longitude) pair.
+    fn latitude(&self) -> f64 {
+        self.0
+    }
+
+    /// Longitude of a (latitude, longitude) pair.
+    fn longitude(&self) -> f64 {
+        self.1
+    }
+}
+
+impl LatLong for RadiusAtLatLong {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for rail_responses::StationResponse {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for rail_responses::StationInformation {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for rail_responses::StationEntrance {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for bus_responses::StopResponse {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for bus_responses::StopRoutes {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for bus_responses::BusPosition {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+impl LatLong for bus_responses::PathShape {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
     }
 }
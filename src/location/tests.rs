@@ -0,0 +1,90 @@
+//! Tests for location types
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_to_query() {
+    let query = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426)
+        .to_query()
+        .unwrap();
+
+    assert_eq!(query[0], ("Radius".to_string(), "1000".to_string()));
+    assert_eq!(query[1], ("Lat".to_string(), "38.8817596".to_string()));
+    assert_eq!(query[2], ("Lon".to_string(), "-77.0166426".to_string()));
+}
+
+#[test]
+fn test_invalid_latitude() {
+    assert!(RadiusAtLatLong::new(1000, 90.1, -77.0).to_query().is_err());
+    assert!(RadiusAtLatLong::new(1000, f64::NAN, -77.0)
+        .to_query()
+        .is_err());
+}
+
+#[test]
+fn test_invalid_longitude() {
+    assert!(RadiusAtLatLong::new(1000, 38.9, -180.5).to_query().is_err());
+}
+
+#[test]
+fn test_large_radius() {
+    assert!(RadiusAtLatLong::new(u32::MAX, 38.9, -77.0)
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn test_distance() {
+    // One degree of longitude along the equator
+    let distance = (0.0, 0.0).distance_to(&(0.0, 1.0));
+
+    assert!((distance - 111_195.0).abs() < 1.0);
+}
+
+#[test]
+fn test_bearing() {
+    assert!(((0.0, 0.0).bearing_to(&(0.0, 1.0)) - 90.0).abs() < 1e-9);
+    assert!(((0.0, 0.0).bearing_to(&(-1.0, 0.0)) - 180.0).abs() < 1e-9);
+    assert!(((0.0, 0.0).bearing_to(&(0.0, -1.0)) - 270.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_contains() {
+    let radius_at_lat_long = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426);
+
+    assert!(radius_at_lat_long.contains(&(38.8817596, -77.0166426)));
+    assert!(!radius_at_lat_long.contains(&(38.8917596, -77.0166426)));
+}
+
+#[test]
+fn test_bounding_box() {
+    let radius_at_lat_long = RadiusAtLatLong::new(1000, 38.8817596, -77.0166426);
+    let bounding_box = radius_at_lat_long.bounding_box();
+
+    // Every bearing at the edge of the radius should fall within the box
+    for bearing in (0..360).step_by(15) {
+        let bearing = f64::from(bearing).to_radians();
+        let latitude = radius_at_lat_long.latitude + 0.0089 * bearing.cos();
+        let longitude = radius_at_lat_long.longitude + 0.0115 * bearing.sin();
+
+        assert!(bounding_box.contains(&(latitude, longitude)));
+    }
+
+    assert!(!bounding_box.contains(&(38.9, -77.0166426)));
+}
+
+#[test]
+fn test_bounding_box_around() {
+    let points: Vec<(f64, f64)> = vec![];
+
+    assert!(BoundingBox::around(&points).is_none());
+    assert_eq!(
+        BoundingBox::around(&[(1.0, 2.0), (-1.0, 3.0)]),
+        Some(BoundingBox {
+            minimum_latitude: -1.0,
+            minimum_longitude: 2.0,
+            maximum_latitude: 1.0,
+            maximum_longitude: 3.0,
+        })
+    );
+}
@@ -1,7 +1,13 @@
 //! Client and responses for MetroRail endpoints.
+pub mod accessibility;
+pub mod circuit;
 pub mod client;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod line;
+pub mod network;
 pub mod station;
+pub mod unit;
 
 mod traits;
-mod urls;
+pub(crate) mod urls;
@@ -0,0 +1,90 @@
+//! Step-free routing around stations with elevator outages.
+mod tests;
+
+use crate::{
+    rail::{
+        client::responses::ElevatorAndEscalatorIncidents,
+        network::{Network, Segment},
+        unit::UnitType,
+    },
+    Station,
+};
+use std::collections::HashSet;
+
+/// A route between two stations, and whether it can be traveled without stairs or escalators.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessibleRoute {
+    /// Every station along this route has working elevators.
+    StepFree(Vec<Segment>),
+    /// No step-free route exists. This is the shortest route regardless of outages,
+    /// along with the segments that touch a station with an elevator outage.
+    NotStepFree {
+        segments: Vec<Segment>,
+        blocked: Vec<Segment>,
+    },
+}
+
+impl AccessibleRoute {
+    /// Segments of this route, in order.
+    pub fn segments(&self) -> &[Segment] {
+        match self {
+            AccessibleRoute::StepFree(segments) => segments,
+            AccessibleRoute::NotStepFree { segments, .. } => segments,
+        }
+    }
+
+    /// Whether this route is step-free.
+    pub fn is_step_free(&self) -> bool {
+        match self {
+            AccessibleRoute::StepFree(_) => true,
+            AccessibleRoute::NotStepFree { .. } => false,
+        }
+    }
+}
+
+/// Stations with at least one elevator out of service.
+///
+/// # Example
+/// ```
+/// use wmata::rail::accessibility::stations_with_elevator_outages;
+/// use wmata::rail::client::responses::ElevatorAndEscalatorIncidents;
+///
+/// let incidents: ElevatorAndEscalatorIncidents = serde_json::from_str(r#"{"ElevatorIncidents": []}"#).unwrap();
+/// assert!(stations_with_elevator_outages(&incidents).is_empty());
+/// ```
+pub fn stations_with_elevator_outages(
+    incidents: &ElevatorAndEscalatorIncidents,
+) -> HashSet<Station> {
+    incidents
+        .incidents
+        .iter()
+        .filter(|incident| incident.unit_type == UnitType::Elevator)
+        .map(|incident| incident.station)
+        .collect()
+}
+
+/// Finds a route between two stations where every station has working elevators.
+///
+/// If there is no such route, the shortest route is returned along with the segments which aren't step-free.
+/// Returns None if the stations aren't connected at all.
+pub fn accessible_route(
+    network: &Network,
+    from: Station,
+    to: Station,
+    incidents: &ElevatorAndEscalatorIncidents,
+) -> Option<AccessibleRoute> {
+    let outages = stations_with_elevator_outages(incidents);
+
+    if let Some(segments) = network.route(from, to, |station| outages.contains(&station)) {
+        return Some(AccessibleRoute::StepFree(segments));
+    }
+
+    let segments = network.route(from, to, |_| false)?;
+    let blocked = segments
+        .iter()
+        .filter(|segment| outages.contains(&segment.from) || outages.contains(&segment.to))
+        .copied()
+        .collect();
+
+    Some(AccessibleRoute::NotStepFree { segments, blocked })
+}
@@ -0,0 +1,64 @@
+//! Tests for step-free routing
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::rail::fixtures::{incident, incidents, network};
+
+#[test]
+fn test_stations_with_elevator_outages() {
+    let outages = stations_with_elevator_outages(&incidents(vec![
+        incident(),
+        incident().unit_type("ESCALATOR").station("C01"),
+    ]));
+
+    assert!(outages.contains(&Station::A01));
+    assert!(!outages.contains(&Station::C01));
+}
+
+#[test]
+fn test_step_free_without_outages() {
+    let route =
+        accessible_route(&network(), Station::A02, Station::D03, &incidents(vec![])).unwrap();
+
+    assert!(route.is_step_free());
+    assert_eq!(route.segments()[1].to, Station::C01);
+}
+
+#[test]
+fn test_step_free_around_outage() {
+    let route = accessible_route(
+        &network(),
+        Station::A02,
+        Station::D03,
+        &incidents(vec![incident().station("C01")]),
+    )
+    .unwrap();
+
+    assert!(route.is_step_free());
+    assert!(route
+        .segments()
+        .iter()
+        .all(|segment| segment.to != Station::C01));
+}
+
+#[test]
+fn test_not_step_free() {
+    let route = accessible_route(
+        &network(),
+        Station::A02,
+        Station::D03,
+        &incidents(vec![incident()]),
+    )
+    .unwrap();
+
+    match route {
+        AccessibleRoute::NotStepFree { segments, blocked } => {
+            assert_eq!(segments.len(), 5);
+            assert_eq!(blocked.len(), 2);
+            assert_eq!(blocked[0].to, Station::A01);
+            assert_eq!(blocked[1].from, Station::A01);
+        }
+        AccessibleRoute::StepFree(_) => panic!("Route should not be step-free"),
+    }
+}
@@ -0,0 +1,93 @@
+//! Approximate locations of track circuits, for placing trains on a map.
+mod tests;
+
+use crate::{
+    location::LatLong,
+    rail::client::responses::{StandardRoutes, Stations, TrainPosition},
+    Station,
+};
+use std::collections::HashMap;
+
+/// Approximate latitude and longitude of track circuits.
+///
+/// WMATA does not publish circuit locations. Circuits at a station are placed at that station,
+/// and circuits between two stations are spaced evenly, by sequence number, between them.
+#[derive(Debug, Default)]
+pub struct CircuitLocations {
+    locations: HashMap<i32, (f64, f64)>,
+}
+
+impl CircuitLocations {
+    /// Resolves circuit locations from the standard route of each line and the location of each station.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::rail::circuit::CircuitLocations;
+    /// use wmata::rail::client::responses::{StandardRoutes, Stations};
+    ///
+    /// let routes: StandardRoutes = serde_json::from_str(r#"{"StandardRoutes": []}"#).unwrap();
+    /// let stations: Stations = serde_json::from_str(r#"{"Stations": []}"#).unwrap();
+    ///
+    /// assert!(CircuitLocations::new(&routes, &stations).locate(1).is_none());
+    /// ```
+    pub fn new(routes: &StandardRoutes, stations: &Stations) -> Self {
+        let station_locations: HashMap<Station, (f64, f64)> = stations
+            .stations
+            .iter()
+            .map(|station| (station.station, (station.latitude(), station.longitude())))
+            .collect();
+
+        let mut locations = HashMap::new();
+
+        for route in routes.standard_routes.iter() {
+            let mut circuits: Vec<_> = route.track_circuits.iter().collect();
+            circuits.sort_by_key(|circuit| circuit.sequence_number);
+
+            let anchors: Vec<(usize, (f64, f64))> = circuits
+                .iter()
+                .enumerate()
+                .filter_map(|(index, circuit)| {
+                    circuit
+                        .station
+                        .and_then(|station| station_locations.get(&station))
+                        .map(|location| (index, *location))
+                })
+                .collect();
+
+            if anchors.is_empty() {
+                continue;
+            }
+
+            for (index, circuit) in circuits.iter().enumerate() {
+                let location = match anchors.iter().position(|(anchor, _)| *anchor >= index) {
+                    Some(0) => anchors[0].1,
+                    Some(next) => {
+                        let (start, from) = anchors[next - 1];
+                        let (end, to) = anchors[next];
+                        let fraction = (index - start) as f64 / (end - start) as f64;
+
+                        (
+                            from.0 + (to.0 - from.0) * fraction,
+                            from.1 + (to.1 - from.1) * fraction,
+                        )
+                    }
+                    None => anchors[anchors.len() - 1].1,
+                };
+
+                locations.entry(circuit.circuit_id).or_insert(location);
+            }
+        }
+
+        CircuitLocations { locations }
+    }
+
+    /// Approximate (latitude, longitude) of a circuit, if it is on a standard route.
+    pub fn locate(&self, circuit_id: i32) -> Option<(f64, f64)> {
+        self.locations.get(&circuit_id).copied()
+    }
+
+    /// Approximate (latitude, longitude) of a train.
+    pub fn locate_train(&self, train: &TrainPosition) -> Option<(f64, f64)> {
+        self.locate(train.circuit_id)
+    }
+}
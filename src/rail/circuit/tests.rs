@@ -0,0 +1,83 @@
+//! Tests for track circuit locations
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn circuit_locations() -> CircuitLocations {
+    let routes: StandardRoutes = serde_json::from_str(
+        r#"{
+            "StandardRoutes": [{
+                "LineCode": "RD",
+                "TrackNum": 1,
+                "TrackCircuits": [
+                    {"SeqNum": 0, "CircuitId": 1, "StationCode": null},
+                    {"SeqNum": 1, "CircuitId": 2, "StationCode": "A01"},
+                    {"SeqNum": 2, "CircuitId": 3, "StationCode": null},
+                    {"SeqNum": 3, "CircuitId": 4, "StationCode": null},
+                    {"SeqNum": 4, "CircuitId": 5, "StationCode": "A02"},
+                    {"SeqNum": 5, "CircuitId": 6, "StationCode": null}
+                ]
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    CircuitLocations::new(&routes, &stations())
+}
+
+#[cfg(test)]
+fn station(code: &str, latitude: f64, longitude: f64) -> String {
+    format!(
+        r#"{{
+            "Address": {{"City": "", "State": "", "Street": "", "Zip": ""}},
+            "Code": "{code}",
+            "Lat": {latitude},
+            "Lon": {longitude},
+            "LineCode1": "RD",
+            "LineCode2": null,
+            "LineCode3": null,
+            "LineCode4": null,
+            "Name": "",
+            "StationTogether1": "",
+            "StationTogether2": ""
+        }}"#,
+        code = code,
+        latitude = latitude,
+        longitude = longitude
+    )
+}
+
+#[cfg(test)]
+fn stations() -> Stations {
+    serde_json::from_str(&format!(
+        r#"{{"Stations": [{}, {}]}}"#,
+        station("A01", 38.0, -77.0),
+        station("A02", 39.0, -78.0)
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_station_circuit() {
+    assert_eq!(circuit_locations().locate(2), Some((38.0, -77.0)));
+    assert_eq!(circuit_locations().locate(5), Some((39.0, -78.0)));
+}
+
+#[test]
+fn test_interpolated_circuit() {
+    let (latitude, longitude) = circuit_locations().locate(3).unwrap();
+
+    assert!((latitude - 38.333_333).abs() < 1e-6);
+    assert!((longitude + 77.333_333).abs() < 1e-6);
+}
+
+#[test]
+fn test_circuit_beyond_stations() {
+    assert_eq!(circuit_locations().locate(1), Some((38.0, -77.0)));
+    assert_eq!(circuit_locations().locate(6), Some((39.0, -78.0)));
+}
+
+#[test]
+fn test_unknown_circuit() {
+    assert_eq!(circuit_locations().locate(100), None);
+}
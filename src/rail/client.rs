@@ -4,23 +4,70 @@ pub mod responses;
 mod tests;
 
 use crate::{
+    builder::Builder,
+    drift::Recorder,
     error::Error,
+    keys::REDACTED,
     rail::{
+        accessibility::{self, AccessibleRoute},
+        circuit::CircuitLocations,
+        network::Network,
         traits::{NeedsLine, NeedsStation},
         urls::URLs,
     },
-    requests::{Fetch, Request as WMATARequest},
+    requests::{Fetch, Request as WMATARequest, Transport, WMATA_API},
+    spatial::SpatialIndex,
     Line, RadiusAtLatLong, Station,
 };
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "testing")]
+use crate::testing::Cassette;
 
 /// MetroRail client. Used to fetch MetroRail-related information from the WMATA API.
+/// Cheap to clone, with clones sharing a connection pool, and any cache or rate limit.
+#[derive(Clone)]
 pub struct Client {
     /// The WMATA API key to use for all requests routed through this client.
+    /// Empty for clients built with [`Builder::from_key_pool`], which send keys from the pool instead.
     pub key: String,
+    /// Base URL requests are sent to. The WMATA API, unless set with [`Client::with_base_url`].
+    base_url: String,
+    transport: Transport,
+    #[cfg(feature = "testing")]
+    cassette: Option<Cassette>,
+    drift_recorder: Option<Recorder>,
+}
+
+impl Fetch for Client {
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn transport(&self) -> Option<&Transport> {
+        Some(&self.transport)
+    }
+
+    #[cfg(feature = "testing")]
+    fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_ref()
+    }
+
+    fn drift_recorder(&self) -> Option<&Recorder> {
+        self.drift_recorder.as_ref()
+    }
 }
 
-impl Fetch for Client {}
+impl fmt::Debug for Client {
+    /// Never shows the API key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetroRail")
+            .field("key", &REDACTED)
+            .field("base_url", &self.base_url)
+            .field("transport", &self.transport)
+            .finish()
+    }
+}
 
 // Constructor
 impl Client {
@@ -33,10 +80,81 @@ impl Client {
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
     /// ```
     pub fn new(api_key: &str) -> Self {
+        Client::with_base_url(api_key, WMATA_API)
+    }
+
+    /// Constructor for a MetroRail client sending requests somewhere other than the WMATA API,
+    /// like a mirror or the fake server in `wmata::testing`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::MetroRail;
+    ///
+    /// let client = MetroRail::with_base_url("9e38c3eab34c4e6c990828002828f5ed", "http://127.0.0.1:8080");
+    /// ```
+    pub fn with_base_url(api_key: &str, base_url: &str) -> Self {
         Client {
             key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            transport: Transport::default(),
+            #[cfg(feature = "testing")]
+            cassette: None,
+            drift_recorder: None,
         }
     }
+
+    /// Builder for a MetroRail client with timeouts, headers, a proxy, and cache, retry, and rate limit policies.
+    /// See [`crate::builder`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::MetroRail;
+    ///
+    /// let client = MetroRail::builder("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(api_key: &str) -> Builder<Self> {
+        Builder::new(api_key)
+    }
+
+    /// This client, sending requests through `transport`, shared with other clients.
+    pub(crate) fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// This client, deserializing responses strictly and recording schema drift to `recorder`.
+    /// See [`crate::drift`].
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{drift::Recorder, MetroRail};
+    ///
+    /// let recorder = Recorder::default();
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed").with_drift_recorder(recorder.clone());
+    /// ```
+    pub fn with_drift_recorder(mut self, recorder: Recorder) -> Self {
+        self.drift_recorder = Some(recorder);
+        self
+    }
+
+    /// This client, recording its requests to or replaying them from `cassette`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{testing::Cassette, MetroRail};
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed")
+    ///     .with_cassette(Cassette::record("target/cassettes/example.json"));
+    /// ```
+    #[cfg(feature = "testing")]
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
 }
 
 // No Station or Line Codes
@@ -49,7 +167,10 @@ impl Client {
     /// use wmata::MetroRail;
     /// use tokio_test::block_on;
     ///
-    /// let lines = block_on(async { MetroRail::new("9e38c3eab34c4e6c990828002828f5ed").lines().await });
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let lines = block_on(async { client.lines().await });
     /// assert!(lines.is_ok());
     /// ```
     pub async fn lines(&self) -> Result<responses::Lines, Error> {
@@ -66,6 +187,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let entrances = block_on(async { client.entrances(RadiusAtLatLong::new(1000, 38.8817596, -77.0166426)).await });
     /// assert!(entrances.is_ok());
     /// ```
@@ -78,7 +201,7 @@ impl Client {
             &URLs::Entrances.to_string(),
             Some(
                 radius_at_lat_long
-                    .to_query()
+                    .to_query()?
                     .iter()
                     .map(|(key, value)| (key.as_str(), value.clone()))
                     .collect(),
@@ -96,6 +219,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let positions = block_on(async { client.positions().await });
     /// assert!(positions.is_ok());
     /// ```
@@ -117,6 +242,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let routes = block_on(async { client.routes().await });
     /// assert!(routes.is_ok());
     /// ```
@@ -138,6 +265,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let circuits = block_on(async { client.circuits().await });
     /// assert!(circuits.is_ok());
     /// ````
@@ -164,6 +293,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let station_to_station = block_on(async { client.station_to_station(Some(Station::A01), Some(Station::A02)).await });
     /// assert!(station_to_station.is_ok());
     /// ```
@@ -190,6 +321,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let incidents = block_on(async { client.elevator_and_escalator_incidents_at(Some(Station::A01)).await });
     /// assert!(incidents.is_ok());
     /// ```
@@ -209,6 +342,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let incidents = block_on(async { client.incidents_at(Some(Station::A01)).await });
     /// assert!(incidents.is_ok());
     /// ```
@@ -228,6 +363,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let next_trains = block_on(async { client.next_trains(Station::A01).await });
     /// assert!(next_trains.is_ok());
     /// ```
@@ -247,6 +384,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let station_information = block_on(async { client.station_information(Station::A01).await });
     /// assert!(station_information.is_ok());
     /// ```
@@ -266,6 +405,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let parking_information = block_on(async { client.parking_information(Station::A01).await });
     /// assert!(parking_information.is_ok());
     /// ```
@@ -285,6 +426,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let path = block_on(async { client.path_from(Station::A01, Station::A02).await });
     /// assert!(path.is_ok());
     /// ```
@@ -305,6 +448,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let timings = block_on(async { client.timings(Station::A01).await });
     /// assert!(timings.is_ok());
     /// ```
@@ -326,6 +471,8 @@ impl Client {
     /// use tokio_test::block_on;
     ///
     /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
     /// let stations = block_on(async { client.stations_on(Some(Line::Red)).await });
     /// assert!(stations.is_ok());
     /// ```
@@ -334,6 +481,128 @@ impl Client {
     }
 }
 
+// Built from multiple endpoints
+impl Client {
+    /// A [`SpatialIndex`] over every station, for nearest-station searches without further requests.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::{MetroRail, RadiusAtLatLong, Station};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let index = block_on(async { client.station_index().await }).unwrap();
+    /// let nearest = index.nearest(&RadiusAtLatLong::new(0, 38.8983144732, -77.0280779971), 1);
+    /// assert_eq!(nearest[0].item.station, Station::A01);
+    /// ```
+    pub async fn station_index(&self) -> Result<SpatialIndex<responses::StationResponse>, Error> {
+        Ok(SpatialIndex::new(
+            self.stations_on(None).await?.stations.into_vec(),
+        ))
+    }
+
+    /// Approximate locations of track circuits, built from standard routes and station locations.
+    /// See [`CircuitLocations`].
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::MetroRail;
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let locations = block_on(async { client.circuit_locations().await });
+    /// assert!(locations.is_ok());
+    /// ```
+    pub async fn circuit_locations(&self) -> Result<CircuitLocations, Error> {
+        Ok(CircuitLocations::new(
+            &self.routes().await?,
+            &self.stations_on(None).await?,
+        ))
+    }
+
+    /// The MetroRail network, built from the path between the start and end station of every line.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::{MetroRail, Station};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let network = block_on(async { client.network().await });
+    /// assert!(network.unwrap().stations().contains(&Station::A01));
+    /// ```
+    pub async fn network(&self) -> Result<Network, Error> {
+        let mut network = Network::new();
+
+        for line in self.lines().await?.lines.iter() {
+            network.add_path(&self.path_from(line.start_station, line.end_station).await?);
+        }
+
+        Ok(network)
+    }
+
+    /// A route between two stations that avoids stations with elevator outages,
+    /// or the segments which aren't step-free if there is no such route.
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::{MetroRail, Station};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// # let server = wmata::testing::FakeServer::start();
+    /// # let client = server.rail();
+    /// let route = block_on(async { client.accessible_route(Station::A01, Station::A02).await });
+    /// assert!(route.is_ok());
+    /// ```
+    pub async fn accessible_route(
+        &self,
+        from_station: Station,
+        to_station: Station,
+    ) -> Result<AccessibleRoute, Error> {
+        let network = self.network().await?;
+        let incidents = self.elevator_and_escalator_incidents_at(None).await?;
+
+        accessibility::accessible_route(&network, from_station, to_station, &incidents).ok_or_else(
+            || {
+                Error::new(format!(
+                    "No route between {} and {}.",
+                    from_station.to_string(),
+                    to_station.to_string()
+                ))
+            },
+        )
+    }
+}
+
+// GTFS Realtime
+#[cfg(feature = "gtfs-rt")]
+impl Client {
+    /// Fetches and decodes one of WMATA's GTFS Realtime feeds. See [`crate::gtfs_rt`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use wmata::{gtfs_rt::RealtimeFeed, MetroRail};
+    /// use tokio_test::block_on;
+    ///
+    /// let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed");
+    /// let feed = block_on(async { client.realtime(RealtimeFeed::RailTripUpdates).await });
+    /// assert!(feed.is_ok());
+    /// ```
+    pub async fn realtime(
+        &self,
+        feed: crate::gtfs_rt::RealtimeFeed,
+    ) -> Result<crate::gtfs_rt::proto::FeedMessage, Error> {
+        crate::gtfs_rt::fetch(self, &self.key, feed).await
+    }
+}
+
 impl FromStr for Client {
     type Err = Error;
 
@@ -348,6 +617,6 @@ impl FromStr for Client {
     /// assert_eq!(client.key, "9e38c3eab34c4e6c990828002828f5ed");
     /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Client { key: s.to_string() })
+        Ok(Client::new(s))
     }
 }
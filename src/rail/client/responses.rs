@@ -1,16 +1,19 @@
 //! MetroRail related responses from the WMATA API.
-use crate::{Line, Station};
-use chrono::{DateTime, FixedOffset};
-use serde::Deserialize;
+use crate::{
+    rail::unit::{UnitStatus, UnitType},
+    Line, Station,
+};
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Lines {
     /// See [`Line`].
     pub lines: Box<[LineResponse]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct LineResponse {
     /// Two letter abbreviation of the line. See [`Line`].
@@ -34,14 +37,14 @@ pub struct LineResponse {
     pub second_internal_destination: Option<Station>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationEntrances {
     /// See [`StationEntrance`].
     pub entrances: Box<[StationEntrance]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationEntrance {
     /// Additional information for the entrance.
@@ -66,14 +69,14 @@ pub struct StationEntrance {
     pub second_station: Option<Station>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrainPositions {
     /// See [`TrainPosition`].
     pub train_positions: Box<[TrainPosition]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrainPosition {
     /// Uniquely identifiable internal train identifier
@@ -100,14 +103,14 @@ pub struct TrainPosition {
     pub service_type: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StandardRoutes {
     /// See [`StandardRoute`].
     pub standard_routes: Box<[StandardRoute]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StandardRoute {
     /// [`Line`] for this route.
@@ -120,7 +123,7 @@ pub struct StandardRoute {
     pub track_circuits: Box<[TrackCircuitWithStation]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrackCircuitWithStation {
     /// Order in which the circuit appears for the given line and track.
@@ -134,14 +137,14 @@ pub struct TrackCircuitWithStation {
     pub station: Option<Station>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrackCircuits {
     /// See [`TrackCircuit`].
     pub track_circuits: Box<[TrackCircuit]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrackCircuit {
     /// Track number. 1 and 2 denote "main" lines, while 0 and 3 are connectors (between different types of tracks) and pocket tracks, respectively.
@@ -152,7 +155,7 @@ pub struct TrackCircuit {
     pub neighbors: Box<[TrackNeighbor]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrackNeighbor {
     /// Left or Right neighbor group. Generally speaking, left neighbors are to the west and south, while right neighbors are to the east/north.
@@ -161,7 +164,7 @@ pub struct TrackNeighbor {
     pub circuit_ids: Box<[i32]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ElevatorAndEscalatorIncidents {
     /// See [`ElevatorAndEscalatorIncident`].
@@ -169,15 +172,15 @@ pub struct ElevatorAndEscalatorIncidents {
     pub incidents: Box<[ElevatorAndEscalatorIncident]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ElevatorAndEscalatorIncident {
     /// Unique identifier for unit, by type (a single elevator and escalator may have the same UnitName, but no two elevators or two escalators will have the same UnitName).
     pub unit_name: String,
-    /// Type of unit. Will be ELEVATOR or ESCALATOR.
-    pub unit_type: String,
-    /// Warning: Deprecated. If listed here, the unit is inoperational or otherwise impaired.
-    pub unit_status: Option<String>,
+    /// Type of unit. See [`UnitType`].
+    pub unit_type: UnitType,
+    /// Warning: Deprecated. If listed here, the unit is inoperational or otherwise impaired. See [`UnitStatus`].
+    pub unit_status: Option<UnitStatus>,
     /// [`Station`] of the incident.
     #[serde(rename = "StationCode")]
     pub station: Station,
@@ -187,32 +190,47 @@ pub struct ElevatorAndEscalatorIncident {
     pub location_description: String,
     /// Warning: Deprecated.
     pub symptom_code: Option<String>,
-    /// Warning: Deprecated. Use the time portion of the DateOutOfServ element.
-    pub time_out_of_service: String,
-    /// Description for why the unit is out of service or otherwise in reduced operation.
+    /// Warning: Deprecated. Use the time portion of the DateOutOfServ element. None if not a time of the form HHMM.
+    #[serde(default)]
+    #[serde(
+        serialize_with = "crate::date::serialize_hour_minute",
+        deserialize_with = "crate::date::deserialize_hour_minute"
+    )]
+    pub time_out_of_service: Option<NaiveTime>,
+    /// Description for why the unit is out of service or otherwise in reduced operation. See [`SymptomCategory`](crate::rail::unit::SymptomCategory).
     pub symptom_description: String,
     /// Warning: Deprecated.
     pub display_order: f64,
     /// Date and time (Eastern Standard Time) unit was reported out of service.
     #[serde(rename = "DateOutOfServ")]
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub date_out_of_service: DateTime<FixedOffset>,
     /// Date and time (Eastern Standard Time) outage details was last updated.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub date_updated: DateTime<FixedOffset>,
-    /// Estimated date and time (Eastern Standard Time) by when unit is expected to return to normal service.
-    #[serde(deserialize_with = "crate::date::deserialize_option")]
+    /// Estimated date and time (Eastern Standard Time) by when unit is expected to return to normal service. None if not estimated.
+    #[serde(default)]
+    #[serde(
+        serialize_with = "crate::date::serialize_option",
+        deserialize_with = "crate::date::deserialize_option"
+    )]
     pub estimated_return_to_service: Option<DateTime<FixedOffset>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RailIncidents {
     /// See [`RailIncident`]
     pub incidents: Box<[RailIncident]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct RailIncident {
     /// Unique identifier for an incident.
@@ -235,18 +253,21 @@ pub struct RailIncident {
     /// Semi-colon and space separated list of line codes (e.g.: RD; or BL; OR; or BL; OR; RD;). =(
     pub lines_affected: String,
     /// Date and time (Eastern Standard Time) of last update.
-    #[serde(deserialize_with = "crate::date::deserialize")]
+    #[serde(
+        serialize_with = "crate::date::serialize",
+        deserialize_with = "crate::date::deserialize"
+    )]
     pub date_updated: DateTime<FixedOffset>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationToStationInfos {
     /// See [`StationToStationInfo`]
     pub station_to_station_infos: Box<[StationToStationInfo]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationToStationInfo {
     /// Average of distance traveled between two stations and straight-line distance (as used for WMATA fare calculations).
@@ -263,7 +284,7 @@ pub struct StationToStationInfo {
     pub source_station: Station,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RailFare {
     /// Fare during off-peak times.
@@ -274,14 +295,14 @@ pub struct RailFare {
     pub senior_disabled: f64,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct RailPredictions {
     /// See [`RailPrediction`].
     pub trains: Box<[RailPrediction]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct RailPrediction {
     /// Number of cars on a train, usually 6 or 8, but might also return -.
@@ -308,7 +329,7 @@ pub struct RailPrediction {
     pub minutes: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationInformation {
     /// Structure describing address information.
@@ -346,7 +367,7 @@ pub struct StationInformation {
     pub second_station_together: Option<Station>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationAddress {
     /// City of this station.
@@ -359,14 +380,14 @@ pub struct StationAddress {
     pub zip: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationsParking {
     /// See [`StationParking`].
     pub stations_parking: Box<[StationParking]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationParking {
     /// [`Station`] of this station.
@@ -380,7 +401,7 @@ pub struct StationParking {
     pub short_term_parking: ShortTermParking,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct AllDayParking {
     /// Number of all-day parking spots available at a station.
@@ -395,7 +416,7 @@ pub struct AllDayParking {
     pub saturday_non_rider_cost: Option<f64>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ShortTermParking {
     /// Number of short-term parking spots available at a station (parking meters).
@@ -404,14 +425,14 @@ pub struct ShortTermParking {
     pub notes: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct PathBetweenStations {
     /// See [`Path`].
     pub path: Box<[Path]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Path {
     /// Distance in feet to the previous station in the list.
@@ -430,14 +451,14 @@ pub struct Path {
     pub station_name: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationTimings {
     /// See [`StationTime`].
     pub station_times: Box<[StationTime]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationTime {
     /// [`Station`] of this station.
@@ -462,7 +483,7 @@ pub struct StationTime {
     pub sunday: StationFirstLastTrains,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationFirstLastTrains {
     /// Station opening time. Format is HH:mm.
@@ -473,7 +494,7 @@ pub struct StationFirstLastTrains {
     pub last_trains: Box<[TrainTime]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrainTime {
     /// Time the train leaves the station.
@@ -483,14 +504,14 @@ pub struct TrainTime {
     pub destination: Station,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Stations {
     /// See [`Station`].
     pub stations: Box<[StationResponse]>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct StationResponse {
     /// See [`Address`].
@@ -528,7 +549,7 @@ pub struct StationResponse {
     pub second_station_together: Option<Station>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct Address {
     /// City of this station.
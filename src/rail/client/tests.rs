@@ -2,9 +2,19 @@
 #[cfg(test)]
 use super::*;
 
+#[cfg(test)]
+use crate::testing::FakeServer;
 #[cfg(test)]
 use tokio_test::block_on;
 
+#[cfg(test)]
+fn query(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
 #[test]
 fn test_constructor() {
     let client = Client::new("9e38c3eab34c4e6c990828002828f5ed");
@@ -13,8 +23,17 @@ fn test_constructor() {
 }
 
 #[test]
-fn test_lines() {
+fn test_from_str() {
     let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+
+    assert_eq!(client.key, "9e38c3eab34c4e6c990828002828f5ed");
+    assert_eq!(client.base_url, "https://api.wmata.com");
+}
+
+#[test]
+fn test_lines() {
+    let server = FakeServer::start();
+    let client = server.rail();
     let lines = block_on(async { client.lines().await });
 
     assert_eq!(lines.unwrap().lines.len(), 6);
@@ -22,31 +41,49 @@ fn test_lines() {
 
 #[test]
 fn test_entrances() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
-    let entrances = block_on(async { client.entrances(RadiusAtLatLong::new(1, 1.0, 1.0)).await });
+    let server = FakeServer::start();
+    let client = server.rail();
+    let entrances = block_on(async {
+        client
+            .entrances(RadiusAtLatLong::new(500, 38.898303, -77.028099))
+            .await
+    });
 
-    assert_eq!(entrances.unwrap().entrances.len(), 0);
+    assert_eq!(entrances.unwrap().entrances[0].first_station, Station::A01);
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[
+            ("Radius", "500"),
+            ("Lat", "38.898303"),
+            ("Lon", "-77.028099")
+        ])
+    );
 }
 
 #[test]
 fn test_stations() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let stations = block_on(async { client.stations_on(Some(Line::Red)).await });
 
-    assert_eq!(stations.unwrap().stations.len(), 27);
+    assert_eq!(stations.unwrap().stations.len(), 3);
+    assert_eq!(server.requests()[0].query, query(&[("LineCode", "RD")]));
 }
 
 #[test]
 fn test_all_stations() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let stations = block_on(async { client.stations_on(None).await });
 
-    assert_eq!(stations.unwrap().stations.len(), 95);
+    assert_eq!(stations.unwrap().stations.len(), 3);
+    assert!(server.requests()[0].query.is_empty());
 }
 
 #[test]
 fn test_station() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let station_to_station = block_on(async {
         client
             .station_to_station(Some(Station::A01), Some(Station::A02))
@@ -57,34 +94,46 @@ fn test_station() {
         station_to_station.unwrap().station_to_station_infos.len(),
         1
     );
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[("FromStationCode", "A01"), ("ToStationCode", "A02")])
+    );
 }
 
 #[test]
 fn test_station_one_station() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let station_to_station =
         block_on(async { client.station_to_station(Some(Station::A01), None).await });
 
     assert_eq!(
         station_to_station.unwrap().station_to_station_infos.len(),
-        93
+        1
+    );
+    assert_eq!(
+        server.requests()[0].query,
+        query(&[("FromStationCode", "A01")])
     );
 }
 
 #[test]
 fn test_station_no_stations() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let station_to_station = block_on(async { client.station_to_station(None, None).await });
 
     assert_eq!(
         station_to_station.unwrap().station_to_station_infos.len(),
-        8922
+        1
     );
+    assert!(server.requests()[0].query.is_empty());
 }
 
 #[test]
 fn test_positions() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let positions = block_on(async { client.positions().await });
 
     assert!(positions.is_ok());
@@ -92,35 +141,40 @@ fn test_positions() {
 
 #[test]
 fn test_routes() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let routes = block_on(async { client.routes().await });
 
-    assert_eq!(routes.unwrap().standard_routes.len(), 14);
+    assert_eq!(routes.unwrap().standard_routes.len(), 1);
 }
 
 #[test]
 fn test_circuits() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let circuits = block_on(async { client.circuits().await });
 
-    assert_eq!(circuits.unwrap().track_circuits.len(), 3486);
+    assert_eq!(circuits.unwrap().track_circuits.len(), 3);
 }
 
 #[test]
 fn test_elevator_and_escalator_incidents() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let incidents = block_on(async {
         client
             .elevator_and_escalator_incidents_at(Some(Station::A01))
             .await
     });
 
-    assert!(incidents.is_ok());
+    assert_eq!(incidents.unwrap().incidents[0].station, Station::A01);
+    assert_eq!(server.requests()[0].query, query(&[("StationCode", "A01")]));
 }
 
 #[test]
 fn test_all_elevator_and_escalator_incidents() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let incidents = block_on(async { client.elevator_and_escalator_incidents_at(None).await });
 
     assert!(incidents.is_ok());
@@ -128,15 +182,18 @@ fn test_all_elevator_and_escalator_incidents() {
 
 #[test]
 fn test_incident() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let incidents = block_on(async { client.incidents_at(Some(Station::A01)).await });
 
-    assert!(incidents.is_ok());
+    assert_eq!(incidents.unwrap().incidents.len(), 1);
+    assert_eq!(server.requests()[0].query, query(&[("StationCode", "A01")]));
 }
 
 #[test]
 fn test_all_incidents() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let incidents = block_on(async { client.incidents_at(None).await });
 
     assert!(incidents.is_ok());
@@ -144,15 +201,21 @@ fn test_all_incidents() {
 
 #[test]
 fn test_next_trains() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let next_trains = block_on(async { client.next_trains(Station::A01).await });
 
-    assert!(next_trains.is_ok());
+    assert_eq!(next_trains.unwrap().trains[0].location, Station::A01);
+    assert_eq!(
+        server.requests()[0].path,
+        "/StationPrediction.svc/json/GetPrediction/A01"
+    );
 }
 
 #[test]
 fn test_information() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let station_information = block_on(async { client.station_information(Station::A01).await });
 
     assert_eq!(station_information.unwrap().station, Station::A01);
@@ -160,7 +223,8 @@ fn test_information() {
 
 #[test]
 fn test_parking_information() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let parking_information = block_on(async { client.parking_information(Station::A01).await });
 
     assert_eq!(parking_information.unwrap().stations_parking.len(), 0);
@@ -168,7 +232,8 @@ fn test_parking_information() {
 
 #[test]
 fn test_path_to_station() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let path = block_on(async { client.path_from(Station::A01, Station::A02).await });
 
     assert_eq!(path.unwrap().path[1].distance_to_previous_station, 4178);
@@ -176,7 +241,8 @@ fn test_path_to_station() {
 
 #[test]
 fn test_timings() {
-    let client: Client = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let server = FakeServer::start();
+    let client = server.rail();
     let timings = block_on(async { client.timings(Station::A01).await });
 
     assert_eq!(timings.unwrap().station_times[0].station, Station::A01);
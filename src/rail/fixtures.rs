@@ -0,0 +1,130 @@
+//! Paths, networks, and elevator and escalator incidents for tests, setting only the fields each test cares about.
+use crate::rail::{
+    client::responses::{
+        ElevatorAndEscalatorIncident, ElevatorAndEscalatorIncidents, PathBetweenStations,
+    },
+    network::Network,
+};
+
+/// A path along `line` (e.g.: `RD`), through each station with its distance from the previous one in feet.
+pub(crate) fn path(line: &str, stations: &[(&str, i32)]) -> PathBetweenStations {
+    let path: Vec<String> = stations
+        .iter()
+        .enumerate()
+        .map(|(index, (station, distance))| {
+            format!(
+                r#"{{"DistanceToPrev": {}, "LineCode": "{}", "SeqNum": {}, "StationCode": "{}", "StationName": ""}}"#,
+                distance,
+                line,
+                index + 1,
+                station
+            )
+        })
+        .collect();
+
+    serde_json::from_str(&format!(r#"{{"Path": [{}]}}"#, path.join(","))).unwrap()
+}
+
+/// Three lines, A02 to B01, F01 to F03, and C01 to D03, joined by the stations sharing a building
+/// (A01 and C01, B01 and F01).
+pub(crate) fn network() -> Network {
+    Network::from_paths(&[
+        path("RD", &[("A02", 0), ("A01", 4000), ("B01", 3000)]),
+        path("GR", &[("F01", 0), ("F02", 2000), ("F03", 3000)]),
+        path(
+            "BL",
+            &[("C01", 0), ("D01", 2000), ("D02", 2000), ("D03", 2000)],
+        ),
+    ])
+}
+
+/// Builder for an [`ElevatorAndEscalatorIncident`]. Unless set, an elevator at A01, named for its station,
+/// out of service since 08:15 on October 1st, 2019, with no estimated return to service.
+pub(crate) struct IncidentBuilder {
+    unit_name: Option<String>,
+    unit_type: String,
+    station: String,
+    time_out_of_service: String,
+    estimated_return: String,
+}
+
+/// An [`IncidentBuilder`] with the defaults.
+pub(crate) fn incident() -> IncidentBuilder {
+    IncidentBuilder {
+        unit_name: None,
+        unit_type: "ELEVATOR".to_string(),
+        station: "A01".to_string(),
+        time_out_of_service: r#""0815""#.to_string(),
+        estimated_return: "null".to_string(),
+    }
+}
+
+/// Every incident built, as a single response.
+pub(crate) fn incidents(incidents: Vec<IncidentBuilder>) -> ElevatorAndEscalatorIncidents {
+    ElevatorAndEscalatorIncidents {
+        incidents: incidents.into_iter().map(IncidentBuilder::build).collect(),
+    }
+}
+
+impl IncidentBuilder {
+    /// Unit name, e.g.: `A01E03`. Unless set, the station code followed by `X01`.
+    pub fn unit_name(mut self, unit_name: &str) -> Self {
+        self.unit_name = Some(unit_name.to_string());
+        self
+    }
+
+    /// Unit type as WMATA sends it, e.g.: `ESCALATOR`.
+    pub fn unit_type(mut self, unit_type: &str) -> Self {
+        self.unit_type = unit_type.to_string();
+        self
+    }
+
+    /// Station code, e.g.: `C01`.
+    pub fn station(mut self, station: &str) -> Self {
+        self.station = station.to_string();
+        self
+    }
+
+    /// `TimeOutOfService` as JSON, e.g.: `"0815"` or `null`.
+    pub fn time_out_of_service(mut self, time_out_of_service: &str) -> Self {
+        self.time_out_of_service = time_out_of_service.to_string();
+        self
+    }
+
+    /// `EstimatedReturnToService` as JSON, e.g.: `"2019-10-02T08:15:00"` or `null`.
+    pub fn estimated_return(mut self, estimated_return: &str) -> Self {
+        self.estimated_return = estimated_return.to_string();
+        self
+    }
+
+    pub fn build(self) -> ElevatorAndEscalatorIncident {
+        let unit_name = match self.unit_name {
+            Some(unit_name) => unit_name,
+            None => format!("{}X01", self.station),
+        };
+
+        serde_json::from_str(&format!(
+            r#"{{
+                "UnitName": "{unit_name}",
+                "UnitType": "{unit_type}",
+                "UnitStatus": null,
+                "StationCode": "{station}",
+                "StationName": "",
+                "LocationDescription": "",
+                "SymptomCode": null,
+                "TimeOutOfService": {time_out_of_service},
+                "SymptomDescription": "Minor Repair",
+                "DisplayOrder": 0,
+                "DateOutOfServ": "2019-10-01T08:15:00",
+                "DateUpdated": "2019-10-01T08:30:00",
+                "EstimatedReturnToService": {estimated_return}
+            }}"#,
+            unit_name = unit_name,
+            unit_type = self.unit_type,
+            station = self.station,
+            time_out_of_service = self.time_out_of_service,
+            estimated_return = self.estimated_return
+        ))
+        .unwrap()
+    }
+}
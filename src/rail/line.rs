@@ -6,12 +6,12 @@ use crate::{
 };
 use serde::{
     de::{Deserializer, Error as SerdeError},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 use std::{error, fmt, str::FromStr};
 
 /// All MetroRail lines.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Line {
     Red,
     Blue,
@@ -50,7 +50,7 @@ impl Line {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/5476364f031f590f38092507/operations/5476364f031f5909e4fe330c)
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Line;
     /// use tokio_test::block_on;
     ///
@@ -73,6 +73,15 @@ impl<'de> Deserialize<'de> for Line {
     }
 }
 
+impl Serialize for Line {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl ToString for Line {
     fn to_string(&self) -> String {
         match self {
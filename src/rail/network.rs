@@ -0,0 +1,237 @@
+//! A graph of the MetroRail network, built from paths between stations.
+mod tests;
+
+use crate::{rail::client::responses, Line, Station};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// Cost, in feet, added to a route for each change of line.
+/// Keeps routes on a single line where trunks are shared (e.g.: Blue, Orange, and Silver).
+const TRANSFER_PENALTY: i32 = 5280;
+
+/// A single hop between two adjacent stations.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Segment {
+    /// [`Station`] this segment starts at.
+    pub from: Station,
+    /// [`Station`] this segment ends at.
+    pub to: Station,
+    /// [`Line`] this segment is traveled on. None when walking between the platforms of a
+    /// station with multiple platforms. See [`Station::together`].
+    pub line: Option<Line>,
+    /// Distance in feet between the two stations.
+    pub distance: i32,
+}
+
+/// The MetroRail network as a graph of stations.
+#[derive(Debug, Default)]
+pub struct Network {
+    segments: HashMap<Station, Vec<Segment>>,
+}
+
+impl Network {
+    /// Constructor for an empty network.
+    pub fn new() -> Self {
+        Network::default()
+    }
+
+    /// Builds a network from a set of paths, usually one for each line from its start to its end station.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::rail::network::Network;
+    ///
+    /// let network = Network::from_paths(&[]);
+    /// assert!(network.stations().is_empty());
+    /// ```
+    pub fn from_paths(paths: &[responses::PathBetweenStations]) -> Self {
+        let mut network = Network::new();
+
+        for path in paths {
+            network.add_path(path);
+        }
+
+        network
+    }
+
+    /// Adds every hop along a path to this network, in both directions.
+    /// Platforms of the same station (see [`Station::together`]) are connected once both are present.
+    pub fn add_path(&mut self, path: &responses::PathBetweenStations) {
+        let mut stops: Vec<&responses::Path> = path.path.iter().collect();
+        stops.sort_by_key(|stop| stop.sequence_number);
+
+        for pair in stops.windows(2) {
+            let (previous, next) = (pair[0], pair[1]);
+
+            self.connect(
+                previous.station,
+                next.station,
+                Some(next.line),
+                next.distance_to_previous_station,
+            );
+        }
+
+        for stop in stops {
+            if let Some(together) = stop.station.together() {
+                if self.segments.contains_key(&together) {
+                    self.connect(stop.station, together, None, 0);
+                }
+            }
+        }
+    }
+
+    /// Every station in this network.
+    pub fn stations(&self) -> Vec<Station> {
+        self.segments.keys().copied().collect()
+    }
+
+    /// Segments leaving the given station.
+    pub fn segments_from(&self, station: Station) -> &[Segment] {
+        self.segments
+            .get(&station)
+            .map(|segments| segments.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Shortest route between two stations, as a list of segments, avoiding any station for which `avoid` returns true.
+    ///
+    /// Changing lines is only possible at stations served by both lines (see [`Station::lines`]),
+    /// and is penalized so that routes stay on one line where possible.
+    /// Returns None if no such route exists.
+    pub fn route<F>(&self, from: Station, to: Station, avoid: F) -> Option<Vec<Segment>>
+    where
+        F: Fn(Station) -> bool,
+    {
+        if avoid(from) || avoid(to) {
+            return None;
+        }
+
+        if from == to {
+            return Some(vec![]);
+        }
+
+        let start = Node {
+            station: from,
+            line: None,
+        };
+
+        let mut costs: HashMap<Node, i32> = HashMap::new();
+        let mut previous: HashMap<Node, (Node, Segment)> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        costs.insert(start, 0);
+        queue.push(Visit {
+            cost: 0,
+            node: start,
+        });
+
+        while let Some(Visit { cost, node }) = queue.pop() {
+            if node.station == to {
+                let mut segments = vec![];
+                let mut current = node;
+
+                while let Some((before, segment)) = previous.get(&current) {
+                    segments.push(*segment);
+                    current = *before;
+                }
+
+                segments.reverse();
+
+                return Some(segments);
+            }
+
+            if let Some(best) = costs.get(&node) {
+                if cost > *best {
+                    continue;
+                }
+            }
+
+            for segment in self.segments_from(node.station) {
+                if avoid(segment.to) {
+                    continue;
+                }
+
+                let line = segment.line.or(node.line);
+                let mut next_cost = cost + segment.distance;
+
+                if let (Some(current), Some(next)) = (node.line, segment.line) {
+                    if current != next {
+                        let lines = node.station.lines();
+
+                        if !lines.contains(&current) || !lines.contains(&next) {
+                            continue;
+                        }
+
+                        next_cost += TRANSFER_PENALTY;
+                    }
+                }
+
+                let next = Node {
+                    station: segment.to,
+                    line,
+                };
+
+                let cheaper = match costs.get(&next) {
+                    Some(best) => next_cost < *best,
+                    None => true,
+                };
+
+                if cheaper {
+                    costs.insert(next, next_cost);
+                    previous.insert(next, (node, *segment));
+                    queue.push(Visit {
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn connect(&mut self, from: Station, to: Station, line: Option<Line>, distance: i32) {
+        for (from, to) in [(from, to), (to, from)].iter() {
+            let segment = Segment {
+                from: *from,
+                to: *to,
+                line,
+                distance,
+            };
+
+            let segments = self.segments.entry(*from).or_default();
+
+            if !segments.contains(&segment) {
+                segments.push(segment);
+            }
+        }
+    }
+}
+
+/// A station, and the line a route arrived on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Node {
+    station: Station,
+    line: Option<Line>,
+}
+
+/// A node in the route search queue, ordered so the cheapest is popped first.
+#[derive(Debug, PartialEq, Eq)]
+struct Visit {
+    cost: i32,
+    node: Node,
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
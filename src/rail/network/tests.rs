@@ -0,0 +1,72 @@
+//! Tests for the MetroRail network
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::rail::fixtures::network;
+
+#[test]
+fn test_stations() {
+    assert_eq!(network().stations().len(), 10);
+}
+
+#[test]
+fn test_platforms_are_connected() {
+    let network = network();
+
+    assert!(network.segments_from(Station::A01).contains(&Segment {
+        from: Station::A01,
+        to: Station::C01,
+        line: None,
+        distance: 0,
+    }));
+}
+
+#[test]
+fn test_route_on_one_line() {
+    let route = network()
+        .route(Station::A02, Station::B01, |_| false)
+        .unwrap();
+
+    assert_eq!(route.len(), 2);
+    assert!(route.iter().all(|segment| segment.line == Some(Line::Red)));
+}
+
+#[test]
+fn test_route_with_transfer() {
+    let route = network()
+        .route(Station::A02, Station::D03, |_| false)
+        .unwrap();
+    let stations: Vec<Station> = route.iter().map(|segment| segment.to).collect();
+
+    assert_eq!(
+        stations,
+        vec![
+            Station::A01,
+            Station::C01,
+            Station::D01,
+            Station::D02,
+            Station::D03
+        ]
+    );
+}
+
+#[test]
+fn test_route_avoiding_station() {
+    let route = network()
+        .route(Station::A02, Station::D03, |station| {
+            station == Station::C01
+        })
+        .unwrap();
+
+    assert_eq!(route.last().unwrap().line, None);
+    assert_eq!(route.last().unwrap().from, Station::F03);
+}
+
+#[test]
+fn test_no_route() {
+    assert!(network()
+        .route(Station::A02, Station::D03, |station| station
+            == Station::A01)
+        .is_none());
+}
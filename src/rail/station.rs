@@ -6,12 +6,12 @@ use crate::{
 };
 use serde::{
     de::{Deserializer, Error as SerdeError},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 use std::{error, fmt, str::FromStr};
 
 /// Every MetroRail station code as defined by WMATA.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Station {
     A01,
     A02,
@@ -119,7 +119,7 @@ impl Station {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/5476364f031f590f38092507/operations/5476364f031f5909e4fe3313?)
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -140,7 +140,7 @@ impl Station {
     /// [WMATA Documentation](https://developer.wmata.com/docs/services/54763641281d83086473f232/operations/54763641281d830c946a3d76?)
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -158,7 +158,7 @@ impl Station {
     /// Reported rail incidents (significant disruptions and delays to normal service) at this station
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -172,7 +172,7 @@ impl Station {
     /// Next train arrivals for this station
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -186,7 +186,7 @@ impl Station {
     /// Location and address information at this station
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -200,7 +200,7 @@ impl Station {
     /// Parking information for this station
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -217,7 +217,7 @@ impl Station {
     /// Set of ordered stations and distances between this station and another on the **same line**.
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -235,7 +235,7 @@ impl Station {
     /// Opening and scheduled first/last train times for this station.
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use wmata::Station;
     /// use tokio_test::block_on;
     ///
@@ -259,6 +259,15 @@ impl<'de> Deserialize<'de> for Station {
     }
 }
 
+impl Serialize for Station {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl Station {
     pub fn name(self) -> String {
         match self {
@@ -453,6 +462,48 @@ impl Station {
             }
         }
     }
+
+    /// For stations with multiple platforms (e.g.: Gallery Place, Fort Totten, L'Enfant Plaza, and Metro Center), the other platform's [`Station`].
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Station;
+    ///
+    /// assert_eq!(Station::A01.together(), Some(Station::C01));
+    /// assert_eq!(Station::A02.together(), None);
+    /// ```
+    pub fn together(self) -> Option<Station> {
+        match self {
+            Station::A01 => Some(Station::C01),
+            Station::C01 => Some(Station::A01),
+            Station::B01 => Some(Station::F01),
+            Station::F01 => Some(Station::B01),
+            Station::B06 => Some(Station::E06),
+            Station::E06 => Some(Station::B06),
+            Station::D03 => Some(Station::F03),
+            Station::F03 => Some(Station::D03),
+            _ => None,
+        }
+    }
+
+    /// Station of a stop ID from one of WMATA's GTFS feeds, which are either a station code
+    /// or contain one, e.g.: `STN_A01_C01` or `PF_A01_C`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::Station;
+    ///
+    /// assert_eq!(Station::from_gtfs_id("PF_A01_C"), Some(Station::A01));
+    /// assert_eq!(Station::from_gtfs_id("1001195"), None);
+    /// ```
+    pub fn from_gtfs_id(stop_id: &str) -> Option<Station> {
+        Station::from_str(stop_id).ok().or_else(|| {
+            stop_id
+                .split('_')
+                .nth(1)
+                .and_then(|code| Station::from_str(code).ok())
+        })
+    }
 }
 
 impl ToString for Station {
@@ -0,0 +1,292 @@
+//! Elevators and escalators, as reported by elevator and escalator incidents.
+mod tests;
+
+use crate::{
+    rail::client::responses::{ElevatorAndEscalatorIncident, ElevatorAndEscalatorIncidents},
+    Station,
+};
+use chrono::{DateTime, Duration, FixedOffset};
+use serde::{de::Deserializer, Deserialize, Serialize, Serializer};
+use std::{collections::HashMap, fmt};
+
+/// Types of units reported by elevator and escalator incidents.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UnitType {
+    Elevator,
+    Escalator,
+    /// A type not known to this crate.
+    Other(String),
+}
+
+impl<'de> Deserialize<'de> for UnitType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(UnitType::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+impl Serialize for UnitType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl fmt::Display for UnitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitType::Elevator => write!(f, "ELEVATOR"),
+            UnitType::Escalator => write!(f, "ESCALATOR"),
+            UnitType::Other(unit_type) => write!(f, "{}", unit_type),
+        }
+    }
+}
+
+impl From<&str> for UnitType {
+    /// Converts a string to a [`UnitType`].
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::rail::unit::UnitType;
+    ///
+    /// assert_eq!(UnitType::from("ELEVATOR"), UnitType::Elevator);
+    /// assert_eq!(UnitType::from("STAIRS"), UnitType::Other("STAIRS".to_string()));
+    /// ```
+    fn from(s: &str) -> Self {
+        match s.trim().to_uppercase().as_str() {
+            "ELEVATOR" => UnitType::Elevator,
+            "ESCALATOR" => UnitType::Escalator,
+            _ => UnitType::Other(s.to_string()),
+        }
+    }
+}
+
+/// Status of a unit with a reported incident.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitStatus {
+    /// The unit is inoperational.
+    OutOfService,
+    /// The unit is running, but impaired (e.g.: an escalator running as stairs).
+    ReducedOperation,
+    /// A status not known to this crate.
+    Other(String),
+}
+
+impl<'de> Deserialize<'de> for UnitStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(UnitStatus::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
+
+impl Serialize for UnitStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            UnitStatus::OutOfService => "O",
+            UnitStatus::ReducedOperation => "R",
+            UnitStatus::Other(status) => status,
+        })
+    }
+}
+
+impl From<&str> for UnitStatus {
+    /// Converts a string to a [`UnitStatus`].
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::rail::unit::UnitStatus;
+    ///
+    /// assert_eq!(UnitStatus::from("O"), UnitStatus::OutOfService);
+    /// ```
+    fn from(s: &str) -> Self {
+        match s.trim().to_uppercase().as_str() {
+            "O" | "OUT" | "OUT OF SERVICE" => UnitStatus::OutOfService,
+            "R" | "REDUCED" | "REDUCED OPERATION" => UnitStatus::ReducedOperation,
+            _ => UnitStatus::Other(s.to_string()),
+        }
+    }
+}
+
+/// Why a unit is out of service, parsed from an incident's symptom description.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymptomCategory {
+    MinorRepair,
+    MajorRepair,
+    Modernization,
+    PreventiveMaintenance,
+    Inspection,
+    ServiceCall,
+    Construction,
+    PowerOutage,
+    /// A symptom not known to this crate.
+    Other(String),
+}
+
+impl From<&str> for SymptomCategory {
+    /// Converts a symptom description to a [`SymptomCategory`].
+    ///
+    /// # Examples
+    /// ```
+    /// use wmata::rail::unit::SymptomCategory;
+    ///
+    /// assert_eq!(SymptomCategory::from("Major Repair"), SymptomCategory::MajorRepair);
+    /// ```
+    fn from(s: &str) -> Self {
+        let symptom = s.to_lowercase();
+
+        if symptom.contains("minor repair") {
+            SymptomCategory::MinorRepair
+        } else if symptom.contains("major repair") {
+            SymptomCategory::MajorRepair
+        } else if symptom.contains("modernization") {
+            SymptomCategory::Modernization
+        } else if symptom.contains("preventive maintenance") {
+            SymptomCategory::PreventiveMaintenance
+        } else if symptom.contains("inspection") {
+            SymptomCategory::Inspection
+        } else if symptom.contains("service call") {
+            SymptomCategory::ServiceCall
+        } else if symptom.contains("construction") || symptom.contains("capital project") {
+            SymptomCategory::Construction
+        } else if symptom.contains("power") {
+            SymptomCategory::PowerOutage
+        } else {
+            SymptomCategory::Other(s.to_string())
+        }
+    }
+}
+
+impl ElevatorAndEscalatorIncident {
+    /// Why this unit is out of service.
+    pub fn symptom_category(&self) -> SymptomCategory {
+        SymptomCategory::from(self.symptom_description.as_str())
+    }
+
+    /// How long this unit has been out of service as of the given time.
+    pub fn outage_duration(&self, now: DateTime<FixedOffset>) -> Duration {
+        now.signed_duration_since(self.date_out_of_service)
+    }
+
+    /// How long this unit is expected to be out of service in total, if a return to service is estimated.
+    pub fn estimated_outage_duration(&self) -> Option<Duration> {
+        self.estimated_return_to_service
+            .map(|estimate| estimate.signed_duration_since(self.date_out_of_service))
+    }
+}
+
+/// A number of elevators and escalators.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct UnitCounts {
+    pub elevators: usize,
+    pub escalators: usize,
+}
+
+impl UnitCounts {
+    /// Constructor for unit counts.
+    pub fn new(elevators: usize, escalators: usize) -> Self {
+        UnitCounts {
+            elevators,
+            escalators,
+        }
+    }
+
+    fn add(&mut self, unit_type: &UnitType) {
+        match unit_type {
+            UnitType::Elevator => self.elevators += 1,
+            UnitType::Escalator => self.escalators += 1,
+            UnitType::Other(_) => {}
+        }
+    }
+}
+
+/// Units out of service at a single station.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StationUnitSummary {
+    /// [`Station`] of the units.
+    pub station: Station,
+    /// Units out of service at this station.
+    pub out_of_service: UnitCounts,
+    /// Units at this station, when known.
+    pub total: Option<UnitCounts>,
+}
+
+impl fmt::Display for StationUnitSummary {
+    /// Formats this summary as, for example, `A01: 2 of 6 elevators down, 1 of 12 escalators down`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total {
+            Some(total) => write!(
+                f,
+                "{}: {} of {} elevators down, {} of {} escalators down",
+                self.station.to_string(),
+                self.out_of_service.elevators,
+                total.elevators,
+                self.out_of_service.escalators,
+                total.escalators
+            ),
+            None => write!(
+                f,
+                "{}: {} elevators down, {} escalators down",
+                self.station.to_string(),
+                self.out_of_service.elevators,
+                self.out_of_service.escalators
+            ),
+        }
+    }
+}
+
+impl ElevatorAndEscalatorIncidents {
+    /// Units out of service, per station, in the order stations first appear in these incidents.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::rail::client::responses::ElevatorAndEscalatorIncidents;
+    ///
+    /// let incidents: ElevatorAndEscalatorIncidents = serde_json::from_str(r#"{"ElevatorIncidents": []}"#).unwrap();
+    /// assert!(incidents.summary().is_empty());
+    /// ```
+    pub fn summary(&self) -> Vec<StationUnitSummary> {
+        self.summary_with_totals(&HashMap::new())
+    }
+
+    /// Units out of service, per station, along with the number of units at each station.
+    /// WMATA does not report the number of units at a station, so totals must be provided.
+    pub fn summary_with_totals(
+        &self,
+        totals: &HashMap<Station, UnitCounts>,
+    ) -> Vec<StationUnitSummary> {
+        let mut summaries: Vec<StationUnitSummary> = vec![];
+
+        for incident in self.incidents.iter() {
+            let index = match summaries
+                .iter()
+                .position(|summary| summary.station == incident.station)
+            {
+                Some(index) => index,
+                None => {
+                    summaries.push(StationUnitSummary {
+                        station: incident.station,
+                        out_of_service: UnitCounts::default(),
+                        total: totals.get(&incident.station).copied(),
+                    });
+
+                    summaries.len() - 1
+                }
+            };
+
+            summaries[index].out_of_service.add(&incident.unit_type);
+        }
+
+        summaries
+    }
+}
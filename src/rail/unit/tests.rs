@@ -0,0 +1,137 @@
+//! Tests for elevator and escalator units
+#[cfg(test)]
+use super::*;
+#[cfg(test)]
+use chrono::NaiveTime;
+
+#[cfg(test)]
+use crate::rail::fixtures::{incident, incidents};
+
+#[test]
+fn test_typed_fields() {
+    let incident = incident()
+        .unit_type("ESCALATOR")
+        .estimated_return(r#""2019-10-02T08:15:00""#)
+        .build();
+
+    assert_eq!(incident.unit_type, UnitType::Escalator);
+    assert_eq!(
+        incident.time_out_of_service,
+        NaiveTime::from_hms_opt(8, 15, 0)
+    );
+    assert_eq!(incident.symptom_category(), SymptomCategory::MinorRepair);
+    assert_eq!(
+        incident.estimated_outage_duration(),
+        Some(Duration::days(1))
+    );
+}
+
+#[test]
+fn test_serialize_round_trip() {
+    let incident = incident()
+        .estimated_return(r#""2019-10-02T08:15:00""#)
+        .build();
+
+    let serialized = serde_json::to_string(&incident).unwrap();
+
+    assert!(serialized.contains(r#""UnitType":"ELEVATOR""#));
+    assert!(serialized.contains(r#""StationCode":"A01""#));
+    assert!(serialized.contains(r#""TimeOutOfService":"0815""#));
+    assert!(serialized.contains(r#""EstimatedReturnToService":"2019-10-02T08:15:00""#));
+    assert_eq!(
+        serde_json::from_str::<ElevatorAndEscalatorIncident>(&serialized).unwrap(),
+        incident
+    );
+}
+
+#[test]
+fn test_unknown_unit_type() {
+    let unit = incident().unit_type("STAIRS").build();
+
+    assert_eq!(unit.unit_type, UnitType::Other("STAIRS".to_string()));
+    assert_eq!(unit.unit_type.to_string(), "STAIRS");
+    assert_eq!(
+        incidents(vec![incident().unit_type("STAIRS")]).summary()[0].out_of_service,
+        UnitCounts::new(0, 0)
+    );
+}
+
+#[test]
+fn test_missing_estimated_return_to_service() {
+    let incident = incident().build();
+
+    assert_eq!(incident.estimated_return_to_service, None);
+    assert_eq!(incident.estimated_outage_duration(), None);
+}
+
+#[test]
+fn test_invalid_estimated_return_to_service() {
+    let incident = incident().estimated_return(r#""soon""#).build();
+
+    assert_eq!(incident.estimated_return_to_service, None);
+}
+
+#[test]
+fn test_invalid_time_out_of_service() {
+    for time in &[r#""8:15 AM""#, r#""""#, "null"] {
+        let incident = incident().time_out_of_service(time).build();
+
+        assert_eq!(incident.time_out_of_service, None);
+    }
+}
+
+#[test]
+fn test_outage_duration() {
+    let incident = incident().build();
+    let now = incident.date_out_of_service + Duration::hours(3);
+
+    assert_eq!(incident.outage_duration(now), Duration::hours(3));
+}
+
+#[test]
+fn test_unit_status() {
+    assert_eq!(UnitStatus::from("O"), UnitStatus::OutOfService);
+    assert_eq!(UnitStatus::from("X"), UnitStatus::Other("X".to_string()));
+}
+
+#[test]
+fn test_symptom_category() {
+    assert_eq!(
+        SymptomCategory::from("Preventive Maintenance Inspection"),
+        SymptomCategory::PreventiveMaintenance
+    );
+    assert_eq!(
+        SymptomCategory::from("Fire Alarm"),
+        SymptomCategory::Other("Fire Alarm".to_string())
+    );
+}
+
+#[test]
+fn test_summary() {
+    let incidents = incidents(vec![
+        incident(),
+        incident(),
+        incident().unit_type("ESCALATOR").station("C01"),
+    ]);
+    let summary = incidents.summary();
+
+    assert_eq!(summary.len(), 2);
+    assert_eq!(summary[0].out_of_service, UnitCounts::new(2, 0));
+    assert_eq!(
+        summary[0].to_string(),
+        "A01: 2 elevators down, 0 escalators down"
+    );
+}
+
+#[test]
+fn test_summary_with_totals() {
+    let incidents = incidents(vec![incident(), incident()]);
+
+    let mut totals = HashMap::new();
+    totals.insert(Station::A01, UnitCounts::new(6, 12));
+
+    assert_eq!(
+        incidents.summary_with_totals(&totals)[0].to_string(),
+        "A01: 2 of 6 elevators down, 0 of 12 escalators down"
+    );
+}
@@ -1,4 +1,5 @@
 //! URLs of MetroRail-related endpoints
+#[derive(Debug, Copy, Clone)]
 pub enum URLs {
     NextTrains,
     Information,
@@ -16,6 +17,27 @@ pub enum URLs {
     Stations,
 }
 
+impl URLs {
+    /// Every endpoint, for naming a request by its URL.
+    #[cfg(feature = "tracing")]
+    pub const ALL: [URLs; 14] = [
+        URLs::NextTrains,
+        URLs::Information,
+        URLs::ParkingInformation,
+        URLs::Path,
+        URLs::Timings,
+        URLs::StationToStation,
+        URLs::Lines,
+        URLs::Entrances,
+        URLs::Positions,
+        URLs::Routes,
+        URLs::Circuits,
+        URLs::ElevatorAndEscalatorIncidents,
+        URLs::Incidents,
+        URLs::Stations,
+    ];
+}
+
 impl ToString for URLs {
     fn to_string(&self) -> String {
         match self {
@@ -1,10 +1,21 @@
 //! Internal requests structs and traits.
-use crate::error::{Error, ErrorResponse};
+use crate::{
+    cache::{Cache, CachePolicy},
+    drift::Recorder,
+    error::{Error, ErrorResponse},
+    keys::KeyPool,
+    limit::RateLimit,
+    retry::RetryPolicy,
+};
 
 use async_trait::async_trait;
-use reqwest;
+use reqwest::{self, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json;
+use std::sync::Arc;
+
+/// Base URL of the WMATA API. Endpoint URLs start with this.
+pub const WMATA_API: &str = "https://api.wmata.com";
 
 pub struct Request<'a> {
     pub api_key: &'a str,
@@ -22,19 +33,276 @@ impl<'a> Request<'a> {
     }
 }
 
+/// HTTP client, keys, and rate limit, retry, and cache policies requests are sent through.
+/// Clones share a connection pool, key pool, rate limit, and cache, as do the views of one [`crate::Wmata`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Transport {
+    pub http: reqwest::Client,
+    /// Keys sent in place of the request's own, if any.
+    pub keys: Option<KeyPool>,
+    pub rate_limit: Option<Arc<RateLimit>>,
+    pub retry: Option<RetryPolicy>,
+    pub cache: Option<(CachePolicy, Arc<Cache>)>,
+}
+
+impl Transport {
+    /// Sends a request, waiting for the rate limit and retrying as allowed.
+    /// Returns the status and body of the last response.
+    async fn send(&self, wmata_request: &Request<'_>) -> Result<(StatusCode, String), Error> {
+        self.send_bytes(wmata_request)
+            .await
+            .map(|(status, body)| (status, String::from_utf8_lossy(&body).into_owned()))
+    }
+
+    /// Sends a request like [`Transport::send`], returning the body as bytes.
+    async fn send_bytes(
+        &self,
+        wmata_request: &Request<'_>,
+    ) -> Result<(StatusCode, Vec<u8>), Error> {
+        let mut retry = 0;
+        let mut rejections = 0;
+
+        loop {
+            if let Some(rate_limit) = &self.rate_limit {
+                rate_limit.acquire().await.map_err(|wait| {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(wait_ms = wait.as_millis() as u64, "rate limit exceeded");
+
+                    Error::new(format!(
+                        "Rate limit exceeded. Try again in {:.1} seconds.",
+                        wait.as_secs_f64()
+                    ))
+                })?;
+            }
+
+            let key = self.keys.as_ref().map(|keys| (keys, keys.acquire()));
+            let api_key = key.map_or(wmata_request.api_key, |(keys, index)| keys.key(index));
+
+            let mut request = self.http.get(wmata_request.path).header("api_key", api_key);
+
+            if let Some(query) = &wmata_request.query {
+                request = request.query(query)
+            }
+
+            let (response, retryable) = match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    #[cfg(feature = "tracing")]
+                    tracing::Span::current().record("status", u64::from(status.as_u16()));
+
+                    match response.bytes().await {
+                        Ok(body) => (
+                            Ok((status, body.to_vec())),
+                            RetryPolicy::retries_status(status.as_u16()),
+                        ),
+                        Err(err) => (Err(Error::new(err.to_string())), true),
+                    }
+                }
+                Err(err) => (Err(Error::new(err.to_string())), true),
+            };
+
+            // A rejected key is out of rotation, so retry with another, once per key
+            if let (Some((keys, index)), Ok((status, body))) = (key, &response) {
+                if keys.record(index, status.as_u16(), &String::from_utf8_lossy(body))
+                    && rejections + 1 < keys.len()
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        key = index as u64,
+                        status = u64::from(status.as_u16()),
+                        "API key rejected, retrying with another"
+                    );
+
+                    rejections += 1;
+                    continue;
+                }
+            }
+
+            match self.retry {
+                Some(policy) if retryable && retry < policy.retries => {
+                    retry += 1;
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(
+                        retry,
+                        backoff_ms = policy.backoff(retry).as_millis() as u64,
+                        "retrying request"
+                    );
+
+                    tokio::time::delay_for(policy.backoff(retry)).await;
+                }
+                _ => return response,
+            }
+        }
+    }
+}
+
+/// Key a request's response is cached under. The API key is left out, since responses don't depend on it.
+fn cache_key(wmata_request: &Request<'_>) -> String {
+    let query: Vec<String> = wmata_request
+        .query
+        .iter()
+        .flatten()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    format!("{}?{}", wmata_request.path, query.join("&"))
+}
+
 /// A trait indicating the implementor can request and deserialize data
 /// from the WMATA API.
 #[async_trait]
 pub trait Fetch: Requester + Deserializer {
+    /// Base URL requests are sent to, in place of [`WMATA_API`].
+    fn base_url(&self) -> &str {
+        WMATA_API
+    }
+
+    /// HTTP client and rate limit to send requests through. A new client without a rate limit, if none.
+    fn transport(&self) -> Option<&Transport> {
+        None
+    }
+
+    /// Cassette recording or replaying requests, if any.
+    #[cfg(feature = "testing")]
+    fn cassette(&self) -> Option<&crate::testing::Cassette> {
+        None
+    }
+
+    /// Recorder of schema drift, if deserializing strictly.
+    fn drift_recorder(&self) -> Option<&Recorder> {
+        None
+    }
+
+    /// Deserializes a response, strictly if recording drift.
+    fn deserialize_response<U>(&self, response: String) -> Result<U, Error>
+    where
+        U: DeserializeOwned,
+    {
+        match self.drift_recorder() {
+            // Error responses aren't drift
+            Some(recorder) if serde_json::from_str::<ErrorResponse>(&response).is_err() => {
+                recorder.deserialize(&response)
+            }
+            _ => Self::deserialize(response),
+        }
+    }
+
     // / Requests and deserializes JSON data from a WMATA endpoint.
     // / Used internally by MetroRail and MetroBus clients.
     async fn fetch<U>(&self, wmata_request: Request<'_>) -> Result<U, Error>
     where
         U: DeserializeOwned,
     {
-        self.request(wmata_request)
+        #[cfg(feature = "tracing")]
+        let fetched = {
+            use tracing::Instrument;
+
+            let span = crate::trace::span(
+                &wmata_request,
+                self.transport()
+                    .and_then(|transport| transport.keys.as_ref()),
+            );
+            let started = std::time::Instant::now();
+            let response = self
+                .fetch_response(wmata_request)
+                .instrument(span.clone())
+                .await;
+            crate::trace::response(&span, &response);
+
+            let fetched = response.and_then(|response| self.deserialize_response(response));
+            crate::trace::finish(&span, started, fetched.as_ref().err());
+
+            fetched
+        };
+
+        #[cfg(not(feature = "tracing"))]
+        let fetched = self
+            .fetch_response(wmata_request)
             .await
-            .and_then(Self::deserialize)
+            .and_then(|response| self.deserialize_response(response));
+
+        fetched
+    }
+
+    /// Requests JSON data from a WMATA endpoint, through the cassette if there is one.
+    async fn fetch_response(&self, wmata_request: Request<'_>) -> Result<String, Error> {
+        let path = wmata_request
+            .path
+            .replacen(WMATA_API, self.base_url().trim_end_matches('/'), 1);
+        let transport = self.transport().cloned().unwrap_or_default();
+
+        #[cfg(feature = "testing")]
+        {
+            if let Some(cassette) = self.cassette() {
+                use crate::testing::cassette::Mode;
+
+                let endpoint = wmata_request.path.trim_start_matches(WMATA_API);
+                let query: Vec<(String, String)> = wmata_request
+                    .query
+                    .iter()
+                    .flatten()
+                    .map(|(key, value)| (key.to_string(), value.clone()))
+                    .collect();
+
+                let response = match cassette.mode() {
+                    Mode::Replay => cassette.play(endpoint, &query)?,
+                    Mode::Record => {
+                        let mut api_keys = vec![wmata_request.api_key];
+                        api_keys.extend(transport.keys.iter().flat_map(KeyPool::keys));
+                        let response = self
+                            .request(
+                                &transport,
+                                Request {
+                                    path: &path,
+                                    ..wmata_request
+                                },
+                            )
+                            .await?;
+                        cassette.save(endpoint, &query, &response, &api_keys)?;
+                        response
+                    }
+                };
+
+                return Ok(response);
+            }
+        }
+
+        self.request(
+            &transport,
+            Request {
+                path: &path,
+                ..wmata_request
+            },
+        )
+        .await
+    }
+
+    /// Requests binary data (e.g.: a GTFS Realtime feed) from a WMATA endpoint.
+    /// Responses aren't cached or recorded to the cassette.
+    #[cfg(feature = "gtfs-rt")]
+    async fn fetch_bytes(&self, wmata_request: Request<'_>) -> Result<Vec<u8>, Error> {
+        let path = wmata_request
+            .path
+            .replacen(WMATA_API, self.base_url().trim_end_matches('/'), 1);
+        let transport = self.transport().cloned().unwrap_or_default();
+
+        let (status, body) = transport
+            .send_bytes(&Request {
+                path: &path,
+                ..wmata_request
+            })
+            .await?;
+
+        if !status.is_success() {
+            return Err(match serde_json::from_slice::<ErrorResponse>(&body) {
+                Ok(json) => Error::new(json.message.to_string()),
+                Err(_) => Error::new(status.to_string()),
+            });
+        }
+
+        Ok(body)
     }
 }
 
@@ -43,24 +311,42 @@ pub trait Fetch: Requester + Deserializer {
 #[async_trait]
 pub trait Requester {
     /// Requests data JSON data from a WMATA endpoint.
-    async fn request(&self, wmata_request: Request<'_>) -> Result<String, Error> {
-        let mut request = reqwest::Client::new().get(wmata_request.path);
-
-        if let Some(query) = wmata_request.query {
-            request = request.query(&query)
-        }
+    async fn request(
+        &self,
+        transport: &Transport,
+        wmata_request: Request<'_>,
+    ) -> Result<String, Error> {
+        let (policy, cache) = match &transport.cache {
+            Some(cache) => cache,
+            None => return transport.send(&wmata_request).await.map(|(_, body)| body),
+        };
 
-        let response = request
-            .header("api_key", wmata_request.api_key)
-            .send()
+        let cached = cache
+            .get_or_fetch(
+                &cache_key(&wmata_request),
+                policy.ttl_for(wmata_request.path),
+                || async {
+                    // Only successful responses are cached. Others are passed through as the error
+                    match transport.send(&wmata_request).await {
+                        Ok((status, body)) if status.is_success() => Ok(body.into_bytes()),
+                        response => Err(response),
+                    }
+                },
+            )
             .await;
 
-        match response {
-            Ok(response) => response
-                .text()
-                .await
-                .map_err(|err| Error::new(err.to_string())),
-            Err(err) => Err(Error::new(err.to_string())),
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record(
+            "cache",
+            match &cached {
+                Ok(cached) if cached.hit => "hit",
+                _ => "miss",
+            },
+        );
+
+        match cached {
+            Ok(cached) => Ok(String::from_utf8_lossy(&cached.body).into_owned()),
+            Err(response) => response.map(|(_, body)| body),
         }
     }
 }
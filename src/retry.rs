@@ -0,0 +1,60 @@
+//! Retrying of requests which failed for reasons likely to pass, like timeouts,
+//! WMATA's rate limit, and server errors.
+mod tests;
+
+use std::time::Duration;
+
+/// How a client retries failed requests. See [`crate::builder::Builder::retry`].
+///
+/// Requests are retried after connection errors, timeouts, and responses with a
+/// status of 429 Too Many Requests or 5xx, waiting twice as long before each retry as the last.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub retries: u32,
+    /// Wait before the first retry.
+    pub backoff: Duration,
+    /// Longest wait before any retry.
+    pub maximum_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy retrying up to `retries` times, waiting `backoff` before the first retry.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use wmata::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new(3, Duration::from_millis(250));
+    /// ```
+    pub fn new(retries: u32, backoff: Duration) -> Self {
+        RetryPolicy {
+            retries,
+            backoff,
+            maximum_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Wait before retry number `retry`, counting from 1.
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+
+        self.backoff
+            .checked_mul(factor)
+            .unwrap_or(self.maximum_backoff)
+            .min(self.maximum_backoff)
+    }
+
+    /// Whether a response with `status` is worth retrying.
+    pub(crate) fn retries_status(status: u16) -> bool {
+        status == 429 || (500..600).contains(&status)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Up to 3 retries, waiting 500ms before the first.
+    fn default() -> Self {
+        RetryPolicy::new(3, Duration::from_millis(500))
+    }
+}
@@ -0,0 +1,29 @@
+//! Tests for retrying requests
+#[cfg(test)]
+use super::*;
+
+#[test]
+fn test_backoff() {
+    let policy = RetryPolicy::new(5, Duration::from_millis(100));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+}
+
+#[test]
+fn test_maximum_backoff() {
+    let policy = RetryPolicy::new(100, Duration::from_secs(1));
+
+    assert_eq!(policy.backoff(6), Duration::from_secs(30));
+    assert_eq!(policy.backoff(100), Duration::from_secs(30));
+}
+
+#[test]
+fn test_retries_status() {
+    assert!(RetryPolicy::retries_status(429));
+    assert!(RetryPolicy::retries_status(503));
+    assert!(!RetryPolicy::retries_status(400));
+    assert!(!RetryPolicy::retries_status(401));
+    assert!(!RetryPolicy::retries_status(200));
+}
@@ -0,0 +1,255 @@
+//! Nearest-neighbor and radius search over anything with a location, without a network round-trip.
+//!
+//! Build a [`SpatialIndex`] once from a cached response (e.g.: every station, every bus stop, or a set of
+//! station entrances), then query it as often as needed.
+mod tests;
+
+use crate::location::{LatLong, RadiusAtLatLong, MAXIMUM_BOUNDED_LATITUDE, METERS_PER_DEGREE};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// Default size, in degrees, of each grid cell. Roughly a kilometer.
+pub const DEFAULT_CELL_SIZE: f64 = 0.01;
+
+/// An item found by a [`SpatialIndex`] search.
+#[derive(Debug, PartialEq)]
+pub struct Neighbor<'a, T> {
+    /// The item found.
+    pub item: &'a T,
+    /// Distance (in meters) from the searched point to the item.
+    pub distance: f64,
+}
+
+/// A geohash-style grid over items with a location.
+#[derive(Debug)]
+pub struct SpatialIndex<T> {
+    items: Vec<T>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    cell_size: f64,
+    maximum_latitude: f64,
+    bounds: Bounds,
+}
+
+/// Smallest and largest occupied rows and columns of a [`SpatialIndex`]'s grid.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    minimum_row: i64,
+    minimum_column: i64,
+    maximum_row: i64,
+    maximum_column: i64,
+}
+
+impl<T> SpatialIndex<T>
+where
+    T: LatLong,
+{
+    /// Constructor for an index with the default cell size.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{RadiusAtLatLong, spatial::SpatialIndex};
+    ///
+    /// let index = SpatialIndex::new(vec![
+    ///     RadiusAtLatLong::new(0, 38.8983144732, -77.0280779971),
+    ///     RadiusAtLatLong::new(0, 38.9032808059, -77.0397008698),
+    /// ]);
+    ///
+    /// let nearest = index.nearest(&RadiusAtLatLong::new(0, 38.8983, -77.0281), 1);
+    /// assert_eq!(nearest[0].item.latitude, 38.8983144732);
+    /// ```
+    pub fn new(items: Vec<T>) -> Self {
+        SpatialIndex::with_cell_size(items, DEFAULT_CELL_SIZE)
+    }
+
+    /// Constructor for an index with a given cell size, in degrees.
+    /// Smaller cells are faster for dense data and small radii.
+    pub fn with_cell_size(items: Vec<T>, cell_size: f64) -> Self {
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        let mut maximum_latitude: f64 = 0.0;
+        let mut bounds = Bounds {
+            minimum_row: i64::MAX,
+            minimum_column: i64::MAX,
+            maximum_row: i64::MIN,
+            maximum_column: i64::MIN,
+        };
+
+        for (index, item) in items.iter().enumerate() {
+            let (row, column) = cell_of(item, cell_size);
+            cells.entry((row, column)).or_default().push(index);
+
+            maximum_latitude = maximum_latitude.max(item.latitude().abs());
+            bounds.minimum_row = bounds.minimum_row.min(row);
+            bounds.minimum_column = bounds.minimum_column.min(column);
+            bounds.maximum_row = bounds.maximum_row.max(row);
+            bounds.maximum_column = bounds.maximum_column.max(column);
+        }
+
+        SpatialIndex {
+            items,
+            cells,
+            cell_size,
+            maximum_latitude,
+            bounds,
+        }
+    }
+
+    /// Every item in this index.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Up to `k` items nearest to the given point, closest first.
+    pub fn nearest<P>(&self, point: &P, k: usize) -> Vec<Neighbor<'_, T>>
+    where
+        P: LatLong,
+    {
+        if k == 0 || self.items.is_empty() {
+            return vec![];
+        }
+
+        let (row, column) = cell_of(point, self.cell_size);
+        let ring_distance = self.cell_size
+            * METERS_PER_DEGREE
+            * self
+                .maximum_latitude
+                .max(point.latitude().abs())
+                .min(MAXIMUM_BOUNDED_LATITUDE)
+                .to_radians()
+                .cos();
+        // Rings closer than the occupied cells are empty, and rings farther than all of them are too
+        let bounds = self.bounds;
+        let minimum_ring = (bounds.minimum_row - row)
+            .max(row - bounds.maximum_row)
+            .max(bounds.minimum_column - column)
+            .max(column - bounds.maximum_column)
+            .max(0);
+        let maximum_ring = (row - bounds.minimum_row)
+            .abs()
+            .max((row - bounds.maximum_row).abs())
+            .max((column - bounds.minimum_column).abs())
+            .max((column - bounds.maximum_column).abs());
+
+        let mut neighbors: Vec<Neighbor<'_, T>> = vec![];
+
+        for ring in minimum_ring..=maximum_ring {
+            for cell in ring_cells(row, column, ring, &bounds) {
+                if let Some(indices) = self.cells.get(&cell) {
+                    neighbors.extend(indices.iter().map(|index| Neighbor {
+                        item: &self.items[*index],
+                        distance: point.distance_to(&self.items[*index]),
+                    }));
+                }
+            }
+
+            if neighbors.len() >= k {
+                sort_by_distance(&mut neighbors);
+
+                // Anything outside of this ring is at least this far away
+                if neighbors[k - 1].distance <= ring as f64 * ring_distance {
+                    break;
+                }
+            }
+        }
+
+        sort_by_distance(&mut neighbors);
+        neighbors.truncate(k);
+
+        neighbors
+    }
+
+    /// Every item within a radius of a point, closest first.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::{RadiusAtLatLong, spatial::SpatialIndex};
+    ///
+    /// let index = SpatialIndex::new(vec![RadiusAtLatLong::new(0, 38.8983144732, -77.0280779971)]);
+    ///
+    /// assert_eq!(index.within(&RadiusAtLatLong::new(1000, 38.8983, -77.0281)).len(), 1);
+    /// assert!(index.within(&RadiusAtLatLong::new(1000, 38.95, -77.0281)).is_empty());
+    /// ```
+    pub fn within(&self, radius_at_lat_long: &RadiusAtLatLong) -> Vec<Neighbor<'_, T>> {
+        let radius = f64::from(radius_at_lat_long.radius);
+        let bounding_box = radius_at_lat_long.bounding_box();
+
+        let (minimum_row, minimum_column) = cell_of(
+            &(
+                bounding_box.minimum_latitude,
+                bounding_box.minimum_longitude,
+            ),
+            self.cell_size,
+        );
+        let (maximum_row, maximum_column) = cell_of(
+            &(
+                bounding_box.maximum_latitude,
+                bounding_box.maximum_longitude,
+            ),
+            self.cell_size,
+        );
+
+        let mut neighbors = vec![];
+
+        for row in minimum_row..=maximum_row {
+            for column in minimum_column..=maximum_column {
+                if let Some(indices) = self.cells.get(&(row, column)) {
+                    for index in indices {
+                        let item = &self.items[*index];
+                        let distance = radius_at_lat_long.distance_to(item);
+
+                        if distance <= radius {
+                            neighbors.push(Neighbor { item, distance });
+                        }
+                    }
+                }
+            }
+        }
+
+        sort_by_distance(&mut neighbors);
+
+        neighbors
+    }
+}
+
+fn cell_of<P>(point: &P, cell_size: f64) -> (i64, i64)
+where
+    P: LatLong + ?Sized,
+{
+    (
+        (point.latitude() / cell_size).floor() as i64,
+        (point.longitude() / cell_size).floor() as i64,
+    )
+}
+
+/// Cells exactly `ring` cells away from the given cell, within `bounds`.
+fn ring_cells(row: i64, column: i64, ring: i64, bounds: &Bounds) -> Vec<(i64, i64)> {
+    let rows = (row - ring).max(bounds.minimum_row)..=(row + ring).min(bounds.maximum_row);
+    let columns =
+        (column - ring).max(bounds.minimum_column)..=(column + ring).min(bounds.maximum_column);
+
+    let mut cells = vec![];
+
+    for other_row in rows {
+        if (other_row - row).abs() == ring {
+            cells.extend(
+                columns
+                    .clone()
+                    .map(|other_column| (other_row, other_column)),
+            );
+        } else {
+            for other_column in [column - ring, column + ring].iter() {
+                if columns.contains(other_column) {
+                    cells.push((other_row, *other_column));
+                }
+            }
+        }
+    }
+
+    cells
+}
+
+fn sort_by_distance<T>(neighbors: &mut [Neighbor<'_, T>]) {
+    neighbors.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(Ordering::Equal)
+    });
+}
@@ -0,0 +1,104 @@
+//! Tests for the spatial index
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+fn stations() -> Vec<(f64, f64)> {
+    vec![
+        // Metro Center
+        (38.8983144732, -77.0280779971),
+        // Farragut North
+        (38.9032808059, -77.0397008698),
+        // Dupont Circle
+        (38.9095980575, -77.0434143597),
+        // Shady Grove
+        (39.1199273249, -77.1646273343),
+    ]
+}
+
+#[test]
+fn test_distance() {
+    // Metro Center to Farragut North is a little over a kilometer
+    let distance = stations()[0].distance_to(&stations()[1]);
+
+    assert!(distance > 1100.0 && distance < 1200.0);
+}
+
+#[test]
+fn test_nearest() {
+    let index = SpatialIndex::new(stations());
+    let nearest = index.nearest(&(38.9090, -77.0430), 2);
+
+    assert_eq!(nearest.len(), 2);
+    assert_eq!(*nearest[0].item, stations()[2]);
+    assert_eq!(*nearest[1].item, stations()[1]);
+    assert!(nearest[0].distance <= nearest[1].distance);
+}
+
+#[test]
+fn test_nearest_far_away() {
+    let index = SpatialIndex::new(stations());
+    let nearest = index.nearest(&(39.2, -77.2), 1);
+
+    assert_eq!(*nearest[0].item, stations()[3]);
+}
+
+#[test]
+fn test_nearest_very_far_away() {
+    // Thousands of kilometers of rings from the nearest station, which are skipped
+    let index = SpatialIndex::with_cell_size(stations(), 0.0001);
+    let nearest = index.nearest(&(0.0, 0.0), 2);
+
+    assert_eq!(*nearest[0].item, stations()[0]);
+    assert_eq!(*nearest[1].item, stations()[1]);
+}
+
+#[test]
+fn test_nearest_more_than_available() {
+    let index = SpatialIndex::new(stations());
+
+    assert_eq!(index.nearest(&(38.9, -77.0), 10).len(), 4);
+    assert!(index.nearest(&(38.9, -77.0), 0).is_empty());
+}
+
+#[test]
+fn test_nearest_matches_brute_force() {
+    let index = SpatialIndex::with_cell_size(stations(), 0.001);
+    let point = (38.95, -77.1);
+
+    let mut expected = stations();
+    expected.sort_by(|a, b| {
+        point
+            .distance_to(a)
+            .partial_cmp(&point.distance_to(b))
+            .unwrap()
+    });
+
+    let nearest: Vec<(f64, f64)> = index
+        .nearest(&point, 4)
+        .iter()
+        .map(|neighbor| *neighbor.item)
+        .collect();
+
+    assert_eq!(nearest, expected);
+}
+
+#[test]
+fn test_within() {
+    let index = SpatialIndex::new(stations());
+    let within = index.within(&RadiusAtLatLong::new(1500, 38.9032808059, -77.0397008698));
+
+    assert_eq!(within.len(), 3);
+    assert_eq!(*within[0].item, stations()[1]);
+    assert_eq!(within[0].distance, 0.0);
+}
+
+#[test]
+fn test_within_empty() {
+    let index: SpatialIndex<(f64, f64)> = SpatialIndex::new(vec![]);
+
+    assert!(index
+        .within(&RadiusAtLatLong::new(1000, 38.9, -77.0))
+        .is_empty());
+    assert!(index.nearest(&(38.9, -77.0), 1).is_empty());
+}
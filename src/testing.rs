@@ -0,0 +1,656 @@
+//! A fake WMATA API, for testing code built on this crate without network access or live data.
+//!
+//! [`FakeServer`] listens on a local port and serves fixture JSON for every endpoint this crate calls.
+//! Requests are checked the way WMATA checks them: the `api_key` header must match the server's key,
+//! and query parameters must be known and well formed. Responses can be scripted per endpoint,
+//! to test error handling and slow responses.
+//!
+//! # Example
+//! ```
+//! use wmata::testing::FakeServer;
+//! use tokio_test::block_on;
+//!
+//! let server = FakeServer::start();
+//! let lines = block_on(async { server.rail().lines().await }).unwrap();
+//!
+//! assert_eq!(lines.lines.len(), 6);
+//! ```
+pub mod cassette;
+mod tests;
+
+pub use cassette::Cassette;
+
+use crate::{
+    bus::urls::URLs as BusURLs, rail::urls::URLs as RailURLs, requests::WMATA_API, Line, MetroBus,
+    MetroRail, Route, Station, Wmata,
+};
+use chrono::NaiveDate;
+use hyper::{
+    header::{HeaderValue, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// API key a [`FakeServer`] accepts, unless started with [`FakeServer::with_api_key`].
+pub const API_KEY: &str = "0123456789abcdef0123456789abcdef";
+
+/// An endpoint served by [`FakeServer`]. One for each WMATA endpoint this crate calls.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    NextTrains,
+    StationInformation,
+    ParkingInformation,
+    Path,
+    Timings,
+    StationToStation,
+    Lines,
+    Entrances,
+    TrainPositions,
+    StandardRoutes,
+    TrackCircuits,
+    ElevatorAndEscalatorIncidents,
+    RailIncidents,
+    Stations,
+    BusRoutes,
+    Stops,
+    BusIncidents,
+    BusPositions,
+    PathDetails,
+    RouteSchedule,
+    NextBuses,
+    StopSchedule,
+}
+
+const ENDPOINTS: [Endpoint; 22] = [
+    Endpoint::NextTrains,
+    Endpoint::StationInformation,
+    Endpoint::ParkingInformation,
+    Endpoint::Path,
+    Endpoint::Timings,
+    Endpoint::StationToStation,
+    Endpoint::Lines,
+    Endpoint::Entrances,
+    Endpoint::TrainPositions,
+    Endpoint::StandardRoutes,
+    Endpoint::TrackCircuits,
+    Endpoint::ElevatorAndEscalatorIncidents,
+    Endpoint::RailIncidents,
+    Endpoint::Stations,
+    Endpoint::BusRoutes,
+    Endpoint::Stops,
+    Endpoint::BusIncidents,
+    Endpoint::BusPositions,
+    Endpoint::PathDetails,
+    Endpoint::RouteSchedule,
+    Endpoint::NextBuses,
+    Endpoint::StopSchedule,
+];
+
+/// What a query parameter's value must be.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Kind {
+    Station,
+    Line,
+    Route,
+    Stop,
+    Date,
+    Bool,
+    Latitude,
+    Longitude,
+    Radius,
+    Json,
+}
+
+/// A query parameter accepted by an endpoint.
+struct Parameter {
+    name: &'static str,
+    kind: Kind,
+    required: bool,
+}
+
+const fn required(name: &'static str, kind: Kind) -> Parameter {
+    Parameter {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, kind: Kind) -> Parameter {
+    Parameter {
+        name,
+        kind,
+        required: false,
+    }
+}
+
+impl Endpoint {
+    /// Path of this endpoint, e.g.: `/Rail.svc/json/jLines`.
+    /// Next trains are requested from this path followed by `/` and the station codes.
+    pub fn path(&self) -> String {
+        let url = match self {
+            Endpoint::NextTrains => RailURLs::NextTrains.to_string(),
+            Endpoint::StationInformation => RailURLs::Information.to_string(),
+            Endpoint::ParkingInformation => RailURLs::ParkingInformation.to_string(),
+            Endpoint::Path => RailURLs::Path.to_string(),
+            Endpoint::Timings => RailURLs::Timings.to_string(),
+            Endpoint::StationToStation => RailURLs::StationToStation.to_string(),
+            Endpoint::Lines => RailURLs::Lines.to_string(),
+            Endpoint::Entrances => RailURLs::Entrances.to_string(),
+            Endpoint::TrainPositions => RailURLs::Positions.to_string(),
+            Endpoint::StandardRoutes => RailURLs::Routes.to_string(),
+            Endpoint::TrackCircuits => RailURLs::Circuits.to_string(),
+            Endpoint::ElevatorAndEscalatorIncidents => {
+                RailURLs::ElevatorAndEscalatorIncidents.to_string()
+            }
+            Endpoint::RailIncidents => RailURLs::Incidents.to_string(),
+            Endpoint::Stations => RailURLs::Stations.to_string(),
+            Endpoint::BusRoutes => BusURLs::Routes.to_string(),
+            Endpoint::Stops => BusURLs::Stops.to_string(),
+            Endpoint::BusIncidents => BusURLs::Incidents.to_string(),
+            Endpoint::BusPositions => BusURLs::Positions.to_string(),
+            Endpoint::PathDetails => BusURLs::PathDetails.to_string(),
+            Endpoint::RouteSchedule => BusURLs::RouteSchedule.to_string(),
+            Endpoint::NextBuses => BusURLs::NextBuses.to_string(),
+            Endpoint::StopSchedule => BusURLs::StopSchedule.to_string(),
+        };
+
+        url.trim_start_matches(WMATA_API).to_string()
+    }
+
+    /// JSON served for this endpoint, unless a response is scripted.
+    pub fn fixture(&self) -> &'static str {
+        match self {
+            Endpoint::NextTrains => include_str!("testing/fixtures/rail/next_trains.json"),
+            Endpoint::StationInformation => {
+                include_str!("testing/fixtures/rail/station_information.json")
+            }
+            Endpoint::ParkingInformation => {
+                include_str!("testing/fixtures/rail/parking_information.json")
+            }
+            Endpoint::Path => include_str!("testing/fixtures/rail/path.json"),
+            Endpoint::Timings => include_str!("testing/fixtures/rail/timings.json"),
+            Endpoint::StationToStation => {
+                include_str!("testing/fixtures/rail/station_to_station.json")
+            }
+            Endpoint::Lines => include_str!("testing/fixtures/rail/lines.json"),
+            Endpoint::Entrances => include_str!("testing/fixtures/rail/entrances.json"),
+            Endpoint::TrainPositions => include_str!("testing/fixtures/rail/positions.json"),
+            Endpoint::StandardRoutes => include_str!("testing/fixtures/rail/routes.json"),
+            Endpoint::TrackCircuits => include_str!("testing/fixtures/rail/circuits.json"),
+            Endpoint::ElevatorAndEscalatorIncidents => {
+                include_str!("testing/fixtures/rail/elevator_incidents.json")
+            }
+            Endpoint::RailIncidents => include_str!("testing/fixtures/rail/incidents.json"),
+            Endpoint::Stations => include_str!("testing/fixtures/rail/stations.json"),
+            Endpoint::BusRoutes => include_str!("testing/fixtures/bus/routes.json"),
+            Endpoint::Stops => include_str!("testing/fixtures/bus/stops.json"),
+            Endpoint::BusIncidents => include_str!("testing/fixtures/bus/incidents.json"),
+            Endpoint::BusPositions => include_str!("testing/fixtures/bus/positions.json"),
+            Endpoint::PathDetails => include_str!("testing/fixtures/bus/path_details.json"),
+            Endpoint::RouteSchedule => include_str!("testing/fixtures/bus/route_schedule.json"),
+            Endpoint::NextBuses => include_str!("testing/fixtures/bus/next_buses.json"),
+            Endpoint::StopSchedule => include_str!("testing/fixtures/bus/stop_schedule.json"),
+        }
+    }
+
+    /// Query parameters accepted by this endpoint.
+    fn parameters(&self) -> &'static [Parameter] {
+        const STATION: [Parameter; 1] = [required("StationCode", Kind::Station)];
+        const OPTIONAL_STATION: [Parameter; 1] = [optional("StationCode", Kind::Station)];
+        const PATH: [Parameter; 2] = [
+            required("FromStationCode", Kind::Station),
+            required("ToStationCode", Kind::Station),
+        ];
+        const STATION_TO_STATION: [Parameter; 2] = [
+            optional("FromStationCode", Kind::Station),
+            optional("ToStationCode", Kind::Station),
+        ];
+        const RADIUS_AT_LAT_LONG: [Parameter; 3] = [
+            optional("Lat", Kind::Latitude),
+            optional("Lon", Kind::Longitude),
+            optional("Radius", Kind::Radius),
+        ];
+        const JSON: [Parameter; 1] = [required("contentType", Kind::Json)];
+        const LINE: [Parameter; 1] = [optional("LineCode", Kind::Line)];
+        const ROUTE: [Parameter; 1] = [optional("Route", Kind::Route)];
+        const BUS_POSITIONS: [Parameter; 4] = [
+            optional("RouteID", Kind::Route),
+            optional("Lat", Kind::Latitude),
+            optional("Lon", Kind::Longitude),
+            optional("Radius", Kind::Radius),
+        ];
+        const PATH_DETAILS: [Parameter; 2] = [
+            required("RouteID", Kind::Route),
+            optional("Date", Kind::Date),
+        ];
+        const ROUTE_SCHEDULE: [Parameter; 3] = [
+            required("RouteID", Kind::Route),
+            optional("Date", Kind::Date),
+            optional("IncludingVariations", Kind::Bool),
+        ];
+        const STOP: [Parameter; 1] = [required("StopID", Kind::Stop)];
+        const STOP_SCHEDULE: [Parameter; 2] =
+            [required("StopID", Kind::Stop), optional("Date", Kind::Date)];
+
+        match self {
+            Endpoint::NextTrains | Endpoint::Lines | Endpoint::BusRoutes => &[],
+            Endpoint::StationInformation | Endpoint::ParkingInformation | Endpoint::Timings => {
+                &STATION
+            }
+            Endpoint::Path => &PATH,
+            Endpoint::StationToStation => &STATION_TO_STATION,
+            Endpoint::Entrances | Endpoint::Stops => &RADIUS_AT_LAT_LONG,
+            Endpoint::TrainPositions | Endpoint::StandardRoutes | Endpoint::TrackCircuits => &JSON,
+            Endpoint::ElevatorAndEscalatorIncidents | Endpoint::RailIncidents => &OPTIONAL_STATION,
+            Endpoint::Stations => &LINE,
+            Endpoint::BusIncidents => &ROUTE,
+            Endpoint::BusPositions => &BUS_POSITIONS,
+            Endpoint::PathDetails => &PATH_DETAILS,
+            Endpoint::RouteSchedule => &ROUTE_SCHEDULE,
+            Endpoint::NextBuses => &STOP,
+            Endpoint::StopSchedule => &STOP_SCHEDULE,
+        }
+    }
+
+    /// The endpoint at `path`, with the station codes following next trains' path.
+    fn from_path(path: &str) -> Option<(Endpoint, Option<&str>)> {
+        ENDPOINTS.iter().find_map(|endpoint| {
+            let endpoint_path = endpoint.path();
+
+            if *endpoint == Endpoint::NextTrains {
+                path.strip_prefix(&endpoint_path)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(|stations| (*endpoint, Some(stations)))
+            } else if path == endpoint_path {
+                Some((*endpoint, None))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A response served in place of an endpoint's fixture. See [`FakeServer::script`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scripted {
+    status: u16,
+    body: String,
+    latency: Duration,
+}
+
+impl Scripted {
+    /// A successful response with `body` as JSON.
+    pub fn json(body: &str) -> Self {
+        Scripted {
+            status: 200,
+            body: body.to_string(),
+            latency: Duration::from_secs(0),
+        }
+    }
+
+    /// An error response with `status`, and `message` in WMATA's error format.
+    ///
+    /// # Example
+    /// ```
+    /// use wmata::testing::Scripted;
+    ///
+    /// let quota_exceeded = Scripted::error(429, "Rate limit is exceeded.");
+    /// ```
+    pub fn error(status: u16, message: &str) -> Self {
+        Scripted {
+            status,
+            body: serde_json::json!({ "Message": message }).to_string(),
+            latency: Duration::from_secs(0),
+        }
+    }
+
+    /// This response, sent after waiting `latency`.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// An error in the format WMATA's API gateway uses, for bad keys and unknown paths.
+    fn gateway(status: u16, message: &str) -> Self {
+        Scripted {
+            status,
+            body: serde_json::json!({ "statusCode": status, "message": message }).to_string(),
+            latency: Duration::from_secs(0),
+        }
+    }
+}
+
+/// A request received by a [`FakeServer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recorded {
+    /// Path requested, e.g.: `/Rail.svc/json/jLines`.
+    pub path: String,
+    /// Query parameters, in the order sent. Values are codes, numbers, and dates, so aren't percent-decoded.
+    pub query: Vec<(String, String)>,
+    /// Value of the `api_key` header, if sent.
+    pub api_key: Option<String>,
+}
+
+/// State shared between a [`FakeServer`] and the requests it serves.
+struct Shared {
+    api_key: String,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    scripts: HashMap<Endpoint, VecDeque<Scripted>>,
+    latency: Duration,
+    requests: Vec<Recorded>,
+}
+
+/// A fake WMATA API server, running on a local port until dropped.
+///
+/// # Example
+/// ```
+/// use wmata::testing::{Endpoint, FakeServer, Scripted};
+/// use tokio_test::block_on;
+///
+/// let server = FakeServer::start();
+/// server.script(Endpoint::Lines, Scripted::error(500, "Internal Server Error"));
+///
+/// let client = server.rail();
+/// assert!(block_on(async { client.lines().await }).is_err());
+/// assert!(block_on(async { client.lines().await }).is_ok());
+/// ```
+pub struct FakeServer {
+    address: SocketAddr,
+    shared: Arc<Shared>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeServer {
+    /// Starts a server accepting [`API_KEY`].
+    ///
+    /// # Panics
+    /// If a local port can't be bound.
+    pub fn start() -> Self {
+        FakeServer::with_api_key(API_KEY)
+    }
+
+    /// Starts a server accepting `api_key`.
+    ///
+    /// # Panics
+    /// If a local port can't be bound.
+    pub fn with_api_key(api_key: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind a local port");
+        let address = listener.local_addr().expect("local address");
+
+        let shared = Arc::new(Shared {
+            api_key: api_key.to_string(),
+            state: Mutex::new(State::default()),
+        });
+        let (shutdown, shutdown_received) = oneshot::channel::<()>();
+
+        let serving = shared.clone();
+        let thread = thread::spawn(move || {
+            let mut runtime = tokio::runtime::Builder::new()
+                .basic_scheduler()
+                .enable_all()
+                .build()
+                .expect("build a runtime");
+
+            runtime.block_on(async move {
+                let service = make_service_fn(move |_| {
+                    let shared = serving.clone();
+
+                    async move {
+                        Ok::<_, Infallible>(service_fn(move |request| {
+                            let shared = shared.clone();
+
+                            async move { Ok::<_, Infallible>(shared.serve(request).await) }
+                        }))
+                    }
+                });
+
+                let server = Server::from_tcp(listener)
+                    .expect("listen on a local port")
+                    .serve(service);
+
+                // Open connections are dropped with the runtime, rather than waited on
+                tokio::select! {
+                    _ = server => {}
+                    _ = shutdown_received => {}
+                }
+            });
+        });
+
+        FakeServer {
+            address,
+            shared,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        }
+    }
+
+    /// Base URL of this server, e.g.: `http://127.0.0.1:49152`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// API key this server accepts.
+    pub fn api_key(&self) -> &str {
+        &self.shared.api_key
+    }
+
+    /// A MetroRail client sending requests to this server, with the key it accepts.
+    pub fn rail(&self) -> MetroRail {
+        MetroRail::with_base_url(self.api_key(), &self.url())
+    }
+
+    /// A MetroBus client sending requests to this server, with the key it accepts.
+    pub fn bus(&self) -> MetroBus {
+        MetroBus::with_base_url(self.api_key(), &self.url())
+    }
+
+    /// A client for both MetroRail and MetroBus sending requests to this server, with the key it accepts.
+    pub fn wmata(&self) -> Wmata {
+        Wmata::with_base_url(self.api_key(), &self.url())
+    }
+
+    /// Serves `response` for the next valid request to `endpoint`, in place of its fixture.
+    /// Responses scripted for the same endpoint are served in the order scripted.
+    ///
+    /// Requests with a wrong key or invalid parameters are still refused, rather than served a scripted response.
+    pub fn script(&self, endpoint: Endpoint, response: Scripted) {
+        self.shared
+            .state()
+            .scripts
+            .entry(endpoint)
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Waits `latency` before sending every response, in addition to any scripted latency.
+    pub fn set_latency(&self, latency: Duration) {
+        self.shared.state().latency = latency;
+    }
+
+    /// Requests received so far, in the order received.
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.shared.state().requests.clone()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Shared {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    async fn serve(&self, request: Request<Body>) -> Response<Body> {
+        let api_key = request
+            .headers()
+            .get("api_key")
+            .and_then(|api_key| api_key.to_str().ok());
+        let reply = self.reply(request.uri().path(), request.uri().query(), api_key);
+
+        if reply.latency > Duration::from_secs(0) {
+            tokio::time::delay_for(reply.latency).await;
+        }
+
+        let mut response = Response::new(Body::from(reply.body));
+        *response.status_mut() =
+            StatusCode::from_u16(reply.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    }
+
+    /// Records a request, then replies to it.
+    fn reply(&self, path: &str, query: Option<&str>, api_key: Option<&str>) -> Scripted {
+        let query: Vec<(String, String)> = query
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut pair = pair.splitn(2, '=');
+                (
+                    pair.next().unwrap_or("").to_string(),
+                    pair.next().unwrap_or("").to_string(),
+                )
+            })
+            .collect();
+
+        let mut state = self.state();
+        state.requests.push(Recorded {
+            path: path.to_string(),
+            query: query.clone(),
+            api_key: api_key.map(str::to_string),
+        });
+
+        let mut reply = match self.validate(path, &query, api_key) {
+            Ok(endpoint) => state
+                .scripts
+                .get_mut(&endpoint)
+                .and_then(VecDeque::pop_front)
+                .unwrap_or_else(|| Scripted::json(endpoint.fixture())),
+            Err(rejection) => rejection,
+        };
+
+        reply.latency += state.latency;
+        reply
+    }
+
+    /// The endpoint requested, or an error response if the request is invalid.
+    fn validate(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+        api_key: Option<&str>,
+    ) -> Result<Endpoint, Scripted> {
+        let (endpoint, stations) = Endpoint::from_path(path)
+            .ok_or_else(|| Scripted::gateway(404, "Resource not found"))?;
+
+        if api_key != Some(self.api_key.as_str()) {
+            return Err(Scripted::gateway(
+                401,
+                "Access denied due to invalid subscription key. Make sure to provide a valid key for an active subscription.",
+            ));
+        }
+
+        if let Some(stations) = stations {
+            if stations != "All"
+                && stations
+                    .split(',')
+                    .any(|station| station.parse::<Station>().is_err())
+            {
+                return Err(Scripted::error(
+                    400,
+                    &format!("Invalid station codes: {}", stations),
+                ));
+            }
+        }
+
+        let parameters = endpoint.parameters();
+
+        for (name, value) in query {
+            let parameter = parameters
+                .iter()
+                .find(|parameter| parameter.name == name)
+                .ok_or_else(|| Scripted::error(400, &format!("Unknown parameter: {}", name)))?;
+
+            if !parameter.kind.accepts(value) {
+                return Err(Scripted::error(
+                    400,
+                    &format!("Invalid {}: {}", parameter.name, value),
+                ));
+            }
+        }
+
+        let is_given = |name: &str| query.iter().any(|(given, _)| given == name);
+
+        if let Some(parameter) = parameters
+            .iter()
+            .find(|parameter| parameter.required && !is_given(parameter.name))
+        {
+            return Err(Scripted::error(
+                400,
+                &format!("Missing parameter: {}", parameter.name),
+            ));
+        }
+
+        let lat_long_radius = ["Lat", "Lon", "Radius"]
+            .iter()
+            .filter(|name| is_given(name))
+            .count();
+
+        if lat_long_radius != 0 && lat_long_radius != 3 {
+            return Err(Scripted::error(
+                400,
+                "Lat, Lon, and Radius must be given together",
+            ));
+        }
+
+        Ok(endpoint)
+    }
+}
+
+impl Kind {
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            Kind::Station => value.parse::<Station>().is_ok(),
+            Kind::Line => value.parse::<Line>().is_ok(),
+            Kind::Route => value.parse::<Route>().is_ok(),
+            Kind::Stop => !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()),
+            Kind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            Kind::Bool => value == "true" || value == "false",
+            Kind::Latitude => {
+                matches!(value.parse::<f64>(), Ok(latitude) if (-90.0..=90.0).contains(&latitude))
+            }
+            Kind::Longitude => {
+                matches!(value.parse::<f64>(), Ok(longitude) if (-180.0..=180.0).contains(&longitude))
+            }
+            Kind::Radius => value.parse::<u32>().is_ok(),
+            Kind::Json => value == "json",
+        }
+    }
+}
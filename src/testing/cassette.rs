@@ -0,0 +1,237 @@
+//! Record-and-replay of WMATA API responses, for regression tests against real payloads.
+//!
+//! A [`Cassette`] attached to a client with `with_cassette` sits in front of every request the client makes.
+//! In [`Mode::Record`] requests go to the API as usual, and each response is saved to the cassette's file.
+//! In [`Mode::Replay`] nothing is sent; responses are served from the file, matched by path and query.
+//!
+//! Cassettes are JSON, meant to be committed alongside tests. API keys are never written to them.
+//!
+//! # Example
+//! ```no_run
+//! use wmata::{testing::Cassette, MetroRail};
+//! use tokio_test::block_on;
+//!
+//! // Records when WMATA_CASSETTE=record, otherwise replays
+//! let cassette = Cassette::from_env("tests/cassettes/lines.json").unwrap();
+//! let client = MetroRail::new("9e38c3eab34c4e6c990828002828f5ed").with_cassette(cassette);
+//!
+//! let lines = block_on(async { client.lines().await });
+//! assert!(lines.is_ok());
+//! ```
+mod tests;
+
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Environment variable choosing the mode of [`Cassette::from_env`].
+/// `record` records, anything else (or nothing) replays.
+pub const CASSETTE_MODE_VARIABLE: &str = "WMATA_CASSETTE";
+
+/// Written in place of the API key, wherever it appears in a response.
+pub use crate::keys::REDACTED;
+
+/// Whether a cassette records or replays.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    /// Send requests to the API, saving responses.
+    Record,
+    /// Serve saved responses, without sending requests.
+    Replay,
+}
+
+/// A request and its response, as saved in a cassette.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Path requested, e.g.: `/Rail.svc/json/jLines`.
+    pub path: String,
+    /// Query parameters, in the order sent.
+    pub query: Vec<(String, String)>,
+    /// Response body. JSON responses are saved as JSON, for readable diffs.
+    pub response: Value,
+}
+
+impl Interaction {
+    fn matches(&self, path: &str, query: &[(String, String)]) -> bool {
+        self.path == path && sorted(&self.query) == sorted(query)
+    }
+
+    fn body(&self) -> String {
+        match &self.response {
+            Value::String(body) => body.clone(),
+            response => response.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+    /// Replayed so far, per interaction.
+    #[serde(skip)]
+    played: Vec<bool>,
+}
+
+/// Recorded requests and responses, backed by a file. Clones share the same recording.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    tape: Arc<Mutex<Tape>>,
+}
+
+impl Cassette {
+    /// A cassette recording to `path`, replacing anything recorded there before.
+    pub fn record<P: AsRef<Path>>(path: P) -> Self {
+        Cassette {
+            path: path.as_ref().to_path_buf(),
+            mode: Mode::Record,
+            tape: Arc::default(),
+        }
+    }
+
+    /// A cassette replaying what was recorded to `path`.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|err| {
+            Error::new(format!(
+                "Unable to read cassette {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        let mut tape: Tape = serde_json::from_str(&json)
+            .map_err(|err| Error::new(format!("Invalid cassette {}: {}", path.display(), err)))?;
+        tape.played = vec![false; tape.interactions.len()];
+
+        Ok(Cassette {
+            path: path.to_path_buf(),
+            mode: Mode::Replay,
+            tape: Arc::new(Mutex::new(tape)),
+        })
+    }
+
+    /// A cassette for `path`, recording if [`CASSETTE_MODE_VARIABLE`] is `record`, otherwise replaying.
+    pub fn from_env<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        match env::var(CASSETTE_MODE_VARIABLE) {
+            Ok(mode) if mode == "record" => Ok(Cassette::record(path)),
+            _ => Cassette::replay(path),
+        }
+    }
+
+    /// Whether this cassette records or replays.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Requests and responses recorded or loaded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.tape().interactions.clone()
+    }
+
+    /// The recorded response to a request for `path` with `query`.
+    ///
+    /// Requests repeated in a recording are answered in the order recorded.
+    /// Once every matching response has been served, the last is served again.
+    pub(crate) fn play(&self, path: &str, query: &[(String, String)]) -> Result<String, Error> {
+        let mut tape = self.tape();
+        let matching: Vec<usize> = tape
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.matches(path, query))
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = matching
+            .iter()
+            .find(|index| !tape.played[**index])
+            .or_else(|| matching.last())
+            .copied()
+            .ok_or_else(|| {
+                Error::new(format!(
+                    "No response to {}{} in cassette {}",
+                    path,
+                    query_string(query),
+                    self.path.display()
+                ))
+            })?;
+
+        tape.played[index] = true;
+
+        Ok(tape.interactions[index].body())
+    }
+
+    /// Saves a response to a request for `path` with `query`, with `api_keys` redacted.
+    pub(crate) fn save(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+        response: &str,
+        api_keys: &[&str],
+    ) -> Result<(), Error> {
+        let redact = |text: &str| {
+            api_keys
+                .iter()
+                .filter(|api_key| !api_key.is_empty())
+                .fold(text.to_string(), |text, api_key| {
+                    text.replace(api_key, REDACTED)
+                })
+        };
+        let response = redact(response);
+
+        let mut tape = self.tape();
+        tape.interactions.push(Interaction {
+            path: redact(path),
+            query: query
+                .iter()
+                .map(|(key, value)| (key.clone(), redact(value)))
+                .collect(),
+            response: serde_json::from_str(&response).unwrap_or(Value::String(response)),
+        });
+        tape.played.push(true);
+
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory).map_err(|err| Error::new(err.to_string()))?;
+        }
+
+        let json =
+            serde_json::to_string_pretty(&*tape).map_err(|err| Error::new(err.to_string()))?;
+
+        fs::write(&self.path, json + "\n").map_err(|err| {
+            Error::new(format!(
+                "Unable to write cassette {}: {}",
+                self.path.display(),
+                err
+            ))
+        })
+    }
+
+    fn tape(&self) -> std::sync::MutexGuard<'_, Tape> {
+        self.tape.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn sorted(query: &[(String, String)]) -> Vec<&(String, String)> {
+    let mut query: Vec<&(String, String)> = query.iter().collect();
+    query.sort();
+    query
+}
+
+fn query_string(query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return String::new();
+    }
+
+    let pairs: Vec<String> = query
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    format!("?{}", pairs.join("&"))
+}
@@ -0,0 +1,120 @@
+//! Tests for cassettes
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{
+    testing::{Endpoint, FakeServer, Scripted},
+    Line, MetroRail, Station,
+};
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+fn cassette_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!(
+        "wmata-cassette-{}-{}.json",
+        name,
+        std::process::id()
+    ))
+}
+
+#[cfg(test)]
+fn query(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_record_and_replay() {
+    let path = cassette_path("record-and-replay");
+
+    {
+        let server = FakeServer::start();
+        let client = server.rail().with_cassette(Cassette::record(&path));
+
+        block_on(async { client.stations_on(Some(Line::Red)).await }).unwrap();
+        block_on(async { client.next_trains(Station::A01).await }).unwrap();
+    }
+
+    let cassette = Cassette::replay(&path).unwrap();
+    assert_eq!(cassette.interactions()[0].path, "/Rail.svc/json/jStations");
+    assert_eq!(
+        cassette.interactions()[0].query,
+        query(&[("LineCode", "RD")])
+    );
+    assert_eq!(
+        cassette.interactions()[1].path,
+        "/StationPrediction.svc/json/GetPrediction/A01"
+    );
+
+    // The server is gone, so responses must come from the cassette
+    let client = MetroRail::with_base_url("key", "http://127.0.0.1:9").with_cassette(cassette);
+
+    let stations = block_on(async { client.stations_on(Some(Line::Red)).await }).unwrap();
+    assert_eq!(stations.stations[0].station, Station::A01);
+
+    let missing = block_on(async { client.stations_on(Some(Line::Blue)).await });
+    assert!(missing.unwrap_err().message.starts_with("No response to"));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_redacts_api_key() {
+    let path = cassette_path("redacts-api-key");
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::Lines,
+        Scripted::error(401, &format!("Invalid key {}", server.api_key())),
+    );
+    let client = server.rail().with_cassette(Cassette::record(&path));
+
+    assert!(block_on(async { client.lines().await }).is_err());
+
+    let recorded = fs::read_to_string(&path).unwrap();
+    assert!(!recorded.contains(server.api_key()));
+    assert!(recorded.contains(REDACTED));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_play_order() {
+    let cassette = Cassette::record(cassette_path("play-order"));
+    {
+        let mut tape = cassette.tape();
+        for response in ["1", "2"].iter() {
+            tape.interactions.push(Interaction {
+                path: "/Rail.svc/json/jPath".to_string(),
+                query: query(&[("FromStationCode", "A01"), ("ToStationCode", "A02")]),
+                response: Value::String(response.to_string()),
+            });
+            tape.played.push(false);
+        }
+    }
+
+    // Matched regardless of parameter order
+    let reversed = query(&[("ToStationCode", "A02"), ("FromStationCode", "A01")]);
+
+    assert_eq!(
+        cassette.play("/Rail.svc/json/jPath", &reversed).unwrap(),
+        "1"
+    );
+    assert_eq!(
+        cassette.play("/Rail.svc/json/jPath", &reversed).unwrap(),
+        "2"
+    );
+    assert_eq!(
+        cassette.play("/Rail.svc/json/jPath", &reversed).unwrap(),
+        "2"
+    );
+    assert!(cassette.play("/Rail.svc/json/jPath", &[]).is_err());
+}
+
+#[test]
+fn test_replay_missing_file() {
+    assert!(Cassette::replay(cassette_path("missing")).is_err());
+}
@@ -0,0 +1,287 @@
+//! Tests for the fake WMATA API
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use std::time::Instant;
+#[cfg(test)]
+use tokio_test::block_on;
+
+#[cfg(test)]
+fn shared() -> Shared {
+    Shared {
+        api_key: API_KEY.to_string(),
+        state: Mutex::new(State::default()),
+    }
+}
+
+#[test]
+fn test_path() {
+    assert_eq!(Endpoint::Lines.path(), "/Rail.svc/json/jLines");
+    assert_eq!(
+        Endpoint::NextBuses.path(),
+        "/NextBusService.svc/json/jPredictions"
+    );
+}
+
+#[test]
+fn test_from_path() {
+    assert_eq!(
+        Endpoint::from_path("/Rail.svc/json/jLines"),
+        Some((Endpoint::Lines, None))
+    );
+    assert_eq!(
+        Endpoint::from_path("/StationPrediction.svc/json/GetPrediction/A01,C01"),
+        Some((Endpoint::NextTrains, Some("A01,C01")))
+    );
+    assert_eq!(
+        Endpoint::from_path("/StationPrediction.svc/json/GetPrediction"),
+        None
+    );
+    assert_eq!(Endpoint::from_path("/Rail.svc/json/jTrains"), None);
+}
+
+#[test]
+fn test_every_endpoint_has_a_fixture() {
+    for endpoint in ENDPOINTS.iter() {
+        assert_eq!(
+            Endpoint::from_path(&endpoint.path()).map(|(endpoint, _)| endpoint),
+            if *endpoint == Endpoint::NextTrains {
+                None
+            } else {
+                Some(*endpoint)
+            }
+        );
+        assert!(serde_json::from_str::<serde_json::Value>(endpoint.fixture()).is_ok());
+    }
+}
+
+#[test]
+fn test_reply_fixture() {
+    let reply = shared().reply(
+        "/Rail.svc/json/jStations",
+        Some("LineCode=RD"),
+        Some(API_KEY),
+    );
+
+    assert_eq!(reply.status, 200);
+    assert_eq!(reply.body, Endpoint::Stations.fixture());
+}
+
+#[test]
+fn test_reply_api_key() {
+    let shared = shared();
+
+    assert_eq!(
+        shared.reply("/Rail.svc/json/jLines", None, None).status,
+        401
+    );
+    assert_eq!(
+        shared
+            .reply("/Rail.svc/json/jLines", None, Some("wrong"))
+            .status,
+        401
+    );
+    assert_eq!(
+        shared.reply("/Rail.svc/json/jTrains", None, None).status,
+        404
+    );
+}
+
+#[test]
+fn test_reply_parameters() {
+    let shared = shared();
+    let status = |path: &str, query: &str| shared.reply(path, Some(query), Some(API_KEY)).status;
+
+    assert_eq!(status("/Rail.svc/json/jPath", "FromStationCode=A01"), 400);
+    assert_eq!(
+        status(
+            "/Rail.svc/json/jPath",
+            "FromStationCode=A01&ToStationCode=Z99"
+        ),
+        400
+    );
+    assert_eq!(status("/Rail.svc/json/jLines", "LineCode=RD"), 400);
+    assert_eq!(status("/Bus.svc/json/jStops", "Lat=38.9&Lon=-77.0"), 400);
+    assert_eq!(
+        status("/Bus.svc/json/jStops", "Lat=138.9&Lon=-77.0&Radius=100"),
+        400
+    );
+    assert_eq!(
+        status("/Bus.svc/json/jStops", "Lat=38.9&Lon=-77.0&Radius=100"),
+        200
+    );
+    assert_eq!(
+        status("/Bus.svc/json/jRouteDetails", "RouteID=10A&Date=2019-10-32"),
+        400
+    );
+    assert_eq!(status("/Bus.svc/json/jStopSchedule", "StopID=stop"), 400);
+    assert_eq!(status("/TrainPositions/TrainPositions", ""), 400);
+    assert_eq!(
+        status("/TrainPositions/TrainPositions", "contentType=json"),
+        200
+    );
+    assert_eq!(
+        status("/StationPrediction.svc/json/GetPrediction/A01,Z99", ""),
+        400
+    );
+    assert_eq!(
+        status("/StationPrediction.svc/json/GetPrediction/All", ""),
+        200
+    );
+}
+
+#[test]
+fn test_reply_bus_positions_route() {
+    let shared = shared();
+    let status = |query: &str| {
+        shared
+            .reply("/Bus.svc/json/jBusPositions", Some(query), Some(API_KEY))
+            .status
+    };
+
+    // WMATA filters bus positions by RouteID. Route is ignored there, so is rejected here
+    assert_eq!(status("RouteID=10A"), 200);
+    assert_eq!(status("Route=10A"), 400);
+}
+
+#[test]
+fn test_reply_scripted() {
+    let shared = shared();
+    shared
+        .state()
+        .scripts
+        .entry(Endpoint::Lines)
+        .or_default()
+        .push_back(Scripted::error(500, "Internal Server Error"));
+
+    // Invalid requests don't use up scripted responses
+    assert_eq!(
+        shared.reply("/Rail.svc/json/jLines", None, None).status,
+        401
+    );
+    assert_eq!(
+        shared
+            .reply("/Rail.svc/json/jLines", None, Some(API_KEY))
+            .status,
+        500
+    );
+    assert_eq!(
+        shared
+            .reply("/Rail.svc/json/jLines", None, Some(API_KEY))
+            .status,
+        200
+    );
+    assert_eq!(shared.state().requests.len(), 3);
+}
+
+#[test]
+fn test_fixtures_deserialize() {
+    let server = FakeServer::start();
+    let rail = server.rail();
+    let bus = server.bus();
+
+    block_on(async {
+        rail.next_trains(Station::A01).await.unwrap();
+        rail.station_information(Station::A01).await.unwrap();
+        rail.parking_information(Station::A01).await.unwrap();
+        rail.path_from(Station::A01, Station::A02).await.unwrap();
+        rail.timings(Station::A01).await.unwrap();
+        rail.station_to_station(None, None).await.unwrap();
+        rail.lines().await.unwrap();
+        rail.entrances(crate::RadiusAtLatLong::new(1000, 38.8983, -77.0281))
+            .await
+            .unwrap();
+        rail.positions().await.unwrap();
+        rail.routes().await.unwrap();
+        rail.circuits().await.unwrap();
+        rail.elevator_and_escalator_incidents_at(None)
+            .await
+            .unwrap();
+        rail.incidents_at(None).await.unwrap();
+        rail.stations_on(None).await.unwrap();
+        bus.routes().await.unwrap();
+        bus.stops(None).await.unwrap();
+        bus.incidents_along(None).await.unwrap();
+        bus.positions_along(None, None).await.unwrap();
+        bus.path(Route::One0A, None).await.unwrap();
+        bus.route_schedule(Route::One0A, None, false).await.unwrap();
+        bus.next_buses(crate::Stop::new("1001195")).await.unwrap();
+        bus.stop_schedule(crate::Stop::new("1001195"), None)
+            .await
+            .unwrap();
+    });
+
+    assert_eq!(server.requests().len(), ENDPOINTS.len());
+}
+
+#[test]
+fn test_recorded() {
+    let server = FakeServer::start();
+    let client = server.rail();
+
+    block_on(async { client.stations_on(Some(Line::Red)).await }).unwrap();
+
+    assert_eq!(
+        server.requests(),
+        vec![Recorded {
+            path: "/Rail.svc/json/jStations".to_string(),
+            query: vec![("LineCode".to_string(), "RD".to_string())],
+            api_key: Some(API_KEY.to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_wrong_api_key() {
+    let server = FakeServer::with_api_key("right");
+    let client = MetroRail::with_base_url("wrong", &server.url());
+
+    let err = block_on(async { client.lines().await }).unwrap_err();
+
+    assert!(err.message.starts_with("Access denied"));
+}
+
+#[test]
+fn test_scripted_error() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::NextBuses,
+        Scripted::error(429, "Rate limit is exceeded."),
+    );
+    let client = server.bus();
+
+    let err = block_on(async { client.next_buses(crate::Stop::new("1001195")).await }).unwrap_err();
+
+    assert_eq!(err.message, "Rate limit is exceeded.");
+}
+
+#[test]
+fn test_scripted_json() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::RailIncidents,
+        Scripted::json(r#"{"Incidents": []}"#),
+    );
+    let client = server.rail();
+
+    let incidents = block_on(async { client.incidents_at(None).await }).unwrap();
+
+    assert!(incidents.incidents.is_empty());
+}
+
+#[test]
+fn test_latency() {
+    let server = FakeServer::start();
+    server.script(
+        Endpoint::Lines,
+        Scripted::json(Endpoint::Lines.fixture()).with_latency(Duration::from_millis(100)),
+    );
+    server.set_latency(Duration::from_millis(50));
+    let client = server.rail();
+
+    let start = Instant::now();
+    block_on(async { client.lines().await }).unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
@@ -0,0 +1,92 @@
+//! Spans for requests, with the `tracing` feature.
+//!
+//! Every fetch is a `fetch` span at INFO, with the endpoint's name and query parameters.
+//! The response's status, size, and latency, whether it came from the cache, and any error
+//! are recorded on the span as they're known. Retries, rejected keys, and rate limit waits are events within it.
+mod tests;
+
+use crate::{
+    bus::urls::URLs as BusURLs,
+    error::Error,
+    keys::{KeyPool, REDACTED},
+    rail::urls::URLs as RailURLs,
+    requests::{Request, WMATA_API},
+};
+use std::time::Instant;
+use tracing::{field, Span};
+
+/// Name of the endpoint at `path` from the URL enums, like `rail.NextTrains`.
+/// The path itself for endpoints without one.
+pub(crate) fn endpoint(path: &str) -> String {
+    RailURLs::ALL
+        .iter()
+        .find(|url| is_endpoint(path, &url.to_string()))
+        .map(|url| format!("rail.{:?}", url))
+        .or_else(|| {
+            BusURLs::ALL
+                .iter()
+                .find(|url| is_endpoint(path, &url.to_string()))
+                .map(|url| format!("bus.{:?}", url))
+        })
+        .unwrap_or_else(|| path.trim_start_matches(WMATA_API).to_string())
+}
+
+/// Whether `path` is the endpoint at `url`, which some endpoints follow with a parameter, like a station code.
+fn is_endpoint(path: &str, url: &str) -> bool {
+    matches!(path.strip_prefix(url), Some(rest) if rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Query parameters of `request`, with API keys redacted.
+pub(crate) fn query(request: &Request<'_>, keys: Option<&KeyPool>) -> String {
+    let is_key = |name: &str, value: &str| {
+        name.eq_ignore_ascii_case("api_key")
+            || (!value.is_empty()
+                && (value == request.api_key
+                    || matches!(keys, Some(keys) if keys.keys().any(|key| key == value))))
+    };
+
+    request
+        .query
+        .iter()
+        .flatten()
+        .map(|(name, value)| {
+            if is_key(name, value) {
+                format!("{}={}", name, REDACTED)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Span around fetching `request`.
+pub(crate) fn span(request: &Request<'_>, keys: Option<&KeyPool>) -> Span {
+    tracing::info_span!(
+        "fetch",
+        endpoint = %endpoint(request.path),
+        query = %query(request, keys),
+        status = field::Empty,
+        cache = field::Empty,
+        size = field::Empty,
+        latency_ms = field::Empty,
+        error = field::Empty,
+    )
+}
+
+/// Records the size of a fetched response on its span.
+pub(crate) fn response(span: &Span, response: &Result<String, Error>) {
+    if let Ok(body) = response {
+        span.record("size", body.len() as u64);
+    }
+}
+
+/// Records the latency of a fetch started at `started` on its span, and its error if it failed,
+/// including while deserializing.
+pub(crate) fn finish(span: &Span, started: Instant, error: Option<&Error>) {
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+
+    if let Some(err) = error {
+        span.record("error", err.message.as_str());
+    }
+}
@@ -0,0 +1,208 @@
+//! Tests for request spans
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use crate::{
+    cache::CachePolicy,
+    retry::RetryPolicy,
+    testing::{Endpoint, FakeServer, Scripted},
+    MetroBus, MetroRail, Station,
+};
+#[cfg(test)]
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+#[cfg(test)]
+use tokio_test::block_on;
+#[cfg(test)]
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// Names and values of fields, in the order recorded.
+#[cfg(test)]
+type Fields = Vec<(String, String)>;
+
+/// Fields recorded on spans, and messages of events, in the order seen. Spans are entered on one thread.
+#[cfg(test)]
+#[derive(Clone, Default)]
+struct Recorded {
+    spans: Arc<Mutex<Vec<Fields>>>,
+    events: Arc<Mutex<Vec<String>>>,
+    metadata: Arc<Mutex<Vec<&'static Metadata<'static>>>>,
+    entered: Arc<Mutex<Vec<span::Id>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+#[cfg(test)]
+struct Visitor<'a>(&'a mut Fields);
+
+#[cfg(test)]
+impl Visit for Visitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+#[cfg(test)]
+impl Subscriber for Recorded {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with("wmata")
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = vec![];
+        span.record(&mut Visitor(&mut fields));
+        self.spans.lock().unwrap().push(fields);
+        self.metadata.lock().unwrap().push(span.metadata());
+
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut Visitor(&mut spans[span.into_u64() as usize - 1]));
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = vec![];
+        event.record(&mut Visitor(&mut fields));
+
+        if let Some((_, message)) = fields.iter().find(|(name, _)| name == "message") {
+            self.events.lock().unwrap().push(message.clone());
+        }
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.entered.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &span::Id) {
+        self.entered.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        match self.entered.lock().unwrap().last() {
+            Some(span) => tracing_core::span::Current::new(
+                span.clone(),
+                self.metadata.lock().unwrap()[span.into_u64() as usize - 1],
+            ),
+            None => tracing_core::span::Current::none(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Recorded {
+    fn field(&self, span: usize, name: &str) -> Option<String> {
+        self.spans.lock().unwrap()[span]
+            .iter()
+            .rev()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+#[test]
+fn test_endpoint() {
+    assert_eq!(
+        endpoint("https://api.wmata.com/StationPrediction.svc/json/GetPrediction"),
+        "rail.NextTrains"
+    );
+    assert_eq!(
+        endpoint("https://api.wmata.com/Rail.svc/json/jStationTimes/A01"),
+        "rail.Timings"
+    );
+    assert_eq!(
+        endpoint("https://api.wmata.com/Incidents.svc/json/BusIncidents"),
+        "bus.Incidents"
+    );
+    assert_eq!(
+        endpoint("https://api.wmata.com/gtfs/rail-gtfsrt-tripupdates.pb"),
+        "/gtfs/rail-gtfsrt-tripupdates.pb"
+    );
+}
+
+#[test]
+fn test_query_redacts_keys() {
+    let request = Request::new(
+        "9e38c3eab34c4e6c990828002828f5ed",
+        "https://api.wmata.com/Bus.svc/json/jStops",
+        Some(vec![
+            ("Lat", "38.8".to_string()),
+            ("api_key", "other".to_string()),
+            ("Echo", "9e38c3eab34c4e6c990828002828f5ed".to_string()),
+            ("Pooled", "pooled-key".to_string()),
+        ]),
+    );
+    let pool = KeyPool::new(vec!["pooled-key".to_string()]).unwrap();
+
+    assert_eq!(
+        query(&request, Some(&pool)),
+        "Lat=38.8&api_key=[REDACTED]&Echo=[REDACTED]&Pooled=[REDACTED]"
+    );
+}
+
+#[test]
+fn test_fetch_span() {
+    let server = FakeServer::start();
+    let client = MetroRail::builder(server.api_key())
+        .base_url(&server.url())
+        .cache(CachePolicy::default())
+        .build()
+        .unwrap();
+    let recorded = Recorded::default();
+
+    tracing::subscriber::with_default(recorded.clone(), || {
+        block_on(async {
+            client.next_trains(Station::A01).await.unwrap();
+            client.next_trains(Station::A01).await.unwrap();
+        })
+    });
+
+    assert_eq!(recorded.field(0, "endpoint").unwrap(), "rail.NextTrains");
+    assert_eq!(recorded.field(0, "status").unwrap(), "200");
+    assert_eq!(recorded.field(0, "cache").unwrap(), "miss");
+    assert!(recorded.field(0, "size").unwrap().parse::<u64>().unwrap() > 0);
+    assert!(recorded.field(0, "latency_ms").is_some());
+    assert!(recorded.field(0, "error").is_none());
+
+    assert_eq!(recorded.field(1, "cache").unwrap(), "hit");
+    assert!(recorded.field(1, "status").is_none());
+}
+
+#[test]
+fn test_retry_events() {
+    let server = FakeServer::start();
+    server.script(Endpoint::BusRoutes, Scripted::error(503, "Unavailable"));
+    server.script(Endpoint::BusRoutes, Scripted::error(503, "Unavailable"));
+    let client = MetroBus::builder(server.api_key())
+        .base_url(&server.url())
+        .retry(RetryPolicy::new(1, Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    let recorded = Recorded::default();
+
+    tracing::subscriber::with_default(recorded.clone(), || {
+        block_on(async { client.routes().await }).unwrap_err();
+    });
+
+    assert_eq!(*recorded.events.lock().unwrap(), ["retrying request"]);
+    assert_eq!(recorded.field(0, "endpoint").unwrap(), "bus.Routes");
+    assert_eq!(recorded.field(0, "status").unwrap(), "503");
+    assert_eq!(recorded.field(0, "error").unwrap(), "Unavailable");
+}
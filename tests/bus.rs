@@ -1,5 +1,19 @@
+use std::env;
 use tokio_test::block_on;
-use wmata::{MetroBus, Route};
+use wmata::{testing::Cassette, MetroBus, Route};
+
+/// A client replaying `tests/cassettes/bus/{name}.json`.
+/// Run with `WMATA_CASSETTE=record` and `WMATA_API_KEY` set to record it again.
+fn client(name: &str) -> MetroBus {
+    let cassette = Cassette::from_env(format!(
+        "{}/tests/cassettes/bus/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+    .unwrap();
+
+    MetroBus::new(&env::var("WMATA_API_KEY").unwrap_or_default()).with_cassette(cassette)
+}
 
 #[test]
 fn test_constructor() {
@@ -10,7 +24,7 @@ fn test_constructor() {
 
 #[test]
 fn test_routes() {
-    let client: MetroBus = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("routes");
     let routes = block_on(async { client.routes().await });
 
     assert!(routes.is_ok());
@@ -18,7 +32,7 @@ fn test_routes() {
 
 #[test]
 fn test_positions_along() {
-    let client: MetroBus = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("positions_along");
     let positions = block_on(async { client.positions_along(Some(Route::One0A), None).await });
 
     assert!(positions.is_ok());
@@ -26,7 +40,7 @@ fn test_positions_along() {
 
 #[test]
 fn test_path() {
-    let client: MetroBus = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("route_schedule");
     let route_schedule = block_on(async { client.route_schedule(Route::A2, None, false).await });
 
     assert!(route_schedule.is_ok());
@@ -1,5 +1,19 @@
+use std::env;
 use tokio_test::block_on;
-use wmata::{Line, MetroRail, Station};
+use wmata::{testing::Cassette, Line, MetroRail, Station};
+
+/// A client replaying `tests/cassettes/rail/{name}.json`.
+/// Run with `WMATA_CASSETTE=record` and `WMATA_API_KEY` set to record it again.
+fn client(name: &str) -> MetroRail {
+    let cassette = Cassette::from_env(format!(
+        "{}/tests/cassettes/rail/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+    .unwrap();
+
+    MetroRail::new(&env::var("WMATA_API_KEY").unwrap_or_default()).with_cassette(cassette)
+}
 
 #[test]
 fn test_constructor() {
@@ -10,7 +24,7 @@ fn test_constructor() {
 
 #[test]
 fn test_lines() {
-    let client: MetroRail = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("lines");
     let lines = block_on(async { client.lines().await });
 
     assert!(lines.is_ok());
@@ -18,7 +32,7 @@ fn test_lines() {
 
 #[test]
 fn test_station_to_station() {
-    let client: MetroRail = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("station_to_station");
     let station_to_station = block_on(async {
         client
             .station_to_station(Some(Station::A01), Some(Station::A02))
@@ -30,7 +44,7 @@ fn test_station_to_station() {
 
 #[test]
 fn test_stations_on() {
-    let client: MetroRail = "9e38c3eab34c4e6c990828002828f5ed".parse().unwrap();
+    let client = client("stations_on");
     let stations = block_on(async { client.stations_on(Some(Line::Red)).await });
 
     assert!(stations.is_ok());